@@ -0,0 +1,87 @@
+//! Throughput of `StringStream` on multi-megabyte inputs
+//!
+//! Run with `cargo bench --bench string_stream`
+
+use std::hint::black_box;
+use std::io::{Cursor, Write};
+use std::time::{Duration, Instant};
+
+use rust_utils::string_stream::StringStream;
+
+const INPUT_SIZE: usize = 16 * 1024 * 1024;
+const RUNS: usize = 5;
+
+fn ascii_input() -> Vec<u8> {
+    let line = b"2024-01-01T00:00:00Z INFO request handled path=/api/v1/items status=200\n";
+    line.iter().copied().cycle().take(INPUT_SIZE).collect()
+}
+
+fn mixed_input() -> Vec<u8> {
+    let line = "Grüße aus Köln, ✓ erledigt, 😉 bis später – καλημέρα κόσμε\n";
+    let mut data = line.repeat(INPUT_SIZE / line.len()).into_bytes();
+    data.truncate(data.len() - data.len() % line.len());
+    data
+}
+
+/// Best of `RUNS` to reduce noise
+fn measure(name: &str, bytes: usize, mut f: impl FnMut() -> usize) {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        black_box(f());
+        best = best.min(start.elapsed());
+    }
+
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    println!(
+        "{name:<40} {:>8.2} ms {:>10.1} MiB/s",
+        best.as_secs_f64() * 1000.0,
+        mb / best.as_secs_f64()
+    );
+}
+
+fn bench_input(label: &str, data: &[u8]) {
+    measure(&format!("{label}: chars"), data.len(), || {
+        StringStream::new(Cursor::new(data)).count()
+    });
+
+    measure(&format!("{label}: lines"), data.len(), || {
+        StringStream::new(Cursor::new(data)).lines().count()
+    });
+
+    measure(&format!("{label}: for_each_line"), data.len(), || {
+        let mut count = 0;
+        StringStream::new(Cursor::new(data)).for_each_line(|_| count += 1);
+        count
+    });
+
+    let path = std::env::temp_dir().join(format!("string_stream_bench_{label}.txt"));
+    std::fs::File::create(&path)
+        .and_then(|mut f| f.write_all(data))
+        .expect("Failed to write temp file");
+
+    measure(
+        &format!("{label}: lines (unbuffered File)"),
+        data.len(),
+        || {
+            let file = std::fs::File::open(&path).expect("Failed to open temp file");
+            StringStream::new(file).lines().count()
+        },
+    );
+    let _ = std::fs::remove_file(&path);
+
+    measure(
+        &format!("{label}: str::lines (reference)"),
+        data.len(),
+        || {
+            std::str::from_utf8(data)
+                .map(|s| s.lines().count())
+                .unwrap_or(0)
+        },
+    );
+}
+
+fn main() {
+    bench_input("ascii", &ascii_input());
+    bench_input("mixed", &mixed_input());
+}
@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::io::{Read, Write};
 use std::mem::size_of;
 
 macro_rules! impl_from_bytes {
@@ -28,6 +28,34 @@ macro_rules! impl_from_bytes {
     }
 }
 
+macro_rules! impl_to_bytes {
+    ($t: ty) => {
+        impl ToBytes for $t {
+            type Error = std::io::Error;
+            fn to_bytes_ne(&self, mut data: impl Write) -> Result<(), Self::Error> {
+                data.write_all(&self.to_ne_bytes())
+            }
+            fn to_bytes_le(&self, mut data: impl Write) -> Result<(), Self::Error> {
+                data.write_all(&self.to_le_bytes())
+            }
+            fn to_bytes_be(&self, mut data: impl Write) -> Result<(), Self::Error> {
+                data.write_all(&self.to_be_bytes())
+            }
+        }
+    };
+
+    ($($t: ty),+) => {
+        $(impl_to_bytes!($t);)+
+    }
+}
+
+/// Byte order used when reading or writing multi-byte values
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
 pub trait FromBytes: Sized {
     type Error;
     fn from_bytes_ne(data: impl Read) -> Result<Self, Self::Error>;
@@ -39,6 +67,17 @@ impl_from_bytes!(i8, i16, i32, i64, i128);
 impl_from_bytes!(u8, u16, u32, u64, u128);
 impl_from_bytes!(f32, f64);
 
+pub trait ToBytes {
+    type Error;
+    fn to_bytes_ne(&self, data: impl Write) -> Result<(), Self::Error>;
+    fn to_bytes_le(&self, data: impl Write) -> Result<(), Self::Error>;
+    fn to_bytes_be(&self, data: impl Write) -> Result<(), Self::Error>;
+}
+
+impl_to_bytes!(i8, i16, i32, i64, i128);
+impl_to_bytes!(u8, u16, u32, u64, u128);
+impl_to_bytes!(f32, f64);
+
 impl FromBytes for bool {
     type Error = std::io::Error;
 
@@ -58,6 +97,20 @@ impl FromBytes for bool {
     }
 }
 
+impl ToBytes for bool {
+    type Error = std::io::Error;
+
+    fn to_bytes_ne(&self, mut data: impl Write) -> Result<(), Self::Error> {
+        data.write_all(&[*self as u8])
+    }
+    fn to_bytes_le(&self, data: impl Write) -> Result<(), Self::Error> {
+        self.to_bytes_ne(data)
+    }
+    fn to_bytes_be(&self, data: impl Write) -> Result<(), Self::Error> {
+        self.to_bytes_ne(data)
+    }
+}
+
 pub fn from_bytes_ne<T: FromBytes>(data: impl Read) -> Result<T, T::Error> {
     T::from_bytes_ne(data)
 }
@@ -70,11 +123,23 @@ pub fn from_bytes_be<T: FromBytes>(data: impl Read) -> Result<T, T::Error> {
     T::from_bytes_be(data)
 }
 
+pub fn to_bytes_ne<T: ToBytes>(value: &T, data: impl Write) -> Result<(), T::Error> {
+    value.to_bytes_ne(data)
+}
+
+pub fn to_bytes_le<T: ToBytes>(value: &T, data: impl Write) -> Result<(), T::Error> {
+    value.to_bytes_le(data)
+}
+
+pub fn to_bytes_be<T: ToBytes>(value: &T, data: impl Write) -> Result<(), T::Error> {
+    value.to_bytes_be(data)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::byte_readers::from_bytes_le;
+    use crate::byte_readers::{from_bytes_le, to_bytes_be};
 
-    use super::FromBytes;
+    use super::{FromBytes, ToBytes};
     use std::io::{Cursor, Seek};
 
     #[test]
@@ -91,4 +156,24 @@ mod tests {
         let x: i32 = from_bytes_le(&mut data).unwrap();
         assert_eq!(x, 1);
     }
+
+    #[test]
+    fn write_bytes_test() {
+        let mut data = Vec::new();
+        1i32.to_bytes_le(&mut data).unwrap();
+        assert_eq!(data, [1, 0, 0, 0]);
+
+        data.clear();
+        to_bytes_be(&1i32, &mut data).unwrap();
+        assert_eq!(data, [0, 0, 0, 1]);
+
+        data.clear();
+        true.to_bytes_ne(&mut data).unwrap();
+        assert_eq!(data, [1]);
+
+        data.clear();
+        1.5f64.to_bytes_le(&mut data).unwrap();
+        let x = f64::from_bytes_le(Cursor::new(data)).unwrap();
+        assert_eq!(x, 1.5);
+    }
 }
@@ -0,0 +1,587 @@
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::rc::Rc;
+
+use crate::string_stream::{DecodeError, DecodeMode, LineConfig, Position, StringStream};
+
+/// Dialect understood by a `CsvReader`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvConfig {
+    /// Character between fields
+    pub delimiter: char,
+    /// Character around quoted fields, doubled to escape it
+    pub quote: char,
+    /// First record holds the field names
+    pub has_header: bool,
+    /// Allow records with a different number of fields than the first
+    pub flexible: bool,
+}
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: '"',
+            has_header: false,
+            flexible: false,
+        }
+    }
+}
+impl CsvConfig {
+    /// Tab separated values
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    /// Source could not be decoded on `line`
+    Decode { line: usize, error: DecodeError },
+    /// Quote inside an unquoted field
+    StrayQuote { position: Position },
+    /// Something other than a delimiter or line end after a closing quote
+    UnexpectedChar { position: Position, ch: char },
+    /// Quoted field starting at `position` is never closed
+    UnterminatedQuote { position: Position },
+    /// Record starting on `line` doesn't have the expected number of fields
+    FieldCount {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+impl CsvError {
+    /// Line of the source the error is on
+    pub fn line(&self) -> usize {
+        match self {
+            Self::StrayQuote { position }
+            | Self::UnexpectedChar { position, .. }
+            | Self::UnterminatedQuote { position } => position.line,
+            Self::Decode { line, .. } | Self::FieldCount { line, .. } => *line,
+        }
+    }
+}
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode { line, error } => write!(f, "{error} on line {line}"),
+            Self::StrayQuote { position } => write!(f, "quote in unquoted field at {position}"),
+            Self::UnexpectedChar { position, ch } => {
+                write!(f, "unexpected {ch:?} after closing quote at {position}")
+            }
+            Self::UnterminatedQuote { position } => {
+                write!(f, "quoted field at {position} is never closed")
+            }
+            Self::FieldCount {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "record on line {line} has {actual} fields, expected {expected}"
+            ),
+        }
+    }
+}
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Fields of one record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Line the record starts on
+    pub line: usize,
+    pub fields: Vec<String>,
+    headers: Option<Rc<[String]>>,
+}
+impl Record {
+    /// Field under the header `name`, the first one if names repeat
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let index = self.headers.as_ref()?.iter().position(|x| x == name)?;
+        self.fields.get(index).map(String::as_str)
+    }
+
+    /// Header row of the source, if it has one
+    pub fn headers(&self) -> Option<&[String]> {
+        self.headers.as_deref()
+    }
+
+    /// Pairs of header and field
+    pub fn named(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers
+            .iter()
+            .flat_map(|x| x.iter())
+            .zip(&self.fields)
+            .map(|(name, field)| (name.as_str(), field.as_str()))
+    }
+}
+
+/// Iterator over the records of RFC 4180 CSV
+///
+/// Records end at `\n`, `\r\n` or a lone `\r`, blank lines are skipped.
+/// Quoted fields may contain delimiters and line breaks
+///
+/// ---
+/// After a malformed record reading continues on the next line,
+/// a decoding error ends iteration
+pub struct CsvReader<T>
+where
+    T: Read,
+{
+    stream: StringStream<T>,
+    config: CsvConfig,
+    headers: Option<Rc<[String]>>,
+    /// Number of fields every record must have
+    width: Option<usize>,
+    /// Header row has been read, or there is none
+    started: bool,
+    done: bool,
+    /// Position of the last character read
+    last: Position,
+}
+impl<T> CsvReader<T>
+where
+    T: Read,
+{
+    /// Read records from `stream`, replacing its `LineConfig`
+    /// so that line numbers count a lone `\r` as well
+    pub fn new(stream: StringStream<T>) -> Self {
+        let lines = LineConfig {
+            cr: true,
+            ..Default::default()
+        };
+        Self {
+            stream: stream.with_line_config(lines),
+            config: CsvConfig::default(),
+            headers: None,
+            width: None,
+            started: false,
+            done: false,
+            last: Position::default(),
+        }
+    }
+
+    pub fn from_reader(x: T) -> Self {
+        Self::new(StringStream::new(x))
+    }
+
+    pub fn with_config(mut self, config: CsvConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Field names from the header row, reading it if needed.
+    /// `None` without `CsvConfig::has_header` or for an empty source
+    pub fn headers(&mut self) -> Result<Option<&[String]>, CsvError> {
+        self.read_headers()?;
+        Ok(self.headers.as_deref())
+    }
+
+    fn read_headers(&mut self) -> Result<(), CsvError> {
+        if self.started {
+            return Ok(());
+        }
+        self.started = true;
+        if self.config.has_header {
+            if let Some(fields) = self.read_fields().inspect_err(|_| self.recover())? {
+                self.width = Some(fields.len());
+                self.headers = Some(fields.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn bump(&mut self) -> Result<Option<char>, CsvError> {
+        self.last = self.stream.position();
+        match self.stream.try_next() {
+            None => Ok(None),
+            Some(Ok(ch)) => Ok(Some(ch)),
+            Some(Err(DecodeError::InvalidSequence { .. }))
+                if self.stream.mode() == DecodeMode::Lossy =>
+            {
+                Ok(Some(char::REPLACEMENT_CHARACTER))
+            }
+            Some(Err(error)) => {
+                self.done = true;
+                Err(CsvError::Decode {
+                    line: self.last.line,
+                    error,
+                })
+            }
+        }
+    }
+
+    /// Skip to the end of the line after a malformed record,
+    /// the `\n` of a `\r\n` is skipped as a blank line
+    fn recover(&mut self) {
+        while !self.done {
+            match self.bump() {
+                Ok(Some('\n' | '\r')) | Ok(None) | Err(_) => break,
+                Ok(Some(_)) => {}
+            }
+        }
+    }
+
+    /// Read the fields of one record, `None` at the end of the source
+    fn read_fields(&mut self) -> Result<Option<Vec<String>>, CsvError> {
+        let CsvConfig {
+            delimiter, quote, ..
+        } = self.config;
+
+        let mut ch = loop {
+            match self.bump()? {
+                Some('\n' | '\r') => {}
+                None => return Ok(None),
+                x => break x,
+            }
+        };
+
+        let mut fields = Vec::new();
+        loop {
+            let mut field = String::new();
+            if ch == Some(quote) {
+                let open = self.last;
+                loop {
+                    match self.bump()? {
+                        None => return Err(CsvError::UnterminatedQuote { position: open }),
+                        Some(x) if x == quote => {
+                            if self.stream.peek() != Some(quote) {
+                                break;
+                            }
+                            self.bump()?;
+                            field.push(quote);
+                        }
+                        Some(x) => field.push(x),
+                    }
+                }
+
+                ch = self.bump()?;
+                match ch {
+                    None | Some('\n' | '\r') => {}
+                    Some(x) if x == delimiter => {}
+                    Some(x) => {
+                        return Err(CsvError::UnexpectedChar {
+                            position: self.last,
+                            ch: x,
+                        })
+                    }
+                }
+            } else {
+                while let Some(x) = ch {
+                    if x == delimiter || x == '\n' || x == '\r' {
+                        break;
+                    }
+                    if x == quote {
+                        return Err(CsvError::StrayQuote {
+                            position: self.last,
+                        });
+                    }
+                    field.push(x);
+                    ch = self.bump()?;
+                }
+            }
+            fields.push(field);
+
+            match ch {
+                Some(x) if x == delimiter => ch = self.bump()?,
+                Some('\r') => {
+                    if self.stream.peek() == Some('\n') {
+                        self.bump()?;
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+        Ok(Some(fields))
+    }
+}
+impl<T> Iterator for CsvReader<T>
+where
+    T: Read,
+{
+    type Item = Result<Record, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Err(e) = self.read_headers() {
+            return Some(Err(e));
+        }
+
+        // Look ahead so the position is after any byte order mark
+        while matches!(self.stream.peek(), Some('\n' | '\r')) {
+            self.stream.next();
+        }
+        let line = self.stream.position().line;
+
+        let fields = match self.read_fields() {
+            Ok(Some(x)) => x,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.recover();
+                return Some(Err(e));
+            }
+        };
+
+        let expected = *self.width.get_or_insert(fields.len());
+        if !self.config.flexible && fields.len() != expected {
+            return Some(Err(CsvError::FieldCount {
+                line,
+                expected,
+                actual: fields.len(),
+            }));
+        }
+
+        Some(Ok(Record {
+            line,
+            fields,
+            headers: self.headers.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::{CsvConfig, CsvError, CsvReader};
+    use crate::string_stream::{DecodeError, DecodeMode, StringStream};
+
+    fn records(data: &str, config: CsvConfig) -> Vec<Result<Vec<String>, usize>> {
+        CsvReader::new(StringStream::with_capacity(4, data.as_bytes()))
+            .with_config(config)
+            .map(|x| x.map(|x| x.fields).map_err(|e| e.line()))
+            .collect()
+    }
+
+    fn row(fields: &[&str]) -> Result<Vec<String>, usize> {
+        Ok(fields.iter().map(|x| x.to_string()).collect())
+    }
+
+    #[test]
+    fn simple_test() {
+        let data = "a,b,c\r\n1,2,3\r\n,,\r\n";
+        assert_eq!(
+            records(data, CsvConfig::default()),
+            [
+                row(&["a", "b", "c"]),
+                row(&["1", "2", "3"]),
+                row(&["", "", ""])
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_test() {
+        let data = "\"a,b\",\"say \"\"hi\"\"\",\"\"\n\"multi\r\nline\nfield\",x,\"\u{2713}\"\nlast,\"\",end";
+        assert_eq!(
+            records(data, CsvConfig::default()),
+            [
+                row(&["a,b", "say \"hi\"", ""]),
+                row(&["multi\r\nline\nfield", "x", "\u{2713}"]),
+                row(&["last", "", "end"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_endings_test() {
+        // Blank lines are skipped, a trailing delimiter is an empty field
+        let data = "a,b\n\n\r\nc,d\re,\n";
+        assert_eq!(
+            records(data, CsvConfig::default()),
+            [row(&["a", "b"]), row(&["c", "d"]), row(&["e", ""])]
+        );
+        assert!(records("", CsvConfig::default()).is_empty());
+        assert!(records("\n\r\n", CsvConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn tsv_test() {
+        let data = "name\tvalue\n\"tab\there\"\t\"a,b\"\n";
+        assert_eq!(
+            records(data, CsvConfig::tsv()),
+            [row(&["name", "value"]), row(&["tab\there", "a,b"])]
+        );
+
+        let config = CsvConfig {
+            delimiter: ';',
+            quote: '\'',
+            ..Default::default()
+        };
+        assert_eq!(records("'x;y';'it''s'\n", config), [row(&["x;y", "it's"])]);
+    }
+
+    #[test]
+    fn header_test() {
+        let data = "id,name,id\n1,\"Smith, J\",9\n\n2,Doe,8\n";
+        let config = CsvConfig {
+            has_header: true,
+            ..Default::default()
+        };
+        let mut reader = CsvReader::from_reader(data.as_bytes()).with_config(config);
+
+        assert_eq!(
+            reader.headers().unwrap().unwrap(),
+            ["id", "name", "id"].map(String::from)
+        );
+        let records = reader.map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+
+        assert_eq!(records[0].line, 2);
+        assert_eq!(records[0].get("name"), Some("Smith, J"));
+        assert_eq!(records[0].get("id"), Some("1"));
+        assert_eq!(records[0].get("missing"), None);
+        assert_eq!(records[1].line, 4);
+        assert_eq!(
+            records[1].named().collect::<Vec<_>>(),
+            [("id", "2"), ("name", "Doe"), ("id", "8")]
+        );
+
+        // Headers are read on the first record too
+        let mut reader = CsvReader::from_reader(data.as_bytes()).with_config(config);
+        assert_eq!(
+            reader.next().unwrap().unwrap().fields,
+            ["1", "Smith, J", "9"]
+        );
+        assert_eq!(reader.headers().unwrap().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn malformed_test() {
+        let data = "a,b\nx\"y,z\n\"ok\",\"multi\nline\"\n\"bad\"x,1\nc,d,e\n\"open,2\n3,4\n";
+        let mut reader = CsvReader::new(StringStream::with_capacity(4, data.as_bytes()));
+
+        assert_eq!(reader.next().unwrap().unwrap().fields, ["a", "b"]);
+        let Err(CsvError::StrayQuote { position }) = reader.next().unwrap() else {
+            panic!("expected stray quote");
+        };
+        assert_eq!((position.line, position.column), (2, 2));
+
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(
+            (record.line, record.fields),
+            (3, vec!["ok".into(), "multi\nline".into()])
+        );
+
+        let Err(CsvError::UnexpectedChar { position, ch }) = reader.next().unwrap() else {
+            panic!("expected unexpected char");
+        };
+        assert_eq!((position.line, position.column, ch), (5, 6, 'x'));
+
+        let Err(CsvError::FieldCount {
+            line,
+            expected,
+            actual,
+        }) = reader.next().unwrap()
+        else {
+            panic!("expected field count");
+        };
+        assert_eq!((line, expected, actual), (6, 2, 3));
+
+        let Err(e @ CsvError::UnterminatedQuote { .. }) = reader.next().unwrap() else {
+            panic!("expected unterminated quote");
+        };
+        assert_eq!(e.line(), 7);
+        assert_eq!(
+            e.to_string(),
+            "quoted field at line 7, column 1 is never closed"
+        );
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn flexible_test() {
+        let data = "a,b\nc\nd,e,f\n";
+        assert_eq!(
+            records(data, CsvConfig::default()),
+            [row(&["a", "b"]), Err(2), Err(3)]
+        );
+
+        let config = CsvConfig {
+            flexible: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            records(data, config),
+            [row(&["a", "b"]), row(&["c"]), row(&["d", "e", "f"])]
+        );
+    }
+
+    #[test]
+    fn decode_error_test() {
+        let data = b"a,b\nc,\xFF\nd,e\n";
+        let results = CsvReader::from_reader(&data[..]).collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        let error = results[1].as_ref().unwrap_err();
+        assert!(matches!(error, CsvError::Decode { line: 2, .. }));
+        assert_eq!(
+            error.to_string(),
+            "invalid sequence [FF] at byte 6 on line 2"
+        );
+
+        let stream = StringStream::new(&data[..]).with_mode(DecodeMode::Lossy);
+        let fields = CsvReader::new(stream)
+            .map(|x| x.unwrap().fields)
+            .collect::<Vec<_>>();
+        assert_eq!(fields[1], ["c", "\u{FFFD}"]);
+        assert_eq!(fields.len(), 3);
+
+        // Failed reads end iteration, whatever the mode
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("broken"))
+            }
+        }
+        let source = b"a,b\nc".chain(Failing);
+        let stream = StringStream::with_capacity(4, source).with_mode(DecodeMode::Lossy);
+        let results = CsvReader::new(stream).collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            &results[1],
+            Err(CsvError::Decode {
+                line: 2,
+                error: DecodeError::Io { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn recover_cr_test() {
+        // Classic Mac OS line endings, recovery stops at the lone `\r`
+        let data = "a,b\rx\"y,z\r\"bad\"x\rc,d\r";
+        assert_eq!(
+            records(data, CsvConfig::default()),
+            [row(&["a", "b"]), Err(2), Err(3), row(&["c", "d"])]
+        );
+        let data = "a,b\r\nx\"y\r\nc,d\r\n";
+        assert_eq!(
+            records(data, CsvConfig::default()),
+            [row(&["a", "b"]), Err(2), row(&["c", "d"])]
+        );
+    }
+
+    #[test]
+    fn bom_test() {
+        let data = "\u{FEFF}a,b\n";
+        let stream = StringStream::new(data.as_bytes()).detect_encoding();
+        let record = CsvReader::new(stream).next().unwrap().unwrap();
+        assert_eq!(record.fields, ["a", "b"]);
+        assert_eq!(record.line, 1);
+    }
+}
@@ -0,0 +1,663 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+
+use crate::string_stream::{DecodeError, LineError, LineTooLong, StringStream};
+
+/// What `IniReader::into_map` does with a key that is already in its section
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Fail with `IniError::DuplicateKey`
+    #[default]
+    Error,
+    /// Keep the first value
+    First,
+    /// Keep the last value, in the place of the first
+    Last,
+}
+
+/// Dialect understood by an `IniReader`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniConfig {
+    /// Characters starting a comment, at the start of a line
+    /// or after whitespace following a value
+    pub comments: Vec<char>,
+    pub duplicates: Duplicates,
+}
+impl Default for IniConfig {
+    fn default() -> Self {
+        Self {
+            comments: vec![';', '#'],
+            duplicates: Duplicates::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum IniError {
+    /// Source could not be decoded on `line`
+    Decode { line: usize, error: DecodeError },
+    /// Line is longer than the `LineConfig::max_len` of the stream
+    TooLong(LineTooLong),
+    /// `[` without a matching `]`
+    UnclosedSection { line: usize },
+    /// Line is neither a section, a pair nor a comment
+    MissingSeparator { line: usize },
+    /// Nothing before the `=` or `:`
+    EmptyKey { line: usize },
+    /// Quoted value is never closed
+    UnterminatedQuote { line: usize },
+    /// Something other than a comment after a section or quoted value
+    TrailingChars { line: usize },
+    /// `key` was already set in `section` on line `first`
+    DuplicateKey {
+        line: usize,
+        first: usize,
+        section: String,
+        key: String,
+    },
+}
+impl IniError {
+    /// Line of the source the error is on
+    pub fn line(&self) -> usize {
+        match self {
+            Self::TooLong(e) => e.start.line,
+            Self::Decode { line, .. }
+            | Self::UnclosedSection { line }
+            | Self::MissingSeparator { line }
+            | Self::EmptyKey { line }
+            | Self::UnterminatedQuote { line }
+            | Self::TrailingChars { line }
+            | Self::DuplicateKey { line, .. } => *line,
+        }
+    }
+}
+impl Display for IniError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode { line, error } => write!(f, "{error} on line {line}"),
+            Self::TooLong(e) => write!(f, "{e}"),
+            Self::UnclosedSection { line } => write!(f, "section on line {line} is never closed"),
+            Self::MissingSeparator { line } => {
+                write!(f, "expected `=` or `:` on line {line}")
+            }
+            Self::EmptyKey { line } => write!(f, "empty key on line {line}"),
+            Self::UnterminatedQuote { line } => {
+                write!(f, "quoted value on line {line} is never closed")
+            }
+            Self::TrailingChars { line } => write!(f, "unexpected text on line {line}"),
+            Self::DuplicateKey {
+                line,
+                first,
+                section,
+                key,
+            } => write!(
+                f,
+                "duplicate key {key:?} in section {section:?} on line {line}, first set on line {first}"
+            ),
+        }
+    }
+}
+impl std::error::Error for IniError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode { error, .. } => Some(error),
+            Self::TooLong(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// One line of meaning read by an `IniReader`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IniEvent {
+    /// `[name]`, the pairs after it belong to this section
+    Section { line: usize, name: String },
+    /// `key = value` or `key: value`
+    Pair {
+        line: usize,
+        key: String,
+        value: String,
+    },
+}
+
+/// Key and value in an `Ini` section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Line the value was read from
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+}
+
+/// Pairs under one section name, in the order they were read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    /// Line the section first appears on, 0 for the unnamed section
+    pub line: usize,
+    entries: Vec<Entry>,
+    /// Index into `entries` by key
+    index: HashMap<String, usize>,
+}
+impl Section {
+    fn new(name: String, line: usize) -> Self {
+        Self {
+            name,
+            line,
+            entries: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entry(key).map(|x| x.value.as_str())
+    }
+
+    pub fn entry(&self, key: &str) -> Option<&Entry> {
+        self.index.get(key).map(|&i| &self.entries[i])
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+/// Sections of an INI source, in the order they were read
+///
+/// Pairs before the first section header are in an unnamed section `""`,
+/// a section appearing more than once is merged into the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ini {
+    sections: Vec<Section>,
+    /// Index into `sections` by name
+    index: HashMap<String, usize>,
+}
+impl Ini {
+    /// Value of `key` in `section`, `""` for keys before any section
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.section(section)?.get(key)
+    }
+
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.index.get(name).map(|&i| &self.sections[i])
+    }
+
+    /// Every section, starting with the unnamed one
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+}
+
+/// Iterator over the sections and pairs of an INI source
+///
+/// Values may be quoted with `"`, which understands the escapes
+/// `\"`, `\\`, `\n`, `\r` and `\t`, or with `'`, taken as is.
+/// Unquoted values run to the end of the line or to a comment
+/// preceded by whitespace, surrounding whitespace is trimmed
+///
+/// ---
+/// Lines end where the `LineConfig` of the stream says, lines longer than
+/// its `max_len` are reported with `LineOverflow::Error`.
+/// After a malformed line reading continues on the next one,
+/// a decoding error ends iteration
+pub struct IniReader<T>
+where
+    T: Read,
+{
+    stream: StringStream<T>,
+    config: IniConfig,
+    /// Line the last line read starts on
+    line: usize,
+    done: bool,
+}
+impl<T> IniReader<T>
+where
+    T: Read,
+{
+    pub fn new(stream: StringStream<T>) -> Self {
+        Self {
+            stream,
+            config: IniConfig::default(),
+            line: 0,
+            done: false,
+        }
+    }
+
+    pub fn from_reader(x: T) -> Self {
+        Self::new(StringStream::new(x))
+    }
+
+    pub fn with_config(mut self, config: IniConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Read the whole source into an `Ini`,
+    /// stopping at the first error
+    pub fn into_map(self) -> Result<Ini, IniError> {
+        let duplicates = self.config.duplicates;
+        let mut ini = Ini {
+            sections: vec![Section::new(String::new(), 0)],
+            index: HashMap::from([(String::new(), 0)]),
+        };
+        let mut current = 0;
+
+        for event in self {
+            match event? {
+                IniEvent::Section { line, name } => {
+                    current = match ini.index.get(&name) {
+                        Some(&i) => i,
+                        None => {
+                            let i = ini.sections.len();
+                            ini.index.insert(name.clone(), i);
+                            ini.sections.push(Section::new(name, line));
+                            i
+                        }
+                    };
+                }
+                IniEvent::Pair { line, key, value } => {
+                    let section = &mut ini.sections[current];
+                    let Some(&i) = section.index.get(&key) else {
+                        section.index.insert(key.clone(), section.entries.len());
+                        section.entries.push(Entry { line, key, value });
+                        continue;
+                    };
+                    let entry = &mut section.entries[i];
+                    match duplicates {
+                        Duplicates::Error => {
+                            return Err(IniError::DuplicateKey {
+                                line,
+                                first: entry.line,
+                                section: section.name.clone(),
+                                key,
+                            })
+                        }
+                        Duplicates::First => {}
+                        Duplicates::Last => {
+                            entry.line = line;
+                            entry.value = value;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(ini)
+    }
+
+    /// Read the next line, without its terminator
+    fn read_line(&mut self) -> Result<Option<String>, IniError> {
+        self.line = self.stream.position().line;
+        match self.stream.try_next_line() {
+            None => Ok(None),
+            Some(Ok(x)) => Ok(Some(x)),
+            Some(Err(LineError::TooLong(e))) => Err(IniError::TooLong(e)),
+            Some(Err(LineError::Decode(error))) => {
+                // The rest of an invalid line is skipped, a failed read can't go on
+                self.done = matches!(error, DecodeError::Io { .. });
+                Err(IniError::Decode {
+                    line: self.line,
+                    error,
+                })
+            }
+        }
+    }
+
+    fn is_comment(&self, ch: char) -> bool {
+        self.config.comments.contains(&ch)
+    }
+
+    /// Whether only whitespace and maybe a comment are left
+    fn is_end(&self, rest: &str) -> bool {
+        let rest = rest.trim_start();
+        rest.chars().next().is_none_or(|x| self.is_comment(x))
+    }
+
+    /// Parse one line, `None` for blank lines and comments
+    fn parse(&self, text: &str) -> Result<Option<IniEvent>, IniError> {
+        let line = self.line;
+        let text = text.trim();
+        let Some(first) = text.chars().next() else {
+            return Ok(None);
+        };
+        if self.is_comment(first) {
+            return Ok(None);
+        }
+
+        if let Some(rest) = text.strip_prefix('[') {
+            let (name, rest) = rest
+                .split_once(']')
+                .ok_or(IniError::UnclosedSection { line })?;
+            if !self.is_end(rest) {
+                return Err(IniError::TrailingChars { line });
+            }
+            let name = name.trim().to_owned();
+            return Ok(Some(IniEvent::Section { line, name }));
+        }
+
+        let (key, value) = text
+            .split_once(['=', ':'])
+            .ok_or(IniError::MissingSeparator { line })?;
+        let key = key.trim_end();
+        if key.is_empty() {
+            return Err(IniError::EmptyKey { line });
+        }
+
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let (value, rest) =
+                    unquote(&value[1..], quote).ok_or(IniError::UnterminatedQuote { line })?;
+                if !self.is_end(rest) {
+                    return Err(IniError::TrailingChars { line });
+                }
+                value
+            }
+            _ => self.strip_comment(value).trim_end().to_owned(),
+        };
+
+        Ok(Some(IniEvent::Pair {
+            line,
+            key: key.to_owned(),
+            value,
+        }))
+    }
+
+    /// Cut an unquoted value at a comment that follows whitespace
+    fn strip_comment<'a>(&self, value: &'a str) -> &'a str {
+        let mut after_space = true;
+        for (i, ch) in value.char_indices() {
+            if after_space && self.is_comment(ch) {
+                return &value[..i];
+            }
+            after_space = ch.is_whitespace();
+        }
+        value
+    }
+}
+impl<T> Iterator for IniReader<T>
+where
+    T: Read,
+{
+    type Item = Result<IniEvent, IniError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let text = match self.read_line() {
+                Ok(Some(x)) => x,
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(event) = self.parse(&text).transpose() {
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+/// Value of a quoted string starting after the opening `quote`,
+/// and the text after the closing one
+fn unquote(text: &str, quote: char) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            _ if ch == quote => return Some((value, &text[i + 1..])),
+            '\\' if quote == '"' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                x @ ('"' | '\\') => value.push(x),
+                x => {
+                    value.push('\\');
+                    value.push(x);
+                }
+            },
+            _ => value.push(ch),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::{Duplicates, IniConfig, IniError, IniEvent, IniReader};
+    use crate::string_stream::{DecodeError, DecodeMode, LineConfig, StringStream};
+
+    fn events(data: &str) -> Vec<Result<IniEvent, usize>> {
+        IniReader::new(StringStream::with_capacity(4, data.as_bytes()))
+            .map(|x| x.map_err(|e| e.line()))
+            .collect()
+    }
+
+    fn section(line: usize, name: &str) -> Result<IniEvent, usize> {
+        Ok(IniEvent::Section {
+            line,
+            name: name.to_owned(),
+        })
+    }
+
+    fn pair(line: usize, key: &str, value: &str) -> Result<IniEvent, usize> {
+        Ok(IniEvent::Pair {
+            line,
+            key: key.to_owned(),
+            value: value.to_owned(),
+        })
+    }
+
+    #[test]
+    fn events_test() {
+        let data = "; leading comment\nglobal = 1\r\n\n[ server ] # main\n  host:  example.com  \nport=8080 ; inline\npath = /a#b\nempty =\n\n[other]\nurl = http://x:80/?a=b\n";
+        assert_eq!(
+            events(data),
+            [
+                pair(2, "global", "1"),
+                section(4, "server"),
+                pair(5, "host", "example.com"),
+                pair(6, "port", "8080"),
+                // Comments need whitespace before them
+                pair(7, "path", "/a#b"),
+                pair(8, "empty", ""),
+                section(10, "other"),
+                // Split at the first separator
+                pair(11, "url", "http://x:80/?a=b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_test() {
+        let data = "a = \"  spaced ; not a comment  \"\nb = \"say \\\"hi\\\"\\n\\tC:\\\\dir \\x\" ; comment\nc = 'it\\n \"raw\"'\nd = \"\"\n";
+        assert_eq!(
+            events(data),
+            [
+                pair(1, "a", "  spaced ; not a comment  "),
+                pair(2, "b", "say \"hi\"\n\tC:\\dir \\x"),
+                pair(3, "c", "it\\n \"raw\""),
+                pair(4, "d", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_test() {
+        let data =
+            "[open\nno separator\n = value\nq = \"never closed\nq = \"x\" y\n[s] z\nok = 1\n";
+        let errors = IniReader::from_reader(data.as_bytes()).collect::<Vec<_>>();
+        assert!(matches!(
+            errors[0],
+            Err(IniError::UnclosedSection { line: 1 })
+        ));
+        assert!(matches!(
+            errors[1],
+            Err(IniError::MissingSeparator { line: 2 })
+        ));
+        assert!(matches!(errors[2], Err(IniError::EmptyKey { line: 3 })));
+        assert!(matches!(
+            errors[3],
+            Err(IniError::UnterminatedQuote { line: 4 })
+        ));
+        assert!(matches!(
+            errors[4],
+            Err(IniError::TrailingChars { line: 5 })
+        ));
+        assert!(matches!(
+            errors[5],
+            Err(IniError::TrailingChars { line: 6 })
+        ));
+        // Reading carries on after each one
+        assert_eq!(errors[6].as_ref().unwrap(), &pair(7, "ok", "1").unwrap());
+        assert_eq!(errors.len(), 7);
+        assert_eq!(
+            errors[1].as_ref().unwrap_err().to_string(),
+            "expected `=` or `:` on line 2"
+        );
+    }
+
+    #[test]
+    fn map_test() {
+        let data = "top = level\n[a]\nx = 1\ny = 2\n[b]\nz = 3\n[a]\nw = 4\n";
+        let ini = IniReader::from_reader(data.as_bytes()).into_map().unwrap();
+
+        assert_eq!(ini.get("", "top"), Some("level"));
+        assert_eq!(ini.get("a", "y"), Some("2"));
+        assert_eq!(ini.get("a", "z"), None);
+        assert_eq!(ini.get("missing", "x"), None);
+
+        // Repeated sections are merged, order is kept
+        let names = ini.sections().iter().map(|x| &x.name).collect::<Vec<_>>();
+        assert_eq!(names, ["", "a", "b"]);
+        let a = ini.section("a").unwrap();
+        assert_eq!(a.line, 2);
+        let keys = a.entries().iter().map(|x| (x.key.as_str(), x.line));
+        assert_eq!(keys.collect::<Vec<_>>(), [("x", 3), ("y", 4), ("w", 8)]);
+    }
+
+    #[test]
+    fn duplicates_test() {
+        let data = "[s]\nkey = first\nother = 0\nkey = last\n";
+        let map = |duplicates| {
+            let config = IniConfig {
+                duplicates,
+                ..Default::default()
+            };
+            IniReader::from_reader(data.as_bytes())
+                .with_config(config)
+                .into_map()
+        };
+
+        let Err(IniError::DuplicateKey {
+            line,
+            first,
+            section,
+            key,
+        }) = map(Duplicates::Error)
+        else {
+            panic!("expected duplicate key");
+        };
+        assert_eq!((line, first, &*section, &*key), (4, 2, "s", "key"));
+
+        let ini = map(Duplicates::First).unwrap();
+        assert_eq!(ini.section("s").unwrap().entry("key").unwrap().line, 2);
+        assert_eq!(ini.get("s", "key"), Some("first"));
+
+        let ini = map(Duplicates::Last).unwrap();
+        assert_eq!(ini.get("s", "key"), Some("last"));
+        let entries = ini.section("s").unwrap().entries();
+        assert_eq!((entries[0].key.as_str(), entries[0].line), ("key", 4));
+
+        // Same key in other sections is fine
+        let ini = IniReader::from_reader("k = 1\n[s]\nk = 2\n".as_bytes())
+            .into_map()
+            .unwrap();
+        assert_eq!(
+            (ini.get("", "k"), ini.get("s", "k")),
+            (Some("1"), Some("2"))
+        );
+    }
+
+    #[test]
+    fn config_test() {
+        let config = IniConfig {
+            comments: vec!['%'],
+            ..Default::default()
+        };
+        let events = IniReader::from_reader("% comment\n; a = b\nc = d % e\n".as_bytes())
+            .with_config(config)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [pair(2, "; a", "b").unwrap(), pair(3, "c", "d").unwrap()]
+        );
+    }
+
+    #[test]
+    fn decode_error_test() {
+        let data = b"a = 1\nb = \xFF\nc = 3\n";
+        // Parsing carries on after the line with invalid data
+        let results = IniReader::from_reader(&data[..]).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        let error = results[1].as_ref().unwrap_err();
+        assert!(matches!(error, IniError::Decode { line: 2, .. }));
+        assert_eq!(
+            error.to_string(),
+            "invalid sequence [FF] at byte 10 on line 2"
+        );
+        assert!(matches!(&results[2], Ok(IniEvent::Pair { line: 3, .. })));
+        assert!(IniReader::from_reader(&data[..]).into_map().is_err());
+
+        let stream = StringStream::new(&data[..]).with_mode(DecodeMode::Lossy);
+        let ini = IniReader::new(stream).into_map().unwrap();
+        assert_eq!(ini.get("", "b"), Some("\u{FFFD}"));
+        assert_eq!(ini.get("", "c"), Some("3"));
+
+        // Failed reads end iteration, whatever the mode
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("broken"))
+            }
+        }
+        let source = b"a = 1\nb".chain(Failing);
+        let stream = StringStream::with_capacity(4, source).with_mode(DecodeMode::Lossy);
+        let results = IniReader::new(stream).collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            &results[1],
+            Err(IniError::Decode {
+                error: DecodeError::Io { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn line_config_test() {
+        // Classic Mac OS line endings
+        let config = LineConfig {
+            lf: false,
+            cr: true,
+            max_len: Some(12),
+            ..Default::default()
+        };
+        let data = "[s]\ra = 1\rlong = too long to keep\rb = 2\r";
+        let stream = StringStream::with_capacity(4, data.as_bytes()).with_line_config(config);
+        let results = IniReader::new(stream).collect::<Vec<_>>();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap(), &section(1, "s").unwrap());
+        assert_eq!(results[1].as_ref().unwrap(), &pair(2, "a", "1").unwrap());
+        let Err(e @ IniError::TooLong(_)) = &results[2] else {
+            panic!("expected line too long");
+        };
+        assert_eq!(e.line(), 3);
+        // Reading carries on after it
+        assert_eq!(results[3].as_ref().unwrap(), &pair(4, "b", "2").unwrap());
+    }
+}
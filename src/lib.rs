@@ -12,3 +12,9 @@ pub mod collect_vec;
 
 #[cfg(feature = "byte_readers")]
 pub mod byte_readers;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+
+#[cfg(feature = "ini")]
+pub mod ini;
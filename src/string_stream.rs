@@ -1,4 +1,48 @@
-use std::io::{Bytes, Read};
+#[cfg(any(feature = "graphemes", feature = "text_stats"))]
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter, Write};
+use std::io::Read;
+use std::ops::ControlFlow;
+
+#[cfg(feature = "async_stream")]
+mod async_stream;
+mod checkpoint;
+#[cfg(feature = "compression")]
+mod compressed;
+mod encoding;
+#[cfg(feature = "graphemes")]
+mod grapheme;
+mod logical;
+mod multi;
+#[cfg(feature = "normalization")]
+mod normalize;
+mod reverse;
+mod scan;
+mod search;
+mod sink;
+#[cfg(feature = "text_stats")]
+mod stats;
+#[cfg(feature = "async_stream")]
+pub use async_stream::AsyncStringStream;
+pub use checkpoint::Checkpoint;
+#[cfg(feature = "compression")]
+pub use compressed::{Compression, Decompress};
+pub use encoding::Encoding;
+pub use logical::{LogicalConfig, LogicalLine, LogicalLines};
+pub use multi::{MultiStream, SourceError, SourceLine, SourcePosition};
+#[cfg(feature = "normalization")]
+pub use normalize::{NormalizationForm, Normalize};
+pub use reverse::ReverseLines;
+pub use scan::TokenError;
+pub use search::{Match, Searcher};
+pub use sink::StringSink;
+#[cfg(feature = "text_stats")]
+pub use stats::TextStats;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+/// Length of the longest sequence, the buffer must be able to hold one
+const MIN_CAPACITY: usize = 4;
 
 const fn hi(byte: u8, bits: u8) -> u8 {
     let mask = match bits {
@@ -38,240 +82,1229 @@ const fn is_ascii(byte: u8) -> bool {
     hi(byte, 1) == 0
 }
 
-const fn starts_with_10(byte: u8) -> bool {
-    hi(byte, 2) == 0x80
+/// Number of bytes in a sequence starting with `first`
+///
+/// ---
+/// `C0`, `C1` and `F5..=FF` can never start a valid sequence (RFC 3629)
+const fn sequence_len(first: u8) -> Option<usize> {
+    match first {
+        0xC0 | 0xC1 | 0xF5..=0xFF => None,
+        _ if is_ascii(first) => Some(1),
+        _ if hi(first, 3) == 0xC0 => Some(2),
+        _ if hi(first, 4) == 0xE0 => Some(3),
+        _ if hi(first, 5) == 0xF0 => Some(4),
+        _ => None,
+    }
+}
+
+/// Inclusive range the second byte of a sequence must fall in
+///
+/// ---
+/// Narrower than a plain continuation byte after some lead bytes,
+/// which rules out overlong forms (`E0`, `F0`), surrogates (`ED`)
+/// and code points above U+10FFFF (`F4`)
+const fn second_byte_range(first: u8) -> (u8, u8) {
+    match first {
+        0xE0 => (0xA0, 0xBF),
+        0xED => (0x80, 0x9F),
+        0xF0 => (0x90, 0xBF),
+        0xF4 => (0x80, 0x8F),
+        _ => (0x80, 0xBF),
+    }
+}
+
+/// Combine a complete sequence into a code point
+const fn decode_sequence(bytes: &[u8]) -> u32 {
+    match bytes.len() {
+        1 => bytes[0] as u32,
+        2 => (lo(bytes[0], 5) as u32) << 6 | lo(bytes[1], 6) as u32,
+        3 => {
+            (lo(bytes[0], 4) as u32) << 12 | (lo(bytes[1], 6) as u32) << 6 | lo(bytes[2], 6) as u32
+        }
+        _ => {
+            (lo(bytes[0], 3) as u32) << 18
+                | (lo(bytes[1], 6) as u32) << 12
+                | (lo(bytes[2], 6) as u32) << 6
+                | lo(bytes[3], 6) as u32
+        }
+    }
+}
+
+/// Result of decoding the sequence at the start of a slice
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Decoded {
+    /// Character and the number of bytes used
+    Char(char, usize),
+    /// Length of the maximal subpart of an invalid sequence
+    Invalid(usize),
+    /// Slice ends part way through a sequence that may still be valid
+    Incomplete,
+}
+
+/// Decode the sequence at the start of `bytes`, which must not be empty
+#[inline]
+fn decode_utf8(bytes: &[u8]) -> Decoded {
+    let first = bytes[0];
+    if is_ascii(first) {
+        return Decoded::Char(first as char, 1);
+    }
+
+    let Some(len) = sequence_len(first) else {
+        return Decoded::Invalid(1);
+    };
+
+    for i in 1..len {
+        let (min, max) = match i {
+            1 => second_byte_range(first),
+            _ => (0x80, 0xBF),
+        };
+
+        match bytes.get(i) {
+            Some(x) if (min..=max).contains(x) => {}
+            Some(_) => return Decoded::Invalid(i),
+            None => return Decoded::Incomplete,
+        }
+    }
+
+    match char::from_u32(decode_sequence(&bytes[..len])) {
+        Some(ch) => Decoded::Char(ch, len),
+        None => Decoded::Invalid(len),
+    }
+}
+
+/// Where `ch` is relative to the range `lo..=hi`, to binary search tables of ranges
+#[cfg(any(feature = "graphemes", feature = "text_stats"))]
+fn cmp_range(ch: char, lo: char, hi: char) -> Ordering {
+    if hi < ch {
+        Ordering::Less
+    } else if lo > ch {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// Reading from the underlying source failed
+    Io { offset: u64, error: std::io::Error },
+    /// Bytes starting at `offset` are not a valid sequence
+    InvalidSequence { offset: u64, bytes: Vec<u8> },
+}
+impl DecodeError {
+    /// Byte offset into the source where the error occurred
+    pub fn offset(&self) -> u64 {
+        match self {
+            Self::Io { offset, .. } | Self::InvalidSequence { offset, .. } => *offset,
+        }
+    }
+}
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { offset, error } => write!(f, "I/O error at byte {offset}: {error}"),
+            Self::InvalidSequence { offset, bytes } => {
+                write!(f, "invalid sequence {bytes:02X?} at byte {offset}")
+            }
+        }
+    }
+}
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::InvalidSequence { .. } => None,
+        }
+    }
+}
+
+/// How `StringStream` handles invalid data when iterating characters
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// End iteration at the first invalid sequence
+    #[default]
+    Stop,
+    /// Replace each invalid sequence with U+FFFD and carry on
+    ///
+    /// ---
+    /// Follows the WHATWG "maximal subpart" practice used by
+    /// `String::from_utf8_lossy`
+    Lossy,
+}
+
+/// Location in a `StringStream` source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// Bytes from the start of the source
+    pub offset: u64,
+    /// Line number, starting at 1. Lines end at the terminators
+    /// of the stream's `LineConfig`
+    pub line: usize,
+    /// Column in characters, starting at 1
+    pub column: usize,
+}
+impl Default for Position {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Terminator that ended a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r`
+    Cr,
+    /// Next line, U+0085
+    Nel,
+    /// Line separator, U+2028
+    Ls,
+    /// Paragraph separator, U+2029
+    Ps,
+    /// `LineConfig::delimiter`
+    Custom(char),
+}
+impl Display for LineEnding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lf => f.write_str("\n"),
+            Self::CrLf => f.write_str("\r\n"),
+            Self::Cr => f.write_str("\r"),
+            Self::Nel => f.write_str("\u{85}"),
+            Self::Ls => f.write_str("\u{2028}"),
+            Self::Ps => f.write_str("\u{2029}"),
+            Self::Custom(ch) => write!(f, "{ch}"),
+        }
+    }
+}
+
+/// How to handle a line longer than `LineConfig::max_len`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineOverflow {
+    /// Keep the start of the line and drop the rest
+    Truncate,
+    /// Return the line in pieces of at most `max_len`,
+    /// every piece but the last has no terminator
+    Split,
+    /// Skip the line and report `LineTooLong`
+    #[default]
+    Error,
+}
+
+/// A line was longer than `LineConfig::max_len`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineTooLong {
+    /// Where the line starts
+    pub start: Position,
+    pub max_len: usize,
+}
+impl Display for LineTooLong {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} at byte {} is longer than {} bytes",
+            self.start.line, self.start.offset, self.max_len
+        )
+    }
+}
+impl std::error::Error for LineTooLong {}
+
+/// Why `try_next_line` couldn't read a line
+#[derive(Debug)]
+pub enum LineError {
+    /// Data that `next` would stop at, the rest of the line
+    /// is skipped and reading carries on at the next line
+    Decode(DecodeError),
+    /// Line was skipped for being longer than `LineConfig::max_len`
+    TooLong(LineTooLong),
+}
+impl Display for LineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode(e) => write!(f, "{e}"),
+            Self::TooLong(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for LineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(e) => Some(e),
+            Self::TooLong(e) => Some(e),
+        }
+    }
+}
+
+/// Which terminators end a line when reading lines from a `StringStream`
+///
+/// ---
+/// The default splits on `\n` and `\r\n`, a lone `\r` is part of the line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineConfig {
+    /// `\n` and `\r\n` end a line
+    pub lf: bool,
+    /// A lone `\r` ends a line (classic Mac OS)
+    pub cr: bool,
+    /// NEL, LS and PS end a line
+    pub unicode: bool,
+    /// Extra character that ends a line
+    pub delimiter: Option<char>,
+    /// Leave the terminator at the end of each line
+    pub keep_terminator: bool,
+    /// Longest line in bytes of UTF-8 text, not counting the terminator
+    pub max_len: Option<usize>,
+    /// What to do with lines longer than `max_len`
+    pub overflow: LineOverflow,
+}
+impl Default for LineConfig {
+    fn default() -> Self {
+        Self {
+            lf: true,
+            cr: false,
+            unicode: false,
+            delimiter: None,
+            keep_terminator: false,
+            max_len: None,
+            overflow: LineOverflow::default(),
+        }
+    }
+}
+impl LineConfig {
+    /// UTF-8 bytes that may start a terminator
+    fn stop_bytes(&self) -> ([u8; 5], usize) {
+        let mut stops = [0; 5];
+        let mut count = 0;
+        let mut push = |x: u8| {
+            stops[count] = x;
+            count += 1;
+        };
+
+        if self.lf {
+            push(b'\n');
+        }
+        if self.cr {
+            push(b'\r');
+        }
+        if self.unicode {
+            // NEL is C2 85, LS and PS are E2 80 A8/A9
+            push(0xC2);
+            push(0xE2);
+        }
+        if let Some(ch) = self.delimiter {
+            let mut buf = [0; 4];
+            push(ch.encode_utf8(&mut buf).as_bytes()[0]);
+        }
+
+        (stops, count)
+    }
+
+    /// `ch` ends a line, unless it is the `\n` of a `\r\n`
+    /// whose `\r` already did
+    fn breaks_line(&self, ch: char, after_cr: bool) -> bool {
+        match ch {
+            '\n' if after_cr => false,
+            '\n' if self.lf => true,
+            '\r' if self.cr => true,
+            '\u{85}' | '\u{2028}' | '\u{2029}' if self.unicode => true,
+            _ => Some(ch) == self.delimiter,
+        }
+    }
 }
 
-const fn is_two_bytes(first: u8, second: u8) -> bool {
-    hi(first, 3) == 0xC0 && starts_with_10(second)
+/// Ending for a line feed, removing the `\r` before it from `line`
+/// unless it was dropped
+fn line_feed(line: &mut String, after_cr: bool, skipping: bool) -> LineEnding {
+    match (after_cr, skipping) {
+        (true, false) => {
+            line.pop();
+            LineEnding::CrLf
+        }
+        (true, true) => LineEnding::CrLf,
+        (false, _) => LineEnding::Lf,
+    }
 }
 
-const fn is_three_bytes(first: u8, second: u8, third: u8) -> bool {
-    hi(first, 4) == 0xE0 && starts_with_10(second) && starts_with_10(third)
+/// Progress through a line read by `read_line`
+#[derive(Debug)]
+struct LineState {
+    /// Position of the first character of the line
+    start: Position,
+    read_any: bool,
+    /// Rest of the line is thrown away after it grows too long
+    skipping: bool,
+    /// Last character of the line, pushed or not, was `\r`
+    prev_cr: bool,
+}
+impl LineState {
+    fn new(start: Position) -> Self {
+        Self {
+            start,
+            read_any: false,
+            skipping: false,
+            prev_cr: false,
+        }
+    }
 }
 
-const fn is_four_bytes(first: u8, second: u8, third: u8, fourth: u8) -> bool {
-    hi(first, 5) == 0xF0
-        && starts_with_10(second)
-        && starts_with_10(third)
-        && starts_with_10(fourth)
+/// Character decoded ahead of the reader by `peek_nth`, or pushed back by `unread`
+#[derive(Debug)]
+struct Pending {
+    result: Result<char, DecodeError>,
+    position: Position,
+    /// `StringStream::after_cr` before this character
+    after_cr: bool,
 }
 
 /// Character based iterator over a **Read**able type
-/// Data is expected to be UTF-8
+/// Data is expected to be UTF-8 unless another `Encoding` is chosen.
+/// For UTF-8, overlong forms, surrogates and code points above U+10FFFF
+/// are invalid (RFC 3629)
+///
+/// Reads are buffered internally, there is no need to wrap
+/// the source in a `BufReader`
 ///
 /// ---
-/// **NOTE**: Will end early if encountering non UTF-8 data
+/// **NOTE**: Will end early if encountering non UTF-8 data,
+/// use `try_chars` to find out why or `DecodeMode::Lossy` to keep going
 pub struct StringStream<T>
 where
     T: Read,
 {
-    bytes: Bytes<T>,
+    source: T,
+    buf: Box<[u8]>,
+    /// Unread data is `buf[start..end]`
+    start: usize,
+    end: usize,
+    /// Offset into `source` of `buf[0]`
+    base: u64,
+    line: usize,
+    column: usize,
+    /// Column of the last line break read, restored when one is unread
+    newline_column: usize,
+    /// Last character decoded was a `\r` that ended a line,
+    /// so a `\n` right after it doesn't end another
+    after_cr: bool,
+    /// Characters to return before decoding any more
+    lookahead: VecDeque<Pending>,
+    mode: DecodeMode,
+    encoding: Encoding,
+    /// Look for a byte order mark before decoding anything
+    detect_bom: bool,
+    line_config: LineConfig,
 }
 impl<T> StringStream<T>
 where
     T: Read,
 {
     pub fn new(x: T) -> Self {
-        Self { bytes: x.bytes() }
+        Self::with_capacity(DEFAULT_CAPACITY, x)
     }
 
-    /// Read until new line or EOF
-    pub fn next_line(&mut self) -> Option<String> {
-        let mut buf = String::new();
+    /// Create with an internal buffer of `capacity` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is less than 4
+    pub fn with_capacity(capacity: usize, x: T) -> Self {
+        assert!(capacity >= MIN_CAPACITY, "Capacity too small");
 
-        while let Some(ch) = self.next() {
-            if ch == '\r' {
-                let next = self.next();
-                if next == Some('\n') {
-                    return Some(buf);
-                } else {
-                    buf.push(ch);
-                }
-            } else if ch == '\n' {
-                return Some(buf);
-            } else {
-                buf.push(ch);
-            }
+        Self {
+            source: x,
+            buf: vec![0; capacity].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            base: 0,
+            line: 1,
+            column: 1,
+            newline_column: 1,
+            after_cr: false,
+            lookahead: VecDeque::new(),
+            mode: DecodeMode::default(),
+            encoding: Encoding::default(),
+            detect_bom: false,
+            line_config: LineConfig::default(),
         }
+    }
 
-        if !buf.is_empty() {
-            Some(buf)
-        } else {
-            None
-        }
+    pub fn with_mode(mut self, mode: DecodeMode) -> Self {
+        self.mode = mode;
+        self
     }
 
-    pub fn lines(mut self) -> impl Iterator<Item = String> {
-        std::iter::from_fn(move || self.next_line())
+    /// Decode the source as `encoding`
+    ///
+    /// ---
+    /// A byte order mark is decoded as U+FEFF unless `detect_encoding`
+    /// is also used
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
     }
-}
-impl<T> Iterator for StringStream<T>
-where
-    T: Read,
-{
-    type Item = char;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut next_byte = || self.bytes.next().and_then(|x| x.ok());
+    /// Choose the encoding from a byte order mark at the start of the source,
+    /// the mark itself is skipped
+    ///
+    /// ---
+    /// Falls back to the encoding given to `with_encoding`, or UTF-8
+    pub fn detect_encoding(mut self) -> Self {
+        self.detect_bom = true;
+        self
+    }
 
-        let first = next_byte()?;
-        if is_ascii(first) {
-            return char::from_u32(first as u32);
-        }
+    /// Choose which terminators end a line in `next_line` and `lines`,
+    /// and for the line numbers of `position`
+    pub fn with_line_config(mut self, config: LineConfig) -> Self {
+        self.line_config = config;
+        self
+    }
+
+    /// How invalid data is handled
+    pub fn mode(&self) -> DecodeMode {
+        self.mode
+    }
+
+    /// Encoding used to decode the source,
+    /// only reflects a detected byte order mark once reading has started
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.buf[self.start..self.end]
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.start += len;
+    }
+
+    /// Number of bytes consumed from the source
+    fn offset(&self) -> u64 {
+        self.base + self.start as u64
+    }
 
-        let second = next_byte()?;
-        if is_two_bytes(first, second) {
-            let first = lo(first, 5);
-            let second = lo(second, 6);
-            let ch = (first as u32) << 6 | second as u32;
-            return char::from_u32(ch);
+    /// Update line and column after `ch` has been read.
+    /// Lines end at the terminators of the `LineConfig`,
+    /// `\r\n` counts as a single line break
+    #[inline]
+    fn advance(&mut self, ch: char) {
+        let after_cr = std::mem::take(&mut self.after_cr);
+        if self.line_config.breaks_line(ch, after_cr) {
+            self.newline_column = self.column;
+            self.line += 1;
+            self.column = 1;
+            self.after_cr = ch == '\r';
+        } else if !(after_cr && ch == '\n') {
+            self.column += 1;
         }
+    }
 
-        let third = next_byte()?;
-        if is_three_bytes(first, second, third) {
-            let first = lo(first, 4);
-            let second = lo(second, 6);
-            let third = lo(third, 6);
+    /// Position of the next byte to be decoded
+    fn decoder_position(&self) -> Position {
+        Position {
+            offset: self.offset(),
+            line: self.line,
+            column: self.column,
+        }
+    }
 
-            let ch = (first as u32) << 12 | (second as u32) << 6 | third as u32;
-            return char::from_u32(ch);
+    /// Position of the next character to be read
+    ///
+    /// ---
+    /// Invalid sequences move the offset but only count
+    /// towards the column when replaced by `DecodeMode::Lossy`
+    ///
+    /// **NOTE**: Through a `&mut StringStream` method resolution picks
+    /// `Iterator::position`, call `StringStream::position(stream)` instead
+    pub fn position(&self) -> Position {
+        match self.lookahead.front() {
+            Some(x) => x.position,
+            None => self.decoder_position(),
         }
+    }
 
-        let fourth = next_byte()?;
-        if is_four_bytes(first, second, third, fourth) {
-            let first = lo(first, 3);
-            let second = lo(second, 6);
-            let third = lo(third, 6);
-            let fourth = lo(fourth, 6);
+    /// Iterate over characters along with where each one starts
+    pub fn char_positions(&mut self) -> impl Iterator<Item = (Position, char)> + '_ {
+        std::iter::from_fn(move || {
+            self.skip_bom();
+            // `Iterator::position` would be picked for `&mut Self`
+            let position = Self::position(self);
+            self.next().map(|ch| (position, ch))
+        })
+    }
 
-            let ch = (first as u32) << 18
-                | (second as u32) << 12
-                | (third as u32) << 6
-                | (fourth as u32);
+    /// Move unread data to the front of the buffer when there is
+    /// no free space after it, or when there is none left to read
+    fn make_room(&mut self) {
+        if self.start == self.end || self.end == self.buf.len() {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.base += self.start as u64;
+            self.end -= self.start;
+            self.start = 0;
+        }
+    }
 
-            return char::from_u32(ch);
+    /// Read more data into the buffer, keeping unread data
+    ///
+    /// ---
+    /// Returns the number of bytes read, `0` meaning EOF
+    fn fill(&mut self) -> std::io::Result<usize> {
+        self.make_room();
+        loop {
+            match self.source.read(&mut self.buf[self.end..]) {
+                Ok(n) => {
+                    self.end += n;
+                    return Ok(n);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
         }
+    }
 
-        None
+    /// Skip a byte order mark now so it isn't part of a position.
+    /// On failure the error is reported again by `next`
+    fn skip_bom(&mut self) {
+        if self.detect_bom {
+            let _ = self.read_bom();
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::io::{Cursor, Read};
+    /// Switch encoding if the source starts with a byte order mark
+    fn read_bom(&mut self) -> std::io::Result<()> {
+        // Longest mark is 3 bytes
+        while self.buffer().len() < 3 {
+            if self.fill()? == 0 {
+                break;
+            }
+        }
 
-    use super::StringStream;
+        self.detect_bom = false;
+        if let Some((encoding, len)) = Encoding::from_bom(self.buffer()) {
+            self.encoding = encoding;
+            self.consume(len);
+        }
 
-    #[test]
-    fn one_byte_test() {
-        let s = "h";
-        let cur = std::io::Cursor::new(s);
-        let mut stream = StringStream::new(cur);
-        assert_eq!(stream.next(), Some('h'));
+        Ok(())
     }
 
-    #[test]
-    fn two_byte_test() {
-        let s = "\u{1B1}";
-        let cur = std::io::Cursor::new(s);
-        let mut stream = StringStream::new(cur);
-        assert_eq!(stream.next(), Some('\u{1B1}'));
+    /// Consume `len` bytes as an invalid sequence
+    fn invalid(&mut self, len: usize) -> DecodeError {
+        let offset = self.offset();
+        let bytes = self.buffer()[..len].to_vec();
+        self.consume(len);
+        self.after_cr = false;
+
+        DecodeError::InvalidSequence { offset, bytes }
     }
 
-    #[test]
-    fn three_byte_test() {
-        let s = "\u{2713}";
-        let cur = std::io::Cursor::new(s);
-        let mut stream = StringStream::new(cur);
-        assert_eq!(stream.next(), Some('\u{2713}'));
+    /// Decode the next character, reporting why decoding failed
+    ///
+    /// ---
+    /// After an invalid sequence decoding resumes at the first byte
+    /// that couldn't be part of that sequence
+    pub fn try_next(&mut self) -> Option<Result<char, DecodeError>> {
+        match self.pop_lookahead() {
+            Some(x) => Some(x),
+            None => self.decode(),
+        }
     }
 
-    #[test]
-    fn four_byte_test() {
-        let s = "😉";
-        let cur = std::io::Cursor::new(s);
-        let mut stream = StringStream::new(cur);
-        assert_eq!(stream.next(), Some('😉'));
+    fn pop_lookahead(&mut self) -> Option<Result<char, DecodeError>> {
+        let pending = self.lookahead.pop_front()?;
+        if let Ok(ch) = pending.result {
+            if self.line_config.breaks_line(ch, pending.after_cr) {
+                self.newline_column = pending.position.column;
+            }
+        }
+
+        Some(pending.result)
     }
 
-    #[test]
-    fn next_line_test() {
-        let with_trailing = Cursor::new("hi\nhow\r\nare\nyou?");
-        let without_trailing = Cursor::new("hi\nhow\r\nare\nyou?\n");
+    /// Character `next` returns for a decoding result
+    fn resolve(&self, result: &Result<char, DecodeError>) -> Option<char> {
+        match (result, self.mode) {
+            (Ok(ch), _) => Some(*ch),
+            (Err(DecodeError::InvalidSequence { .. }), DecodeMode::Lossy) => {
+                Some(char::REPLACEMENT_CHARACTER)
+            }
+            (Err(_), _) => None,
+        }
+    }
 
-        let expected = ["hi", "how", "are", "you?"];
+    /// Decode from the buffer, ignoring any lookahead
+    fn decode(&mut self) -> Option<Result<char, DecodeError>> {
+        if self.detect_bom {
+            if let Err(error) = self.read_bom() {
+                let offset = self.offset() + self.buffer().len() as u64;
+                return Some(Err(DecodeError::Io { offset, error }));
+            }
+        }
 
-        fn get_lines(data: impl Read) -> Vec<String> {
-            let mut stream = StringStream::new(data);
-            let mut lines = Vec::new();
-            while let Some(l) = stream.next_line() {
-                lines.push(l);
+        loop {
+            if !self.buffer().is_empty() {
+                match self.encoding.decode(self.buffer()) {
+                    Decoded::Char(ch, len) => {
+                        self.consume(len);
+                        self.advance(ch);
+                        return Some(Ok(ch));
+                    }
+                    Decoded::Invalid(len) => {
+                        if self.mode == DecodeMode::Lossy {
+                            self.advance(char::REPLACEMENT_CHARACTER);
+                        }
+                        return Some(Err(self.invalid(len)));
+                    }
+                    Decoded::Incomplete => {}
+                }
             }
 
-            lines
+            let available = self.end - self.start;
+            match self.fill() {
+                Ok(0) if available == 0 => return None,
+                // Sequence cut short by EOF
+                Ok(0) => return Some(Err(self.invalid(available))),
+                Ok(_) => {}
+                Err(error) => {
+                    let offset = self.offset() + available as u64;
+                    return Some(Err(DecodeError::Io { offset, error }));
+                }
+            }
         }
+    }
 
-        assert_eq!(get_lines(with_trailing), expected);
-        assert_eq!(get_lines(without_trailing), expected);
+    /// Refill the buffer or handle an error, kept out of line
+    /// so `next` stays small enough to inline
+    #[inline(never)]
+    fn next_slow(&mut self) -> Option<char> {
+        let result = self.try_next()?;
+        self.resolve(&result)
     }
 
-    #[test]
-    fn lines_test() {
-        let s = Cursor::new("how\nmuch\nwood\nwould\na\nwoodchuck\nchuck?");
-        let stream = StringStream::new(s);
-        let lines = stream.lines().collect::<Vec<_>>();
+    /// Look at the next character without consuming it
+    pub fn peek(&mut self) -> Option<char> {
+        self.peek_nth(0)
+    }
 
-        assert_eq!(
-            lines,
-            vec!["how", "much", "wood", "would", "a", "woodchuck", "chuck?"]
-        )
+    /// Look `n` characters ahead without consuming anything,
+    /// `peek_nth(0)` is the same as `peek`
+    ///
+    /// ---
+    /// Characters are decoded into an internal buffer, so peeking
+    /// far ahead holds that many characters in memory
+    pub fn peek_nth(&mut self, n: usize) -> Option<char> {
+        while self.lookahead.len() <= n && self.decode_ahead() {}
+
+        // `next` would stop at anything that doesn't resolve to a character
+        let mut chars = self.lookahead.iter().map(|x| self.resolve(&x.result));
+        for _ in 0..n {
+            chars.next()??;
+        }
+        chars.next()?
     }
 
-    // Lines iterator should read entire file
-    // even if it has blank lines in the middle
-    #[test]
-    fn lines_empty_space_test() {
-        let data = Cursor::new(
-            "Line 1
-                    Line 2
+    /// Decode one more character into the lookahead, `false` at EOF
+    fn decode_ahead(&mut self) -> bool {
+        // Decoding ahead must not disturb the column an unread line break gets
+        let newline_column = self.newline_column;
+        let position = self.decoder_position();
+        let after_cr = self.after_cr;
+        let decoded = self.decode();
+        self.newline_column = newline_column;
 
-                    Line 3",
-        );
+        match decoded {
+            Some(result) => {
+                self.lookahead.push_back(Pending {
+                    result,
+                    position,
+                    after_cr,
+                });
+                true
+            }
+            None => false,
+        }
+    }
 
-        let stream = StringStream::new(data);
+    /// Push `ch` back onto the stream, it will be the next character read
+    ///
+    /// ---
+    /// `position` moves back by the size of `ch` in the source encoding.
+    /// The column is only restored exactly for the last line break read,
+    /// the `\n` of a `\r\n` counts as a line break of its own
+    pub fn unread(&mut self, ch: char) {
+        let current = Self::position(self);
+        let len = self.encoding.encoded_len(ch) as u64;
 
-        let lines = stream
-            .lines()
-            .map(|x| x.trim().to_owned())
-            .collect::<Vec<_>>();
+        let position = if self.line_config.breaks_line(ch, false) {
+            Position {
+                offset: current.offset.saturating_sub(len),
+                line: current.line.saturating_sub(1).max(1),
+                column: self.newline_column,
+            }
+        } else {
+            Position {
+                offset: current.offset.saturating_sub(len),
+                line: current.line,
+                column: current.column.saturating_sub(1).max(1),
+            }
+        };
 
-        assert_eq!(lines, ["Line 1", "Line 2", "", "Line 3"]);
+        self.lookahead.push_front(Pending {
+            result: Ok(ch),
+            position,
+            after_cr: false,
+        });
     }
 
-    #[test]
-    fn lines_empty_string_test() {
-        let data = Cursor::new("");
-        let lines = StringStream::new(data).lines().collect::<Vec<_>>();
+    /// Iterate over characters, yielding an error for each invalid
+    /// sequence or failed read instead of stopping
+    pub fn try_chars(&mut self) -> impl Iterator<Item = Result<char, DecodeError>> + '_ {
+        std::iter::from_fn(move || self.try_next())
+    }
 
-        assert!(lines.is_empty())
+    /// Read until new line or EOF
+    ///
+    /// ---
+    /// **NOTE**: Returns `None` for a line that is too long with
+    /// `LineOverflow::Error` or that starts with data `next` stops at,
+    /// use `try_next_line` to tell them apart from EOF
+    pub fn next_line(&mut self) -> Option<String> {
+        self.next_line_with_ending().map(|(line, _)| line)
     }
 
-    #[test]
-    fn lines_trailing_space_test() {
-        #[rustfmt::skip]
-        let data =
-            "Line 1
-             Line 2 
+    /// Read until new line or EOF, along with the terminator that ended
+    /// the line. The terminator is `None` for a last line without one
+    pub fn next_line_with_ending(&mut self) -> Option<(String, Option<LineEnding>)> {
+        let mut line = String::new();
+        let ending = self.read_line(&mut line, false)?.ok()?;
+        Some((line, ending))
+    }
 
+    /// Read until new line or EOF, reporting lines longer than
+    /// `LineConfig::max_len` with `LineOverflow::Error`, and
+    /// the data `next` would stop at
+    ///
+    /// ---
+    /// The stream moves on to the next line after a line that is too long
+    /// or holds an invalid sequence, so each terminator ends one line
+    pub fn try_next_line(&mut self) -> Option<Result<String, LineError>> {
+        let mut line = String::new();
+        Some(self.read_line(&mut line, true)?.map(|_| line))
+    }
 
+    /// Read the next line into `buf`, replacing its contents but
+    /// keeping its allocation. Splits lines the same way as `next_line`
+    ///
+    /// ---
+    /// Returns `None` at EOF, for a line that is too long with
+    /// `LineOverflow::Error` or that starts with data `next` stops at,
+    /// otherwise the terminator that ended the line
+    pub fn read_line_into(&mut self, buf: &mut String) -> Option<Option<LineEnding>> {
+        buf.clear();
+        self.read_line(buf, false)?.ok()
+    }
 
+    /// Same as `read_line_into`, but reports errors the same as `try_next_line`
+    ///
+    /// ---
+    /// After a decoding error `buf` holds the part of the line before it
+    pub fn try_read_line_into(
+        &mut self,
+        buf: &mut String,
+    ) -> Option<Result<Option<LineEnding>, LineError>> {
+        buf.clear();
+        self.read_line(buf, true)
+    }
 
+    /// Call `f` with each remaining line, reusing a single buffer
+    pub fn for_each_line(&mut self, mut f: impl FnMut(&str)) {
+        let mut buf = String::new();
+        while self.read_line_into(&mut buf).is_some() {
+            f(&buf);
+        }
+    }
 
+    pub fn lines(mut self) -> impl Iterator<Item = String> {
+        std::iter::from_fn(move || self.next_line())
+    }
 
-            ";
+    pub fn lines_with_endings(mut self) -> impl Iterator<Item = (String, Option<LineEnding>)> {
+        std::iter::from_fn(move || self.next_line_with_ending())
+    }
 
-        let stream = StringStream::new(Cursor::new(data));
+    /// Append the next line to `line`
+    ///
+    /// ---
+    /// Returns `None` when nothing was read before EOF, or before
+    /// a decoding error without `errors`, otherwise the terminator that
+    /// ended the line. Without `errors` a decoding error ends the line
+    /// the same as EOF, with them an invalid sequence skips the rest of the line
+    fn read_line(
+        &mut self,
+        line: &mut String,
+        errors: bool,
+    ) -> Option<Result<Option<LineEnding>, LineError>> {
+        let config = self.line_config;
+        let (stops, stop_count) = config.stop_bytes();
+        let stops = &stops[..stop_count];
+
+        self.skip_bom();
+        let mut state = LineState::new(Self::position(self));
+
+        let ending = loop {
+            // Fast path for UTF-8, copy valid data up to anything that could
+            // start a terminator in bulk. ASCII never appears inside
+            // a multi-byte sequence
+            let buffer = match self.encoding {
+                Encoding::Utf8 if self.lookahead.is_empty() => self.buffer(),
+                _ => &[],
+            };
+            let stop = match stops {
+                [x] => buffer.iter().position(|b| b == x),
+                _ => buffer.iter().position(|b| stops.contains(b)),
+            };
+            let search = &buffer[..stop.unwrap_or(buffer.len())];
+            let mut chunk = match std::str::from_utf8(search) {
+                Ok(s) => s,
+                Err(e) => std::str::from_utf8(&search[..e.valid_up_to()]).unwrap_or_default(),
+            };
+
+            // The `\r` of a `\r\n` doesn't count towards `max_len`. One at the
+            // end of the buffer is left for the slow path to look past
+            let mut crlf = false;
+            if let Some(content) = chunk.strip_suffix('\r') {
+                match buffer.get(chunk.len()) {
+                    Some(b'\n') => crlf = config.lf,
+                    None => chunk = content,
+                    Some(_) => {}
+                }
+            }
+
+            if !chunk.is_empty() {
+                state.read_any = true;
+                state.prev_cr = chunk.ends_with('\r');
+
+                let content_len = chunk.len() - crlf as usize;
+                let room = match config.max_len {
+                    _ if state.skipping => 0,
+                    Some(max) => max.saturating_sub(line.len()),
+                    None => content_len,
+                };
+                let mut keep = room.min(content_len);
+                while !chunk.is_char_boundary(keep) {
+                    keep -= 1;
+                }
+
+                if keep < content_len && !state.skipping && config.overflow == LineOverflow::Split {
+                    // Always make progress, even if a single character is too long
+                    if keep == 0 && line.is_empty() {
+                        keep = chunk.chars().next().map_or(0, char::len_utf8);
+                    }
+
+                    let chars = chunk[..keep].chars().count();
+                    line.push_str(&chunk[..keep]);
+                    self.consume(keep);
+                    self.column += chars;
+                    self.after_cr = false;
+                    return Some(Ok(None));
+                }
+
+                if crlf && keep == content_len && !state.skipping {
+                    keep = chunk.len();
+                }
+                let (len, chars) = (chunk.len(), chunk.chars().count());
+                line.push_str(&chunk[..keep]);
+                state.skipping |= keep < len;
+                self.consume(len);
+                // Nothing in the chunk ends a line
+                self.column += chars;
+                self.after_cr = false;
+
+                // Common case of a plain line feed, skip decoding it
+                let lf_ends = config.lf && config.delimiter.is_none();
+                if lf_ends && self.buffer().first() == Some(&b'\n') {
+                    self.consume(1);
+                    self.advance('\n');
+                    break Some(line_feed(line, state.prev_cr, state.skipping));
+                }
+                continue;
+            }
+
+            // Possible terminator, or the buffer is empty
+            // or starts with a partial or invalid sequence
+            let ch = match self.try_next() {
+                Some(result) => match self.resolve(&result) {
+                    Some(ch) => Ok(ch),
+                    None => Err(result.err()),
+                },
+                None => Err(None),
+            };
+            let ch = match ch {
+                Ok(ch) => ch,
+                Err(Some(error @ DecodeError::InvalidSequence { .. })) if errors => {
+                    // Drop the rest of the line too, so every terminator
+                    // still ends exactly one line
+                    self.skip_line();
+                    return Some(Err(LineError::Decode(error)));
+                }
+                Err(Some(error)) if errors => return Some(Err(LineError::Decode(error))),
+                Err(_) if !state.read_any => return None,
+                Err(_) => break None,
+            };
+            if let ControlFlow::Break(ending) = self.line_char(&mut state, line, ch) {
+                break ending;
+            }
+        };
+
+        Some(self.finish_line(&state, line, ending))
+    }
+
+    /// Add `ch` to the line being read, breaking with the terminator
+    /// it ends the line with, or with `None` if the line is split before it
+    ///
+    /// ---
+    /// Peeks past a `\r` to see if it is part of a `\r\n`
+    fn line_char(
+        &mut self,
+        state: &mut LineState,
+        line: &mut String,
+        ch: char,
+    ) -> ControlFlow<Option<LineEnding>> {
+        let config = self.line_config;
+        state.read_any = true;
+
+        let ending = match ch {
+            x if Some(x) == config.delimiter => LineEnding::Custom(x),
+            '\n' if config.lf => line_feed(line, state.prev_cr, state.skipping),
+            '\r' if config.cr => {
+                if config.lf && self.peek() == Some('\n') {
+                    self.next();
+                    LineEnding::CrLf
+                } else {
+                    LineEnding::Cr
+                }
+            }
+            '\u{85}' if config.unicode => LineEnding::Nel,
+            '\u{2028}' if config.unicode => LineEnding::Ls,
+            '\u{2029}' if config.unicode => LineEnding::Ps,
+            _ => {
+                state.prev_cr = ch == '\r';
+                if state.skipping {
+                    return ControlFlow::Continue(());
+                }
+
+                let too_long = config
+                    .max_len
+                    .is_some_and(|max| line.len() + ch.len_utf8() > max)
+                    // The `\r` of a `\r\n` is removed with the `\n`
+                    && !(ch == '\r' && config.lf && self.peek() == Some('\n'));
+                match config.overflow {
+                    _ if !too_long => line.push(ch),
+                    LineOverflow::Split if line.is_empty() => line.push(ch),
+                    LineOverflow::Split => {
+                        self.unread(ch);
+                        return ControlFlow::Break(None);
+                    }
+                    LineOverflow::Truncate | LineOverflow::Error => state.skipping = true,
+                }
+                return ControlFlow::Continue(());
+            }
+        };
+        ControlFlow::Break(Some(ending))
+    }
+
+    /// Result for a line that ended with `ending`
+    fn finish_line(
+        &self,
+        state: &LineState,
+        line: &mut String,
+        ending: Option<LineEnding>,
+    ) -> Result<Option<LineEnding>, LineError> {
+        let config = self.line_config;
+        if state.skipping && config.overflow == LineOverflow::Error {
+            line.clear();
+            return Err(LineError::TooLong(LineTooLong {
+                start: state.start,
+                max_len: config.max_len.unwrap_or_default(),
+            }));
+        }
+
+        if let (Some(ending), true) = (ending, config.keep_terminator) {
+            // Writing to a `String` can't fail
+            let _ = write!(line, "{ending}");
+        }
+        Ok(ending)
+    }
+
+    /// Consume up to and including the next terminator
+    fn skip_line(&mut self) {
+        let config = self.line_config;
+        while let Some(result) = self.try_next() {
+            let ch = match result {
+                Ok(ch) => ch,
+                Err(DecodeError::InvalidSequence { .. }) => continue,
+                Err(DecodeError::Io { .. }) => break,
+            };
+            // A `\r` that ends a line takes the `\n` of a `\r\n` with it
+            if config.breaks_line(ch, false) {
+                if ch == '\r' && config.lf && self.peek() == Some('\n') {
+                    self.next();
+                }
+                break;
+            }
+        }
+    }
+}
+impl<T> Iterator for StringStream<T>
+where
+    T: Read,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Fast path for complete, valid sequences. A byte order mark
+        // may still be buffered after restoring the start of the source
+        if self.start < self.end && self.lookahead.is_empty() && !self.detect_bom {
+            if let Decoded::Char(ch, len) = self.encoding.decode(self.buffer()) {
+                self.consume(len);
+                self.advance(ch);
+                return Some(ch);
+            }
+        }
+
+        self.next_slow()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use super::{
+        DecodeError, DecodeMode, LineConfig, LineEnding, LineError, LineOverflow, LineTooLong,
+        Position, StringStream,
+    };
+
+    #[test]
+    fn one_byte_test() {
+        let s = "h";
+        let cur = std::io::Cursor::new(s);
+        let mut stream = StringStream::new(cur);
+        assert_eq!(stream.next(), Some('h'));
+    }
+
+    #[test]
+    fn two_byte_test() {
+        let s = "\u{1B1}";
+        let cur = std::io::Cursor::new(s);
+        let mut stream = StringStream::new(cur);
+        assert_eq!(stream.next(), Some('\u{1B1}'));
+    }
+
+    #[test]
+    fn three_byte_test() {
+        let s = "\u{2713}";
+        let cur = std::io::Cursor::new(s);
+        let mut stream = StringStream::new(cur);
+        assert_eq!(stream.next(), Some('\u{2713}'));
+    }
+
+    #[test]
+    fn four_byte_test() {
+        let s = "😉";
+        let cur = std::io::Cursor::new(s);
+        let mut stream = StringStream::new(cur);
+        assert_eq!(stream.next(), Some('😉'));
+    }
+
+    #[test]
+    fn next_line_test() {
+        let with_trailing = Cursor::new("hi\nhow\r\nare\nyou?");
+        let without_trailing = Cursor::new("hi\nhow\r\nare\nyou?\n");
+
+        let expected = ["hi", "how", "are", "you?"];
+
+        fn get_lines(data: impl Read) -> Vec<String> {
+            let mut stream = StringStream::new(data);
+            let mut lines = Vec::new();
+            while let Some(l) = stream.next_line() {
+                lines.push(l);
+            }
+
+            lines
+        }
+
+        assert_eq!(get_lines(with_trailing), expected);
+        assert_eq!(get_lines(without_trailing), expected);
+    }
+
+    #[test]
+    fn lines_test() {
+        let s = Cursor::new("how\nmuch\nwood\nwould\na\nwoodchuck\nchuck?");
+        let stream = StringStream::new(s);
+        let lines = stream.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec!["how", "much", "wood", "would", "a", "woodchuck", "chuck?"]
+        )
+    }
+
+    // Lines iterator should read entire file
+    // even if it has blank lines in the middle
+    #[test]
+    fn lines_empty_space_test() {
+        let data = Cursor::new(
+            "Line 1
+                    Line 2
+
+                    Line 3",
+        );
+
+        let stream = StringStream::new(data);
+
+        let lines = stream
+            .lines()
+            .map(|x| x.trim().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, ["Line 1", "Line 2", "", "Line 3"]);
+    }
+
+    #[test]
+    fn lines_empty_string_test() {
+        let data = Cursor::new("");
+        let lines = StringStream::new(data).lines().collect::<Vec<_>>();
+
+        assert!(lines.is_empty())
+    }
+
+    #[test]
+    fn lines_trailing_space_test() {
+        #[rustfmt::skip]
+        let data =
+            "Line 1
+             Line 2 
+
+
+
+
+
+
+            ";
+
+        let stream = StringStream::new(Cursor::new(data));
 
         let lines = stream
             .lines()
@@ -280,4 +1313,1102 @@ mod tests {
 
         assert_eq!(lines, ["Line 1", "Line 2", "", "", "", "", "", "", ""])
     }
+
+    #[test]
+    fn try_chars_invalid_test() {
+        let data: &[u8] = b"a\xFFb\xE2\x9C\xE2\x9C\x93\xF0\x9F";
+        let mut stream = StringStream::new(data);
+        let res = stream.try_chars().collect::<Vec<_>>();
+
+        assert_eq!(res.len(), 6);
+        assert_eq!(res[0].as_ref().unwrap(), &'a');
+        assert!(matches!(
+            &res[1],
+            Err(DecodeError::InvalidSequence { offset: 1, bytes }) if bytes == &[0xFF]
+        ));
+        assert_eq!(res[2].as_ref().unwrap(), &'b');
+        // Truncated sequence, next lead byte is not swallowed
+        assert!(matches!(
+            &res[3],
+            Err(DecodeError::InvalidSequence { offset: 3, bytes }) if bytes == &[0xE2, 0x9C]
+        ));
+        assert_eq!(res[4].as_ref().unwrap(), &'\u{2713}');
+
+        // Truncated by EOF
+        assert!(matches!(
+            &res[5],
+            Err(DecodeError::InvalidSequence { offset: 8, bytes }) if bytes == &[0xF0, 0x9F]
+        ));
+    }
+
+    #[test]
+    fn try_chars_io_error_test() {
+        struct Failing(usize);
+        impl Read for Failing {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(std::io::Error::other("broken"));
+                }
+                self.0 -= 1;
+                buf[0] = b'x';
+                Ok(1)
+            }
+        }
+
+        let mut stream = StringStream::new(Failing(2));
+        assert_eq!(stream.try_next().unwrap().unwrap(), 'x');
+        assert_eq!(stream.try_next().unwrap().unwrap(), 'x');
+
+        let err = stream.try_next().unwrap().unwrap_err();
+        assert!(matches!(err, DecodeError::Io { offset: 2, .. }));
+        assert_eq!(err.to_string(), "I/O error at byte 2: broken");
+    }
+
+    #[test]
+    fn next_stops_on_error_test() {
+        let data: &[u8] = b"ab\xC0";
+        let stream = StringStream::new(data);
+        assert_eq!(stream.collect::<String>(), "ab");
+    }
+
+    fn lossy(data: &[u8]) -> String {
+        StringStream::new(data)
+            .with_mode(DecodeMode::Lossy)
+            .collect()
+    }
+
+    #[test]
+    fn lossy_matches_std_test() {
+        let cases: &[&[u8]] = &[
+            b"",
+            b"plain ascii",
+            b"\xFF",
+            b"a\x80b",
+            b"\x80\x80\x80",
+            b"\xE2\x9C",
+            b"\xE2\x9CA",
+            b"\xE2\x9C\xE2\x9C\x93",
+            b"\xF0\x9F\x98",
+            b"\xF0\x9F\x98A\xF0\x9F\x98\x89",
+            b"\xC3",
+            b"\xC3\xC3\xA9",
+            b"\xF8\x88\x80\x80\x80",
+            b"\xFE\xFF",
+            b"\xC0\x80",
+            b"\xE0\x80\xAF",
+            b"\xED\xA0\x80",
+            b"\xF4\x90\x80\x80",
+            b"\xF5\x80",
+            "caf\u{e9} \u{2713} \u{1F609}".as_bytes(),
+        ];
+
+        for case in cases {
+            assert_eq!(
+                lossy(case),
+                String::from_utf8_lossy(case),
+                "input: {case:02X?}"
+            );
+        }
+    }
+
+    #[test]
+    fn lossy_lines_test() {
+        let data: &[u8] = b"one\xFF\ntwo\xE2\x9C\r\nthree";
+        let lines = StringStream::new(data)
+            .with_mode(DecodeMode::Lossy)
+            .lines()
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, ["one\u{FFFD}", "two\u{FFFD}", "three"]);
+    }
+
+    #[test]
+    fn lossy_matches_std_random_test() {
+        // xorshift, biased towards bytes that form interesting sequences
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        const INTERESTING: [u8; 12] = [
+            0x41, 0x80, 0x8F, 0x90, 0x9F, 0xA0, 0xBF, 0xC2, 0xE0, 0xED, 0xF0, 0xF4,
+        ];
+
+        for _ in 0..2000 {
+            let len = (next() % 12) as usize;
+            let data = (0..len)
+                .map(|_| match next() % 3 {
+                    0 => next() as u8,
+                    _ => INTERESTING[(next() % 12) as usize],
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                lossy(&data),
+                String::from_utf8_lossy(&data),
+                "input: {data:02X?}"
+            );
+        }
+    }
+
+    #[test]
+    fn position_test() {
+        let data = Cursor::new("ab\r\n\u{2713}c\n\nd");
+        let mut stream = StringStream::new(data);
+        assert_eq!(stream.position(), Position::default());
+
+        let positions = stream
+            .char_positions()
+            .map(|(pos, ch)| (ch, pos.offset, pos.line, pos.column))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            positions,
+            [
+                ('a', 0, 1, 1),
+                ('b', 1, 1, 2),
+                ('\r', 2, 1, 3),
+                ('\n', 3, 1, 4),
+                ('\u{2713}', 4, 2, 1),
+                ('c', 7, 2, 2),
+                ('\n', 8, 2, 3),
+                ('\n', 9, 3, 1),
+                ('d', 10, 4, 1),
+            ]
+        );
+        assert_eq!(
+            stream.position(),
+            Position {
+                offset: 11,
+                line: 4,
+                column: 2
+            }
+        );
+    }
+
+    #[test]
+    fn position_next_line_test() {
+        let text = "first\r\nsecond \u{2713}\nthird";
+        for capacity in [4, 7, 64] {
+            let mut stream = StringStream::with_capacity(capacity, text.as_bytes());
+
+            assert_eq!(stream.next().unwrap(), 'f');
+            assert_eq!(stream.next_line().unwrap(), "irst");
+            assert_eq!(stream.position().line, 2);
+            assert_eq!(stream.position().offset, 7);
+
+            stream.next_line().unwrap();
+            assert_eq!(stream.position().line, 3);
+            assert_eq!(stream.position().column, 1);
+
+            assert_eq!(stream.next().unwrap(), 't');
+            assert_eq!(stream.next().unwrap(), 'h');
+            let pos = stream.position();
+            assert_eq!((pos.line, pos.column, pos.offset), (3, 3, 20));
+            assert_eq!(pos.to_string(), "line 3, column 3");
+        }
+    }
+
+    #[test]
+    fn position_line_config_test() {
+        // Lines end at whichever terminators the config has
+        let config = LineConfig {
+            cr: true,
+            unicode: true,
+            delimiter: Some(';'),
+            ..Default::default()
+        };
+        let data = "a\rbc\r\r\nd\u{2028}e;f\n";
+        for capacity in [4, 64] {
+            let mut stream =
+                StringStream::with_capacity(capacity, data.as_bytes()).with_line_config(config);
+            // Decoded ahead, positions must not change
+            assert_eq!(stream.peek_nth(6), Some('\n'));
+
+            let positions = stream
+                .char_positions()
+                .map(|(pos, ch)| (ch, pos.offset, pos.line, pos.column))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                positions,
+                [
+                    ('a', 0, 1, 1),
+                    ('\r', 1, 1, 2),
+                    ('b', 2, 2, 1),
+                    ('c', 3, 2, 2),
+                    ('\r', 4, 2, 3),
+                    ('\r', 5, 3, 1),
+                    ('\n', 6, 4, 1),
+                    ('d', 7, 4, 1),
+                    ('\u{2028}', 8, 4, 2),
+                    ('e', 11, 5, 1),
+                    (';', 12, 5, 2),
+                    ('f', 13, 6, 1),
+                    ('\n', 14, 6, 2),
+                ]
+            );
+            assert_eq!((stream.position().line, stream.position().column), (7, 1));
+        }
+
+        // Same through `next_line` and for lines that are too long
+        let config = LineConfig {
+            lf: false,
+            cr: true,
+            ..limited(3, LineOverflow::Error)
+        };
+        for capacity in [4, 64] {
+            let mut stream = StringStream::with_capacity(capacity, "ab\rcd\rtoolong\rx".as_bytes())
+                .with_line_config(config);
+            assert_eq!(too_long(stream.try_next_line()), Some(Ok("ab".to_owned())));
+            assert_eq!(too_long(stream.try_next_line()), Some(Ok("cd".to_owned())));
+            let error = too_long(stream.try_next_line()).unwrap().unwrap_err();
+            assert_eq!(
+                error.start,
+                Position {
+                    offset: 6,
+                    line: 3,
+                    column: 1
+                }
+            );
+            assert_eq!(stream.position().line, 4);
+        }
+    }
+
+    #[test]
+    fn position_invalid_test() {
+        let data: &[u8] = b"a\xFFb\n\xE2\x9Cc";
+        let mut stream = StringStream::new(data).with_mode(DecodeMode::Lossy);
+        assert_eq!(stream.next_line().unwrap(), "a\u{FFFD}b");
+        assert_eq!(stream.next_line().unwrap(), "\u{FFFD}c");
+
+        let pos = stream.position();
+        assert_eq!((pos.line, pos.column, pos.offset), (2, 3, 7));
+
+        let mut stream = StringStream::new(data);
+        let chars = stream
+            .try_chars()
+            .filter_map(Result::ok)
+            .collect::<String>();
+        assert_eq!(chars, "ab\nc");
+
+        let pos = stream.position();
+        assert_eq!((pos.line, pos.column, pos.offset), (2, 2, 7));
+    }
+
+    #[test]
+    fn peek_test() {
+        let mut stream = StringStream::new(Cursor::new("a\u{2713}c"));
+
+        assert_eq!(stream.peek(), Some('a'));
+        assert_eq!(stream.peek(), Some('a'));
+        assert_eq!(stream.peek_nth(2), Some('c'));
+        assert_eq!(stream.peek_nth(3), None);
+        assert_eq!(stream.position().offset, 0);
+
+        assert_eq!(stream.next(), Some('a'));
+        assert_eq!(stream.position().offset, 1);
+        assert_eq!(stream.peek_nth(1), Some('c'));
+        assert_eq!(stream.collect::<String>(), "\u{2713}c");
+    }
+
+    #[test]
+    fn peek_next_line_test() {
+        let mut stream = StringStream::with_capacity(4, Cursor::new("ab\r\ncd\nef"));
+
+        assert_eq!(stream.peek_nth(5), Some('d'));
+        assert_eq!(stream.next_line().unwrap(), "ab");
+        assert_eq!(stream.position().line, 2);
+
+        assert_eq!(stream.peek(), Some('c'));
+        assert_eq!(stream.next_line().unwrap(), "cd");
+        assert_eq!(stream.next_line().unwrap(), "ef");
+        assert_eq!(stream.next_line(), None);
+    }
+
+    #[test]
+    fn peek_invalid_test() {
+        let data: &[u8] = b"a\xFFb";
+
+        let mut stream = StringStream::new(data);
+        assert_eq!(stream.peek_nth(2), None);
+        assert_eq!(stream.peek(), Some('a'));
+        let res = stream.try_chars().collect::<Vec<_>>();
+        assert!(matches!(
+            res.as_slice(),
+            [
+                Ok('a'),
+                Err(DecodeError::InvalidSequence { offset: 1, .. }),
+                Ok('b')
+            ]
+        ));
+
+        let mut stream = StringStream::new(data).with_mode(DecodeMode::Lossy);
+        assert_eq!(stream.peek_nth(1), Some('\u{FFFD}'));
+        assert_eq!(stream.peek_nth(2), Some('b'));
+        assert_eq!(stream.collect::<String>(), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn unread_test() {
+        let mut stream = StringStream::new(Cursor::new("ab\ncd"));
+
+        assert_eq!(stream.next(), Some('a'));
+        assert_eq!(stream.next(), Some('b'));
+        assert_eq!(stream.next(), Some('\n'));
+        assert_eq!(stream.position().line, 2);
+
+        stream.unread('\n');
+        let pos = stream.position();
+        assert_eq!((pos.offset, pos.line, pos.column), (2, 1, 3));
+        assert_eq!(stream.peek(), Some('\n'));
+
+        stream.unread('b');
+        stream.unread('a');
+        let pos = stream.position();
+        assert_eq!((pos.offset, pos.line, pos.column), (0, 1, 1));
+
+        let positions = stream
+            .char_positions()
+            .map(|(pos, ch)| (ch, pos.offset, pos.line, pos.column))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [
+                ('a', 0, 1, 1),
+                ('b', 1, 1, 2),
+                ('\n', 2, 1, 3),
+                ('c', 3, 2, 1),
+                ('d', 4, 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn unread_next_line_test() {
+        let mut stream = StringStream::new(Cursor::new("x\u{2713}\nrest"));
+        let first = stream.next().unwrap();
+        stream.unread(first);
+        stream.unread('>');
+
+        assert_eq!(stream.next_line().unwrap(), ">x\u{2713}");
+        assert_eq!(stream.next_line().unwrap(), "rest");
+    }
+
+    fn lines_with(data: &str, config: LineConfig) -> Vec<(String, Option<LineEnding>)> {
+        StringStream::with_capacity(4, data.as_bytes())
+            .with_line_config(config)
+            .lines_with_endings()
+            .collect()
+    }
+
+    fn line(text: &str, ending: Option<LineEnding>) -> (String, Option<LineEnding>) {
+        (text.to_owned(), ending)
+    }
+
+    #[test]
+    fn line_endings_default_test() {
+        let lines = lines_with("a\nb\r\nc\rd\u{2028}e", LineConfig::default());
+        assert_eq!(
+            lines,
+            [
+                line("a", Some(LineEnding::Lf)),
+                line("b", Some(LineEnding::CrLf)),
+                line("c\rd\u{2028}e", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_endings_cr_test() {
+        let config = LineConfig {
+            cr: true,
+            ..Default::default()
+        };
+        let lines = lines_with("a\rb\r\nc\n\r\rd", config);
+        assert_eq!(
+            lines,
+            [
+                line("a", Some(LineEnding::Cr)),
+                line("b", Some(LineEnding::CrLf)),
+                line("c", Some(LineEnding::Lf)),
+                line("", Some(LineEnding::Cr)),
+                line("", Some(LineEnding::Cr)),
+                line("d", None),
+            ]
+        );
+
+        // Classic Mac only, line feeds are content
+        let config = LineConfig {
+            lf: false,
+            cr: true,
+            ..Default::default()
+        };
+        let lines = lines_with("a\rb\nc\r\n", config);
+        assert_eq!(
+            lines,
+            [
+                line("a", Some(LineEnding::Cr)),
+                line("b\nc", Some(LineEnding::Cr)),
+                line("\n", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_endings_unicode_test() {
+        let config = LineConfig {
+            unicode: true,
+            ..Default::default()
+        };
+        let lines = lines_with("a\u{85}\u{2713}\u{2028}c\u{2029}\u{C2}d\n", config);
+        assert_eq!(
+            lines,
+            [
+                line("a", Some(LineEnding::Nel)),
+                line("\u{2713}", Some(LineEnding::Ls)),
+                line("c", Some(LineEnding::Ps)),
+                line("\u{C2}d", Some(LineEnding::Lf)),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_endings_delimiter_test() {
+        let config = LineConfig {
+            lf: false,
+            delimiter: Some('\0'),
+            ..Default::default()
+        };
+        let lines = lines_with("a\nb\0c\0", config);
+        assert_eq!(
+            lines,
+            [
+                line("a\nb", Some(LineEnding::Custom('\0'))),
+                line("c", Some(LineEnding::Custom('\0'))),
+            ]
+        );
+
+        let config = LineConfig {
+            delimiter: Some('\u{2713}'),
+            ..Default::default()
+        };
+        let lines = lines_with("a\u{2713}b\u{2714}c\nd", config);
+        assert_eq!(
+            lines,
+            [
+                line("a", Some(LineEnding::Custom('\u{2713}'))),
+                line("b\u{2714}c", Some(LineEnding::Lf)),
+                line("d", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_endings_keep_terminator_test() {
+        let text = "a\nb\r\nc\rd\u{85}e\r\n\n";
+        let config = LineConfig {
+            cr: true,
+            unicode: true,
+            keep_terminator: true,
+            ..Default::default()
+        };
+        let lines = lines_with(text, config);
+        assert_eq!(
+            lines,
+            [
+                line("a\n", Some(LineEnding::Lf)),
+                line("b\r\n", Some(LineEnding::CrLf)),
+                line("c\r", Some(LineEnding::Cr)),
+                line("d\u{85}", Some(LineEnding::Nel)),
+                line("e\r\n", Some(LineEnding::CrLf)),
+                line("\n", Some(LineEnding::Lf)),
+            ]
+        );
+
+        let joined = lines.into_iter().map(|(x, _)| x).collect::<String>();
+        assert_eq!(joined, text);
+    }
+
+    #[test]
+    fn line_endings_lossless_test() {
+        let text = "mixed\r\nline\nendings\r\n\nno end";
+        let config = LineConfig {
+            cr: true,
+            ..Default::default()
+        };
+
+        let rebuilt = lines_with(text, config)
+            .into_iter()
+            .map(|(line, ending)| match ending {
+                Some(x) => format!("{line}{x}"),
+                None => line,
+            })
+            .collect::<String>();
+        assert_eq!(rebuilt, text);
+
+        let normalised = lines_with(text, config)
+            .into_iter()
+            .map(|(line, ending)| match ending {
+                Some(_) => format!("{line}\n"),
+                None => line,
+            })
+            .collect::<String>();
+        assert_eq!(normalised, "mixed\nline\nendings\n\nno end");
+    }
+
+    #[test]
+    fn read_line_into_test() {
+        let data = "a longer first line\r\nb\n\nlast";
+        let mut stream = StringStream::with_capacity(4, data.as_bytes());
+        let mut buf = String::new();
+
+        assert_eq!(
+            stream.read_line_into(&mut buf),
+            Some(Some(LineEnding::CrLf))
+        );
+        assert_eq!(buf, "a longer first line");
+        let ptr = buf.as_ptr();
+
+        assert_eq!(stream.read_line_into(&mut buf), Some(Some(LineEnding::Lf)));
+        assert_eq!(buf, "b");
+        assert_eq!(stream.read_line_into(&mut buf), Some(Some(LineEnding::Lf)));
+        assert_eq!(buf, "");
+        assert_eq!(stream.read_line_into(&mut buf), Some(None));
+        assert_eq!(buf, "last");
+        assert_eq!(stream.read_line_into(&mut buf), None);
+        assert_eq!(buf, "");
+
+        // No reallocation for shorter lines
+        assert_eq!(buf.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn for_each_line_test() {
+        let data = "how\nmuch\r\nwood\n\nwould";
+        let mut lines = Vec::new();
+        StringStream::new(data.as_bytes()).for_each_line(|x| lines.push(x.to_owned()));
+
+        let expected = StringStream::new(data.as_bytes())
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, expected);
+        assert_eq!(lines, ["how", "much", "wood", "", "would"]);
+    }
+
+    /// Result of `try_next_line` where only lines can be too long
+    fn too_long(result: Option<Result<String, LineError>>) -> Option<Result<String, LineTooLong>> {
+        result.map(|x| {
+            x.map_err(|e| match e {
+                LineError::TooLong(e) => e,
+                e => panic!("unexpected {e}"),
+            })
+        })
+    }
+
+    fn limited(max_len: usize, overflow: LineOverflow) -> LineConfig {
+        LineConfig {
+            max_len: Some(max_len),
+            overflow,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn max_len_truncate_test() {
+        let data = "short\nmuch too long\r\nex\u{e4}ct\nabc\u{e4}\u{e4}";
+        for capacity in [4, 7, 64] {
+            let lines = StringStream::with_capacity(capacity, data.as_bytes())
+                .with_line_config(limited(6, LineOverflow::Truncate))
+                .lines_with_endings()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                lines,
+                [
+                    line("short", Some(LineEnding::Lf)),
+                    line("much t", Some(LineEnding::CrLf)),
+                    line("ex\u{e4}ct", Some(LineEnding::Lf)),
+                    // Never cuts a character in half
+                    line("abc\u{e4}", None),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn max_len_split_test() {
+        let data = "abcdefg\nabc\n\u{2713}\u{2713}x\n";
+        for capacity in [4, 5, 64] {
+            let lines = StringStream::with_capacity(capacity, data.as_bytes())
+                .with_line_config(limited(3, LineOverflow::Split))
+                .lines_with_endings()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                lines,
+                [
+                    line("abc", None),
+                    line("def", None),
+                    line("g", Some(LineEnding::Lf)),
+                    line("abc", Some(LineEnding::Lf)),
+                    line("\u{2713}", None),
+                    line("\u{2713}", None),
+                    line("x", Some(LineEnding::Lf)),
+                ]
+            );
+        }
+
+        // A character longer than the limit still makes progress
+        let lines = lines_with("\u{2713}\u{2713}", limited(1, LineOverflow::Split));
+        assert_eq!(lines, [line("\u{2713}", None), line("\u{2713}", None)]);
+    }
+
+    #[test]
+    fn max_len_error_test() {
+        let data = "ok\nthis line is too long\nfine\nalso too long";
+        for capacity in [4, 64] {
+            let mut stream = StringStream::with_capacity(capacity, data.as_bytes())
+                .with_line_config(limited(5, LineOverflow::Error));
+
+            assert_eq!(too_long(stream.try_next_line()), Some(Ok("ok".to_owned())));
+            let error = too_long(stream.try_next_line()).unwrap().unwrap_err();
+            assert_eq!(
+                error.start,
+                Position {
+                    offset: 3,
+                    line: 2,
+                    column: 1
+                }
+            );
+            assert_eq!(error.max_len, 5);
+            assert_eq!(
+                too_long(stream.try_next_line()),
+                Some(Ok("fine".to_owned()))
+            );
+            assert_eq!(
+                too_long(stream.try_next_line())
+                    .unwrap()
+                    .unwrap_err()
+                    .start
+                    .line,
+                4
+            );
+            assert_eq!(too_long(stream.try_next_line()), None);
+        }
+
+        // Infallible methods stop at the first line that is too long
+        let lines = StringStream::new(data.as_bytes())
+            .with_line_config(limited(5, LineOverflow::Error))
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["ok"]);
+
+        let mut stream = StringStream::new("abcdef\r\nabc".as_bytes())
+            .with_line_config(limited(3, LineOverflow::Error));
+        let mut buf = String::new();
+        assert!(stream.try_read_line_into(&mut buf).unwrap().is_err());
+        assert_eq!(buf, "");
+        assert_eq!(stream.try_read_line_into(&mut buf).unwrap().unwrap(), None);
+        assert_eq!(buf, "abc");
+    }
+
+    #[test]
+    fn max_len_crlf_test() {
+        // The `\r` of a `\r\n` is part of the terminator, not the line
+        let data = "abc\r\nxy\n";
+        for capacity in 4..=8 {
+            let stream = || {
+                let trickle = Trickle {
+                    data: data.as_bytes(),
+                    interrupt: false,
+                };
+                [
+                    StringStream::with_capacity(
+                        capacity,
+                        Box::new(data.as_bytes()) as Box<dyn Read>,
+                    ),
+                    StringStream::with_capacity(capacity, Box::new(trickle)),
+                ]
+            };
+
+            for mut stream in stream() {
+                stream = stream.with_line_config(limited(3, LineOverflow::Error));
+                assert_eq!(too_long(stream.try_next_line()), Some(Ok("abc".to_owned())));
+                assert_eq!(too_long(stream.try_next_line()), Some(Ok("xy".to_owned())));
+                assert_eq!(too_long(stream.try_next_line()), None);
+            }
+
+            for stream in stream() {
+                let lines = stream
+                    .with_line_config(limited(3, LineOverflow::Split))
+                    .lines_with_endings()
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    lines,
+                    [
+                        line("abc", Some(LineEnding::CrLf)),
+                        line("xy", Some(LineEnding::Lf))
+                    ],
+                    "capacity {capacity}"
+                );
+            }
+        }
+
+        let lines = lines_with("abcd\r\nxy\r\n", limited(3, LineOverflow::Truncate));
+        assert_eq!(
+            lines,
+            [
+                line("abc", Some(LineEnding::CrLf)),
+                line("xy", Some(LineEnding::CrLf))
+            ]
+        );
+
+        // A lone `\r` still counts
+        let mut stream = StringStream::new("abc\rd\n".as_bytes())
+            .with_line_config(limited(3, LineOverflow::Error));
+        assert!(too_long(stream.try_next_line()).unwrap().is_err());
+        let lines = lines_with("abc\r\n", limited(3, LineOverflow::Split));
+        assert_eq!(lines, [line("abc", Some(LineEnding::CrLf))]);
+        let lines = lines_with("ab\r\rc", limited(3, LineOverflow::Split));
+        assert_eq!(lines, [line("ab\r", None), line("\rc", None)]);
+    }
+
+    #[test]
+    fn try_next_line_decode_test() {
+        let data = b"ok\nab\xFFcd\n\xFE\nend";
+        for capacity in [4, 64] {
+            let mut stream = StringStream::with_capacity(capacity, &data[..]);
+            assert_eq!(stream.try_next_line().unwrap().unwrap(), "ok");
+            let error = stream.try_next_line().unwrap().unwrap_err();
+            assert!(matches!(
+                error,
+                LineError::Decode(DecodeError::InvalidSequence { offset: 5, .. })
+            ));
+            assert_eq!(error.to_string(), "invalid sequence [FF] at byte 5");
+            // Carries on at the next line
+            assert!(stream.try_next_line().unwrap().is_err());
+            assert_eq!(stream.try_next_line().unwrap().unwrap(), "end");
+            assert!(stream.try_next_line().is_none());
+        }
+
+        // Part of the line before the error
+        let mut stream = StringStream::new(&data[..]);
+        let mut buf = String::new();
+        stream.read_line_into(&mut buf);
+        assert!(stream.try_read_line_into(&mut buf).unwrap().is_err());
+        assert_eq!(buf, "ab");
+
+        // Infallible methods end lines where `next` stops
+        let lines = StringStream::new(&data[..]).lines().collect::<Vec<_>>();
+        assert_eq!(lines, ["ok", "ab", "cd"]);
+        let mut stream = StringStream::new(&b"hello\xFF"[..]);
+        assert_eq!(stream.next_line().unwrap(), "hello");
+        assert_eq!(stream.next_line(), None);
+
+        // A terminator after the error ends the skipped line
+        let config = LineConfig {
+            cr: true,
+            ..LineConfig::default()
+        };
+        let mut stream = StringStream::new(&b"a\xFFb\r\nc\rd"[..]).with_line_config(config);
+        assert!(stream.try_next_line().unwrap().is_err());
+        assert_eq!(stream.try_next_line().unwrap().unwrap(), "c");
+        assert_eq!(stream.try_next_line().unwrap().unwrap(), "d");
+
+        // Nothing to report when invalid data is replaced
+        let mut stream = StringStream::new(&data[..]).with_mode(DecodeMode::Lossy);
+        stream.try_next_line();
+        assert_eq!(stream.try_next_line().unwrap().unwrap(), "ab\u{FFFD}cd");
+
+        let source = b"line\nrest".chain(Broken);
+        let mut stream = StringStream::with_capacity(4, source).with_mode(DecodeMode::Lossy);
+        assert_eq!(stream.try_next_line().unwrap().unwrap(), "line");
+        assert!(matches!(
+            stream.try_next_line(),
+            Some(Err(LineError::Decode(DecodeError::Io { .. })))
+        ));
+    }
+
+    /// Always fails to read
+    struct Broken;
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken"))
+        }
+    }
+
+    /// Hands out at most one byte per read, with interruptions
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+
+            let Some((first, rest)) = self.data.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *first;
+            self.data = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn buffer_boundaries_test() {
+        let text = "a\u{1B1}\u{2713}😉\r\nline \u{2713}\u{2713}\n\nlast😉";
+        let expected_lines = ["a\u{1B1}\u{2713}😉", "line \u{2713}\u{2713}", "", "last😉"];
+
+        for capacity in 4..=12 {
+            let stream = StringStream::with_capacity(capacity, text.as_bytes());
+            assert_eq!(stream.collect::<String>(), text, "capacity {capacity}");
+
+            let stream = StringStream::with_capacity(capacity, text.as_bytes());
+            let lines = stream.lines().collect::<Vec<_>>();
+            assert_eq!(lines, expected_lines, "capacity {capacity}");
+        }
+
+        let trickle = Trickle {
+            data: text.as_bytes(),
+            interrupt: false,
+        };
+        let lines = StringStream::new(trickle).lines().collect::<Vec<_>>();
+        assert_eq!(lines, expected_lines);
+    }
+
+    #[test]
+    fn buffer_boundaries_invalid_test() {
+        let data: &[u8] = b"ab\xE2\x9C\xE2\x9C\x93\xFF\ncd\xF0\x9F\x98";
+        for capacity in 4..=8 {
+            let stream = StringStream::with_capacity(capacity, data).with_mode(DecodeMode::Lossy);
+            let lines = stream.lines().collect::<Vec<_>>();
+            assert_eq!(lines, ["ab\u{FFFD}\u{2713}\u{FFFD}", "cd\u{FFFD}"]);
+        }
+    }
+
+    #[test]
+    fn lone_carriage_return_test() {
+        let data = Cursor::new("a\rb\r\nc\r");
+        let lines = StringStream::new(data).lines().collect::<Vec<_>>();
+        assert_eq!(lines, ["a\rb", "c\r"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn capacity_too_small_test() {
+        StringStream::with_capacity(3, Cursor::new(""));
+    }
+
+    /// Cases from Markus Kuhn's UTF-8 decoder capability and stress test
+    mod utf8_stress {
+        use super::lossy;
+        use crate::string_stream::StringStream;
+
+        fn decode(data: &[u8]) -> (String, usize) {
+            let mut stream = StringStream::new(data);
+            let mut chars = String::new();
+            let mut errors = 0;
+            for res in stream.try_chars() {
+                match res {
+                    Ok(ch) => chars.push(ch),
+                    Err(_) => errors += 1,
+                }
+            }
+
+            (chars, errors)
+        }
+
+        fn assert_valid(data: &[u8], expected: char) {
+            assert_eq!(decode(data), (expected.to_string(), 0), "{data:02X?}");
+        }
+
+        /// Every byte of a rejected sequence is accounted for
+        /// by U+FFFD following the maximal subpart practice
+        fn assert_invalid(data: &[u8], replacements: usize) {
+            let (chars, errors) = decode(data);
+            assert_eq!(chars, "", "{data:02X?}");
+            assert_eq!(errors, replacements, "{data:02X?}");
+            assert_eq!(lossy(data), String::from_utf8_lossy(data), "{data:02X?}");
+        }
+
+        // 1 Some correct UTF-8 text
+        #[test]
+        fn correct_text() {
+            let data = b"\xCE\xBA\xE1\xBD\xB9\xCF\x83\xCE\xBC\xCE\xB5";
+            let expected = "\u{3BA}\u{1F79}\u{3C3}\u{3BC}\u{3B5}";
+            assert_eq!(decode(data), (expected.to_owned(), 0));
+        }
+
+        // 2.1 First possible sequence of a certain length
+        #[test]
+        fn first_possible_sequence() {
+            assert_valid(b"\x00", '\u{0}');
+            assert_valid(b"\xC2\x80", '\u{80}');
+            assert_valid(b"\xE0\xA0\x80", '\u{800}');
+            assert_valid(b"\xF0\x90\x80\x80", '\u{10000}');
+
+            assert_invalid(b"\xF8\x88\x80\x80\x80", 5);
+            assert_invalid(b"\xFC\x84\x80\x80\x80\x80", 6);
+        }
+
+        // 2.2 Last possible sequence of a certain length
+        #[test]
+        fn last_possible_sequence() {
+            assert_valid(b"\x7F", '\u{7F}');
+            assert_valid(b"\xDF\xBF", '\u{7FF}');
+            assert_valid(b"\xEF\xBF\xBF", '\u{FFFF}');
+
+            assert_invalid(b"\xF7\xBF\xBF\xBF", 4);
+            assert_invalid(b"\xFB\xBF\xBF\xBF\xBF", 5);
+            assert_invalid(b"\xFD\xBF\xBF\xBF\xBF\xBF", 6);
+        }
+
+        // 2.3 Other boundary conditions
+        #[test]
+        fn other_boundary_conditions() {
+            assert_valid(b"\xED\x9F\xBF", '\u{D7FF}');
+            assert_valid(b"\xEE\x80\x80", '\u{E000}');
+            assert_valid(b"\xEF\xBF\xBD", '\u{FFFD}');
+            assert_valid(b"\xF4\x8F\xBF\xBF", '\u{10FFFF}');
+
+            assert_invalid(b"\xF4\x90\x80\x80", 4);
+        }
+
+        // 3.1 Unexpected continuation bytes
+        #[test]
+        fn unexpected_continuation_bytes() {
+            assert_invalid(b"\x80", 1);
+            assert_invalid(b"\xBF", 1);
+            assert_invalid(b"\x80\xBF", 2);
+            assert_invalid(b"\x80\xBF\x80", 3);
+            assert_invalid(b"\x80\xBF\x80\xBF", 4);
+            assert_invalid(b"\x80\xBF\x80\xBF\x80", 5);
+            assert_invalid(b"\x80\xBF\x80\xBF\x80\xBF", 6);
+            assert_invalid(b"\x80\xBF\x80\xBF\x80\xBF\x80", 7);
+
+            let all = (0x80..=0xBF).collect::<Vec<u8>>();
+            assert_invalid(&all, 64);
+        }
+
+        // 3.2 Lonely start characters
+        #[test]
+        fn lonely_start_characters() {
+            for first in 0xC0..=0xFD {
+                let data = [first, b' '];
+                let (chars, errors) = decode(&data);
+                assert_eq!(chars, " ", "{data:02X?}");
+                assert_eq!(errors, 1, "{data:02X?}");
+            }
+        }
+
+        // 3.3 Sequences with last continuation byte missing
+        #[test]
+        fn last_continuation_byte_missing() {
+            assert_invalid(b"\xC0", 1);
+            assert_invalid(b"\xE0\x80", 2);
+            assert_invalid(b"\xF0\x80\x80", 3);
+            assert_invalid(b"\xF8\x80\x80\x80", 4);
+            assert_invalid(b"\xFC\x80\x80\x80\x80", 5);
+
+            assert_invalid(b"\xDF", 1);
+            assert_invalid(b"\xEF\xBF", 1);
+            assert_invalid(b"\xF7\xBF\xBF", 3);
+            assert_invalid(b"\xFB\xBF\xBF\xBF", 4);
+            assert_invalid(b"\xFD\xBF\xBF\xBF\xBF", 5);
+        }
+
+        // 3.4 Concatenation of incomplete sequences
+        #[test]
+        fn concatenated_incomplete_sequences() {
+            let data = b"\xC0\xE0\x80\xF0\x80\x80\xF8\x80\x80\x80\xFC\x80\x80\x80\x80\
+                         \xDF\xEF\xBF\xF7\xBF\xBF\xFB\xBF\xBF\xBF\xFD\xBF\xBF\xBF\xBF";
+            assert_invalid(data, 29);
+        }
+
+        // 3.5 Impossible bytes
+        #[test]
+        fn impossible_bytes() {
+            assert_invalid(b"\xFE", 1);
+            assert_invalid(b"\xFF", 1);
+            assert_invalid(b"\xFE\xFE\xFF\xFF", 4);
+        }
+
+        // 4.1 Examples of an overlong ASCII character
+        #[test]
+        fn overlong_ascii() {
+            assert_invalid(b"\xC0\xAF", 2);
+            assert_invalid(b"\xE0\x80\xAF", 3);
+            assert_invalid(b"\xF0\x80\x80\xAF", 4);
+            assert_invalid(b"\xF8\x80\x80\x80\xAF", 5);
+            assert_invalid(b"\xFC\x80\x80\x80\x80\xAF", 6);
+        }
+
+        // 4.2 Maximum overlong sequences
+        #[test]
+        fn maximum_overlong() {
+            assert_invalid(b"\xC1\xBF", 2);
+            assert_invalid(b"\xE0\x9F\xBF", 3);
+            assert_invalid(b"\xF0\x8F\xBF\xBF", 4);
+            assert_invalid(b"\xF8\x87\xBF\xBF\xBF", 5);
+            assert_invalid(b"\xFC\x83\xBF\xBF\xBF\xBF", 6);
+        }
+
+        // 4.3 Overlong representation of the NUL character
+        #[test]
+        fn overlong_nul() {
+            assert_invalid(b"\xC0\x80", 2);
+            assert_invalid(b"\xE0\x80\x80", 3);
+            assert_invalid(b"\xF0\x80\x80\x80", 4);
+            assert_invalid(b"\xF8\x80\x80\x80\x80", 5);
+            assert_invalid(b"\xFC\x80\x80\x80\x80\x80", 6);
+        }
+
+        // 5.1 Single UTF-16 surrogates
+        #[test]
+        fn single_surrogates() {
+            for data in [
+                b"\xED\xA0\x80",
+                b"\xED\xAD\xBF",
+                b"\xED\xAE\x80",
+                b"\xED\xAF\xBF",
+                b"\xED\xB0\x80",
+                b"\xED\xBE\x80",
+                b"\xED\xBF\xBF",
+            ] {
+                assert_invalid(data, 3);
+            }
+        }
+
+        // 5.2 Paired UTF-16 surrogates
+        #[test]
+        fn paired_surrogates() {
+            for data in [
+                b"\xED\xA0\x80\xED\xB0\x80",
+                b"\xED\xA0\x80\xED\xBF\xBF",
+                b"\xED\xAD\xBF\xED\xB0\x80",
+                b"\xED\xAD\xBF\xED\xBF\xBF",
+                b"\xED\xAE\x80\xED\xB0\x80",
+                b"\xED\xAE\x80\xED\xBF\xBF",
+                b"\xED\xAF\xBF\xED\xB0\x80",
+                b"\xED\xAF\xBF\xED\xBF\xBF",
+            ] {
+                assert_invalid(data, 6);
+            }
+        }
+
+        // 5.3 Noncharacter code positions are valid UTF-8
+        #[test]
+        fn noncharacters() {
+            assert_valid(b"\xEF\xBF\xBE", '\u{FFFE}');
+            assert_valid(b"\xEF\xBF\xBF", '\u{FFFF}');
+            assert_valid(b"\xEF\xB7\x90", '\u{FDD0}');
+            assert_valid(b"\xEF\xB7\xAF", '\u{FDEF}');
+            assert_valid(b"\xF4\x8F\xBF\xBE", '\u{10FFFE}');
+        }
+    }
 }
@@ -0,0 +1,468 @@
+use std::future::poll_fn;
+use std::io::{Empty, ErrorKind};
+use std::ops::ControlFlow;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use futures_io::AsyncRead;
+
+use super::{
+    DecodeError, DecodeMode, Decoded, Encoding, LineConfig, LineEnding, LineError, LineState,
+    Pending, Position, StringStream,
+};
+
+/// Character based stream over an **AsyncRead**able type,
+/// the async counterpart of `StringStream`
+///
+/// Decoding is shared with `StringStream`, so invalid data, positions,
+/// byte order marks and line endings are handled the same way
+/// no matter how the source splits its reads
+///
+/// ---
+/// **NOTE**: Like the blocking `next`, the `Stream` implementation
+/// yields `None` at an invalid sequence with `DecodeMode::Stop`,
+/// polling again carries on after it
+pub struct AsyncStringStream<R>
+where
+    R: AsyncRead + Unpin,
+{
+    source: R,
+    /// Buffer and decoder, its own source is always at EOF.
+    /// Only asked to decode once a full sequence is buffered or `source` is done
+    inner: StringStream<Empty>,
+    eof: bool,
+}
+impl<R> AsyncStringStream<R>
+where
+    R: AsyncRead + Unpin,
+{
+    pub fn new(x: R) -> Self {
+        Self::with_capacity(super::DEFAULT_CAPACITY, x)
+    }
+
+    /// Create with an internal buffer of `capacity` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is less than 4
+    pub fn with_capacity(capacity: usize, x: R) -> Self {
+        Self {
+            source: x,
+            inner: StringStream::with_capacity(capacity, std::io::empty()),
+            eof: false,
+        }
+    }
+
+    /// Set how invalid data is handled
+    pub fn with_mode(mut self, mode: DecodeMode) -> Self {
+        self.inner = self.inner.with_mode(mode);
+        self
+    }
+
+    /// Decode the source as `encoding`
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.inner = self.inner.with_encoding(encoding);
+        self
+    }
+
+    /// Pick the encoding from a byte order mark at the start of the source
+    pub fn detect_encoding(mut self) -> Self {
+        self.inner = self.inner.detect_encoding();
+        self
+    }
+
+    /// Choose which terminators end a line in `next_line`,
+    /// and for the line numbers of `position`
+    pub fn with_line_config(mut self, config: LineConfig) -> Self {
+        self.inner = self.inner.with_line_config(config);
+        self
+    }
+
+    /// Encoding used to decode the source,
+    /// only reflects a detected byte order mark once reading has started
+    pub fn encoding(&self) -> Encoding {
+        self.inner.encoding()
+    }
+
+    /// Position of the next character to be read
+    pub fn position(&self) -> Position {
+        self.inner.position()
+    }
+
+    /// Buffered data isn't enough to decode the next character
+    fn needs_data(&mut self) -> bool {
+        if self.eof || !self.inner.lookahead.is_empty() {
+            return false;
+        }
+
+        if self.inner.detect_bom {
+            // Longest mark is 3 bytes. The inner source is never read
+            // here, so this can't fail
+            if self.inner.buffer().len() < 3 {
+                return true;
+            }
+            let _ = self.inner.read_bom();
+        }
+
+        let buffer = self.inner.buffer();
+        buffer.is_empty() || matches!(self.inner.encoding.decode(buffer), Decoded::Incomplete)
+    }
+
+    /// Read more data into the buffer
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let inner = &mut self.inner;
+        inner.make_room();
+        loop {
+            let read = Pin::new(&mut self.source).poll_read(cx, &mut inner.buf[inner.end..]);
+            match ready!(read) {
+                Ok(0) => self.eof = true,
+                Ok(n) => inner.end += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+            return Poll::Ready(Ok(()));
+        }
+    }
+
+    /// Read until the next character can be decoded without reading
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), DecodeError>> {
+        while self.needs_data() {
+            if let Err(error) = ready!(self.poll_fill(cx)) {
+                let offset = self.inner.offset() + self.inner.buffer().len() as u64;
+                return Poll::Ready(Err(DecodeError::Io { offset, error }));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Poll for the next character, reporting why decoding failed
+    pub fn poll_try_next(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<char, DecodeError>>> {
+        if let Err(error) = ready!(self.poll_ready(cx)) {
+            return Poll::Ready(Some(Err(error)));
+        }
+        Poll::Ready(self.inner.try_next())
+    }
+
+    /// Decode the next character ahead of time,
+    /// so the inner stream can peek at it without reading
+    async fn decode_ahead(&mut self) {
+        if !self.inner.lookahead.is_empty() {
+            return;
+        }
+        match poll_fn(|cx| self.poll_ready(cx)).await {
+            Ok(()) => {
+                self.inner.decode_ahead();
+            }
+            // Kept for the next read to report
+            Err(error) => self.inner.lookahead.push_back(Pending {
+                result: Err(error),
+                position: self.inner.decoder_position(),
+                after_cr: self.inner.after_cr,
+            }),
+        }
+    }
+
+    /// Decode the next character, reporting why decoding failed
+    pub async fn try_next(&mut self) -> Option<Result<char, DecodeError>> {
+        poll_fn(|cx| self.poll_try_next(cx)).await
+    }
+
+    /// Read until new line or EOF, splitting lines the same way
+    /// as `StringStream::next_line`
+    ///
+    /// ---
+    /// **NOTE**: Returns `None` for a line that is too long with
+    /// `LineOverflow::Error` or that starts with data `next` stops at
+    pub async fn next_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        self.read_line(&mut line).await?.ok()?;
+        Some(line)
+    }
+
+    /// Append the next line to `line`, a decoding error ends it the same as EOF
+    ///
+    /// ---
+    /// Returns `None` when nothing was read, otherwise the terminator
+    /// that ended the line
+    async fn read_line(
+        &mut self,
+        line: &mut String,
+    ) -> Option<Result<Option<LineEnding>, LineError>> {
+        // A byte order mark is not part of the line
+        poll_fn(|cx| self.poll_ready(cx)).await.ok()?;
+        let mut state = LineState::new(self.inner.position());
+
+        let ending = loop {
+            let result = self.try_next().await;
+            let Some(ch) = result.and_then(|x| self.inner.resolve(&x)) else {
+                if !state.read_any {
+                    return None;
+                }
+                break None;
+            };
+
+            // Only what follows a `\r` is peeked at
+            if ch == '\r' {
+                self.decode_ahead().await;
+            }
+            if let ControlFlow::Break(ending) = self.inner.line_char(&mut state, line, ch) {
+                break ending;
+            }
+        };
+
+        Some(self.inner.finish_line(&state, line, ending))
+    }
+}
+impl<R> Stream for AsyncStringStream<R>
+where
+    R: AsyncRead + Unpin,
+{
+    type Item = char;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let result = ready!(this.poll_try_next(cx));
+        Poll::Ready(result.and_then(|x| this.inner.resolve(&x)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::{poll_fn, Future};
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll, Waker};
+
+    use futures_core::Stream;
+    use futures_io::AsyncRead;
+
+    use super::AsyncStringStream;
+    use crate::string_stream::{
+        DecodeError, DecodeMode, Encoding, LineConfig, LineOverflow, StringStream,
+    };
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(x) = future.as_mut().poll(&mut cx) {
+                return x;
+            }
+        }
+    }
+
+    /// Hands out `chunk` bytes per read, returning `Pending` before each one
+    struct Chunked<'a> {
+        data: &'a [u8],
+        chunk: usize,
+        ready: bool,
+    }
+    impl<'a> Chunked<'a> {
+        fn new(data: &'a [u8], chunk: usize) -> Self {
+            Self {
+                data,
+                chunk,
+                ready: false,
+            }
+        }
+    }
+    impl AsyncRead for Chunked<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.ready = !self.ready;
+            if !self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            let len = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    fn collect_chars<R: AsyncRead + Unpin>(mut stream: AsyncStringStream<R>) -> String {
+        block_on(async {
+            let mut out = String::new();
+            while let Some(ch) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+                out.push(ch);
+            }
+            out
+        })
+    }
+
+    fn collect_lines<R: AsyncRead + Unpin>(mut stream: AsyncStringStream<R>) -> Vec<String> {
+        block_on(async {
+            let mut lines = Vec::new();
+            while let Some(line) = stream.next_line().await {
+                lines.push(line);
+            }
+            lines
+        })
+    }
+
+    #[test]
+    fn chars_test() {
+        let data = "a\u{e9}\u{2713}\u{1F600}\r\nz";
+        for chunk in 1..=5 {
+            let stream = AsyncStringStream::with_capacity(4, Chunked::new(data.as_bytes(), chunk));
+            assert_eq!(collect_chars(stream), data);
+        }
+    }
+
+    #[test]
+    fn lines_match_blocking_test() {
+        let data = "first\r\nsecond\n\r\n\rlone cr\r\n\u{2713}\u{1F600}\nlast\r";
+        let expected = StringStream::new(data.as_bytes())
+            .lines()
+            .collect::<Vec<_>>();
+
+        for chunk in 1..=7 {
+            for capacity in [4, 5, 64] {
+                let source = Chunked::new(data.as_bytes(), chunk);
+                let lines = collect_lines(AsyncStringStream::with_capacity(capacity, source));
+                assert_eq!(lines, expected, "chunk {chunk}, capacity {capacity}");
+            }
+        }
+        assert!(collect_lines(AsyncStringStream::new(Chunked::new(b"", 1))).is_empty());
+    }
+
+    #[test]
+    fn line_config_test() {
+        let data = "ab\r\ncd\rlonger line\r\n\u{85}e\u{2028}f;g\u{2713}\u{2713}h\r\r\n\nend\r";
+        let config = LineConfig::default();
+        let configs = [
+            config,
+            LineConfig {
+                lf: false,
+                cr: true,
+                ..config
+            },
+            LineConfig {
+                cr: true,
+                keep_terminator: true,
+                ..config
+            },
+            LineConfig {
+                unicode: true,
+                delimiter: Some(';'),
+                ..config
+            },
+            LineConfig {
+                delimiter: Some('\u{2713}'),
+                keep_terminator: true,
+                ..config
+            },
+            LineConfig {
+                max_len: Some(4),
+                overflow: LineOverflow::Split,
+                ..config
+            },
+            LineConfig {
+                max_len: Some(2),
+                overflow: LineOverflow::Truncate,
+                ..config
+            },
+            LineConfig {
+                cr: true,
+                max_len: Some(4),
+                overflow: LineOverflow::Error,
+                ..config
+            },
+        ];
+
+        for config in configs {
+            let expected = StringStream::new(data.as_bytes())
+                .with_line_config(config)
+                .lines()
+                .collect::<Vec<_>>();
+            for chunk in 1..=5 {
+                for capacity in [4, 64] {
+                    let source = Chunked::new(data.as_bytes(), chunk);
+                    let stream =
+                        AsyncStringStream::with_capacity(capacity, source).with_line_config(config);
+                    assert_eq!(collect_lines(stream), expected, "{config:?}, chunk {chunk}");
+                }
+            }
+        }
+
+        // Line numbers follow the terminators in use
+        let config = LineConfig { cr: true, ..config };
+        let mut stream =
+            AsyncStringStream::new(Chunked::new(b"a\rb\r\nc", 1)).with_line_config(config);
+        assert_eq!(block_on(stream.next_line()).unwrap(), "a");
+        assert_eq!(block_on(stream.next_line()).unwrap(), "b");
+        assert_eq!(stream.position().line, 3);
+    }
+
+    #[test]
+    fn invalid_test() {
+        let data = b"ok\xE2\x9C\nnext\xF0\x9F\x98";
+        for mode in [DecodeMode::Stop, DecodeMode::Lossy] {
+            let expected = StringStream::new(&data[..])
+                .with_mode(mode)
+                .lines()
+                .collect::<Vec<_>>();
+            for chunk in 1..=4 {
+                let stream = AsyncStringStream::new(Chunked::new(data, chunk)).with_mode(mode);
+                assert_eq!(collect_lines(stream), expected);
+            }
+        }
+
+        let mut stream = AsyncStringStream::new(Chunked::new(data, 1));
+        let errors = block_on(async {
+            let mut errors = Vec::new();
+            while let Some(result) = stream.try_next().await {
+                if let Err(DecodeError::InvalidSequence { offset, bytes }) = result {
+                    errors.push((offset, bytes));
+                }
+            }
+            errors
+        });
+        assert_eq!(errors, [(2, vec![0xE2, 0x9C]), (9, vec![0xF0, 0x9F, 0x98])]);
+    }
+
+    #[test]
+    fn encoding_test() {
+        let text = "bom \u{1F600}\nline";
+        let mut data = vec![0xFF, 0xFE];
+        data.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+
+        for chunk in 1..=3 {
+            let mut stream = AsyncStringStream::new(Chunked::new(&data, chunk)).detect_encoding();
+            let first = block_on(stream.next_line()).unwrap();
+            assert_eq!(first, "bom \u{1F600}");
+            assert_eq!(stream.encoding(), Encoding::Utf16Le);
+            assert_eq!(stream.position().line, 2);
+            assert_eq!(collect_chars(stream), "line");
+        }
+    }
+
+    #[test]
+    fn io_error_test() {
+        struct Failing;
+        impl AsyncRead for Failing {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                _: &mut Context<'_>,
+                _: &mut [u8],
+            ) -> Poll<std::io::Result<usize>> {
+                Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into()))
+            }
+        }
+
+        let mut stream = AsyncStringStream::new(Failing);
+        let result = block_on(stream.try_next());
+        assert!(matches!(
+            result,
+            Some(Err(DecodeError::Io { offset: 0, .. }))
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use super::{Encoding, Position, StringStream};
+
+/// Saved state of a `StringStream`, see `StringStream::checkpoint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    position: Position,
+    newline_column: usize,
+    after_cr: bool,
+    encoding: Encoding,
+    detect_bom: bool,
+}
+impl Checkpoint {
+    /// Position of the next character to be read after restoring
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read,
+{
+    /// Save the current position to go back to with `restore`
+    ///
+    /// ---
+    /// **NOTE**: Characters pushed back with `unread` are not saved,
+    /// after restoring the source is decoded again from the same offset
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: Self::position(self),
+            newline_column: self.newline_column,
+            after_cr: self.lookahead.front().map_or(self.after_cr, |x| x.after_cr),
+            encoding: self.encoding,
+            detect_bom: self.detect_bom,
+        }
+    }
+
+    /// Go back to `checkpoint` if its data is still in the buffer.
+    /// Works for any source, in-memory data fits when it is smaller
+    /// than the capacity
+    ///
+    /// ---
+    /// Returns `false` and leaves the stream as it is if the data is gone
+    pub fn restore_buffered(&mut self, checkpoint: &Checkpoint) -> bool {
+        let offset = checkpoint.position.offset;
+        let buffered = self.base..=self.base + self.end as u64;
+        if !buffered.contains(&offset) {
+            return false;
+        }
+
+        self.start = (offset - self.base) as usize;
+        self.reset(checkpoint);
+        true
+    }
+
+    /// Put the position, line, column and encoding back
+    fn reset(&mut self, checkpoint: &Checkpoint) {
+        self.lookahead.clear();
+        self.line = checkpoint.position.line;
+        self.column = checkpoint.position.column;
+        self.newline_column = checkpoint.newline_column;
+        self.after_cr = checkpoint.after_cr;
+        self.encoding = checkpoint.encoding;
+        self.detect_bom = checkpoint.detect_bom;
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read + Seek,
+{
+    /// Go back to a `checkpoint` made by this stream,
+    /// seeking the source if the data isn't buffered anymore
+    ///
+    /// ---
+    /// Offsets are relative to where the source was when the stream was
+    /// created, seeking is relative to the current source position
+    pub fn restore(&mut self, checkpoint: &Checkpoint) -> std::io::Result<()> {
+        if self.restore_buffered(checkpoint) {
+            return Ok(());
+        }
+
+        let offset = checkpoint.position.offset;
+        // The source is positioned just past the buffered data
+        let read = self.base + self.end as u64;
+        let delta = offset as i64 - read as i64;
+        self.source.seek(SeekFrom::Current(delta))?;
+
+        self.base = offset;
+        self.start = 0;
+        self.end = 0;
+        self.reset(checkpoint);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Seek, SeekFrom};
+
+    use crate::string_stream::{DecodeMode, Encoding, Position, StringStream};
+
+    #[test]
+    fn restore_test() {
+        let data = "first \u{2713}\nsecond\r\nthird \u{1F600} line\n";
+        for capacity in [4, 5, 16, 128] {
+            let mut stream = StringStream::with_capacity(capacity, Cursor::new(data));
+            assert_eq!(stream.next_line().unwrap(), "first \u{2713}");
+            let checkpoint = stream.checkpoint();
+
+            let expected = (StringStream::position(&stream), stream.next_line().unwrap());
+            assert_eq!(expected.1, "second");
+            let rest = stream.by_ref().collect::<String>();
+            assert_eq!(rest, "third \u{1F600} line\n");
+
+            for _ in 0..3 {
+                stream.restore(&checkpoint).unwrap();
+                assert_eq!(StringStream::position(&stream), checkpoint.position());
+                assert_eq!(
+                    (StringStream::position(&stream), stream.next_line().unwrap()),
+                    expected
+                );
+                assert_eq!(stream.next_line().unwrap(), "third \u{1F600} line");
+                assert_eq!(
+                    StringStream::position(&stream),
+                    Position {
+                        offset: data.len() as u64,
+                        line: 4,
+                        column: 1
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn backtrack_test() {
+        // Try to read a number, go back and read a word if that fails
+        let data = "12 abc 3x 45";
+        let mut stream = StringStream::with_capacity(4, Cursor::new(data));
+        let mut tokens = Vec::new();
+
+        while stream.peek().is_some() {
+            stream.skip_whitespace();
+            let checkpoint = stream.checkpoint();
+            match stream.next_token::<u32>() {
+                Some(Ok(x)) => tokens.push(x.to_string()),
+                Some(Err(_)) => {
+                    stream.restore(&checkpoint).unwrap();
+                    let word = stream.read_while(char::is_alphanumeric);
+                    tokens.push(format!("word {word}"));
+                }
+                None => break,
+            }
+        }
+        assert_eq!(tokens, ["12", "word abc", "word 3x", "45"]);
+    }
+
+    #[test]
+    fn peeked_and_unread_test() {
+        let mut stream = StringStream::with_capacity(4, Cursor::new("ab\ncd"));
+        stream.next();
+        // Peeked characters are still ahead after restoring
+        assert_eq!(stream.peek_nth(2), Some('c'));
+        let checkpoint = stream.checkpoint();
+        assert_eq!(stream.by_ref().collect::<String>(), "b\ncd");
+
+        stream.restore(&checkpoint).unwrap();
+        assert_eq!(stream.next_line().unwrap(), "b");
+
+        // A line feed pushed back gets its column back on restore
+        stream.unread('\n');
+        let checkpoint = stream.checkpoint();
+        assert_eq!(checkpoint.position().column, 3);
+        stream.next();
+        stream.restore(&checkpoint).unwrap();
+        assert_eq!(stream.next(), Some('\n'));
+        stream.unread('\n');
+        assert_eq!(StringStream::position(&stream), checkpoint.position());
+    }
+
+    #[test]
+    fn restore_buffered_test() {
+        let data = "in memory\nsource\n";
+        let mut stream = StringStream::new(data.as_bytes());
+        let checkpoint = stream.checkpoint();
+        stream.next_line();
+        stream.next_line();
+        assert!(stream.restore_buffered(&checkpoint));
+        assert_eq!(stream.next_line().unwrap(), "in memory");
+
+        // Small buffers drop the data, the stream is left as it was
+        let mut stream = StringStream::with_capacity(4, data.as_bytes());
+        let checkpoint = stream.checkpoint();
+        stream.next_line();
+        assert!(!stream.restore_buffered(&checkpoint));
+        assert_eq!(stream.next_line().unwrap(), "source");
+    }
+
+    #[test]
+    fn encoding_test() {
+        let mut data = vec![0xFE, 0xFF];
+        data.extend("x\ny\u{1F600}".encode_utf16().flat_map(u16::to_be_bytes));
+
+        let mut stream = StringStream::with_capacity(4, Cursor::new(data)).detect_encoding();
+        let start = stream.checkpoint();
+        assert_eq!(stream.by_ref().collect::<String>(), "x\ny\u{1F600}");
+
+        let mut stream = stream.with_encoding(Encoding::Latin1);
+        // Restoring the start detects the mark again
+        stream.restore(&start).unwrap();
+        assert_eq!(stream.next_line().unwrap(), "x");
+        assert_eq!(stream.encoding(), Encoding::Utf16Be);
+
+        // Mark still in the buffer when restoring
+        let data = "\u{FEFF}ab\ncd";
+        let mut stream = StringStream::new(data.as_bytes()).detect_encoding();
+        let start = stream.checkpoint();
+        assert_eq!(stream.next(), Some('a'));
+        assert!(stream.restore_buffered(&start));
+        assert_eq!(stream.char_positions().next().unwrap().0.offset, 3);
+        assert!(stream.restore_buffered(&start));
+        assert_eq!(stream.collect::<String>(), "ab\ncd");
+    }
+
+    #[test]
+    fn offset_source_test() {
+        // Source already past some data when the stream is created
+        let mut cursor = Cursor::new(b"skip|k\xC3\xA9ep \xFFthis".to_vec());
+        cursor.seek(SeekFrom::Start(5)).unwrap();
+
+        let mut stream = StringStream::with_capacity(4, cursor).with_mode(DecodeMode::Lossy);
+        let checkpoint = stream.checkpoint();
+        let all = stream.by_ref().collect::<String>();
+        assert_eq!(all, "k\u{e9}ep \u{FFFD}this");
+
+        stream.restore(&checkpoint).unwrap();
+        assert_eq!(stream.collect::<String>(), all);
+    }
+}
@@ -0,0 +1,330 @@
+use std::fs::File;
+use std::io::{Chain, Cursor, Read};
+use std::path::Path;
+
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use flate2::{FlushDecompress, Status};
+
+use super::StringStream;
+
+/// Compression detected by `Decompress`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Not compressed, read as is
+    None,
+    /// gzip, RFC 1952
+    Gzip,
+    /// zlib, RFC 1950
+    Zlib,
+}
+impl Compression {
+    /// Bytes needed to tell compressed data apart from plain data
+    const HEADER_LEN: usize = 7;
+    /// Bytes inflated to make sure data with a zlib header is zlib
+    const TRIAL_LEN: usize = 8 * 1024;
+}
+
+/// `header` is a zlib header followed by a valid deflate block header
+fn is_zlib_header(header: &[u8]) -> bool {
+    let [cmf, flg, block, ref rest @ ..] = *header else {
+        return false;
+    };
+    // Deflate with a window of at most 32K and no preset dictionary
+    let deflate = cmf & 0x0F == 8 && cmf >> 4 <= 7;
+    let check = u16::from_be_bytes([cmf, flg]) % 31 == 0;
+    if !deflate || !check || flg & 0x20 != 0 {
+        return false;
+    }
+
+    // Bits of the block header are read from the least significant
+    match (block >> 1) & 0b11 {
+        // Stored, the length is followed by its one's complement
+        0b00 => match *rest {
+            [a, b, c, d, ..] => u16::from_le_bytes([a, b]) == !u16::from_le_bytes([c, d]),
+            _ => false,
+        },
+        // Fixed Huffman codes
+        0b01 => true,
+        // Dynamic Huffman codes, at most 286 length and 30 distance codes
+        0b10 => match rest.first() {
+            Some(&next) => {
+                let bits = u16::from_le_bytes([block, next]);
+                let lengths = (bits >> 3) & 0x1F;
+                let distances = (bits >> 8) & 0x1F;
+                lengths <= 29 && distances <= 29
+            }
+            None => false,
+        },
+        // Reserved
+        _ => false,
+    }
+}
+
+/// `prefix` inflates without errors, to the end of the stream
+/// if it is the whole source
+fn inflates(prefix: &[u8], whole: bool) -> bool {
+    let mut inflate = flate2::Decompress::new(true);
+    let mut out = vec![0; 32 * 1024];
+    loop {
+        let read = inflate.total_in() as usize;
+        let written = inflate.total_out();
+        match inflate.decompress(&prefix[read..], &mut out, FlushDecompress::None) {
+            Ok(Status::StreamEnd) => return true,
+            Ok(_) if inflate.total_in() as usize == prefix.len() => return !whole,
+            Ok(_) if inflate.total_in() as usize == read && inflate.total_out() == written => {
+                return false
+            }
+            Ok(_) => {}
+            Err(_) => return false,
+        }
+    }
+}
+
+/// Read from `x` until `buf` is full or EOF, returning the length read
+fn read_full(x: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match x.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
+
+/// Source with the bytes read to detect compression put back in front
+type Prefixed<R> = Chain<Cursor<Vec<u8>>, R>;
+
+enum Inner<R>
+where
+    R: Read,
+{
+    Plain(Prefixed<R>),
+    Gzip(MultiGzDecoder<Prefixed<R>>),
+    Zlib(ZlibDecoder<Prefixed<R>>),
+}
+
+/// Reader that decompresses gzip and zlib data,
+/// telling them apart from plain data by their headers
+///
+/// Concatenated gzip members are read one after another,
+/// the same as `gzip -d` does
+///
+/// ---
+/// **NOTE**: As plain data starting with `x^` has a valid zlib header,
+/// data is only taken for zlib if its first 8 KiB also inflate without
+/// errors, and make up a complete zlib stream in a shorter source
+pub struct Decompress<R>
+where
+    R: Read,
+{
+    inner: Inner<R>,
+}
+impl<R> Decompress<R>
+where
+    R: Read,
+{
+    /// Read the first bytes of `x` to see if it is compressed
+    pub fn new(mut x: R) -> std::io::Result<Self> {
+        let mut prefix = vec![0; Compression::HEADER_LEN];
+        let len = read_full(&mut x, &mut prefix)?;
+        prefix.truncate(len);
+
+        // Only read further ahead for what looks like zlib,
+        // plain data from a pipe may be slow to arrive
+        let mut compression = match &*prefix {
+            [0x1F, 0x8B, ..] => Compression::Gzip,
+            _ if is_zlib_header(&prefix) => Compression::Zlib,
+            _ => Compression::None,
+        };
+        if compression == Compression::Zlib {
+            prefix.resize(Compression::TRIAL_LEN, 0);
+            let len = len + read_full(&mut x, &mut prefix[len..])?;
+            prefix.truncate(len);
+            if !inflates(&prefix, len < Compression::TRIAL_LEN) {
+                compression = Compression::None;
+            }
+        }
+
+        let source = Cursor::new(prefix).chain(x);
+        let inner = match compression {
+            Compression::None => Inner::Plain(source),
+            Compression::Gzip => Inner::Gzip(MultiGzDecoder::new(source)),
+            Compression::Zlib => Inner::Zlib(ZlibDecoder::new(source)),
+        };
+        Ok(Self { inner })
+    }
+
+    pub fn compression(&self) -> Compression {
+        match self.inner {
+            Inner::Plain(_) => Compression::None,
+            Inner::Gzip(_) => Compression::Gzip,
+            Inner::Zlib(_) => Compression::Zlib,
+        }
+    }
+}
+impl<R> Read for Decompress<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(x) => x.read(buf),
+            Inner::Gzip(x) => x.read(buf),
+            Inner::Zlib(x) => x.read(buf),
+        }
+    }
+}
+
+impl StringStream<Decompress<File>> {
+    /// Open the file at `path`, decompressing it if it is gzip or zlib
+    ///
+    /// ---
+    /// Offsets are in decompressed bytes
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self::new(Decompress::new(file)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ErrorKind, Write};
+    use std::path::PathBuf;
+
+    use flate2::write::{GzEncoder, ZlibEncoder};
+
+    use super::{Compression, Decompress};
+    use crate::string_stream::{DecodeError, StringStream};
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zlib(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Write `data` to a file only this test uses
+    fn fixture(name: &str, data: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rust-utils-{}-{name}", std::process::id()));
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    fn log() -> String {
+        (1..=2000)
+            .map(|x| format!("{x} GET /caf\u{e9}/{} 200\n", x * 7))
+            .collect()
+    }
+
+    /// Text that compresses to more than the bytes inflated on detection
+    fn noise() -> String {
+        let mut x = 1u32;
+        (0..40_000)
+            .map(|_| {
+                x = x.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                char::from_digit((x >> 16) % 16, 16).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn open_test() {
+        let text = log();
+        for (name, data) in [
+            ("plain.log", text.clone().into_bytes()),
+            ("rotated.log.gz", gzip(text.as_bytes())),
+            ("rotated.log.z", zlib(text.as_bytes())),
+        ] {
+            let path = fixture(name, &data);
+            let stream = StringStream::open(&path).unwrap();
+            let lines = stream.lines().collect::<Vec<_>>();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(lines.len(), 2000, "{name}");
+            assert_eq!(lines[1999], "2000 GET /caf\u{e9}/14000 200", "{name}");
+        }
+
+        let missing = StringStream::open(std::env::temp_dir().join("rust-utils-missing"));
+        assert_eq!(missing.err().unwrap().kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn detect_test() {
+        // An empty stored block in place of the empty fixed one
+        let mut stored = zlib(b"");
+        stored.splice(2..4, [0x01, 0x00, 0x00, 0xFF, 0xFF]);
+        let mut stream = StringStream::new(Decompress::new(&stored[..]).unwrap());
+        assert!(stream.try_next().is_none());
+        for (data, compression) in [
+            (gzip(b"a"), Compression::Gzip),
+            (zlib(b"a"), Compression::Zlib),
+            (zlib(log().as_bytes()), Compression::Zlib),
+            (stored, Compression::Zlib),
+            (b"xy".to_vec(), Compression::None),
+            (b"\x1F".to_vec(), Compression::None),
+            (Vec::new(), Compression::None),
+        ] {
+            let reader = Decompress::new(&data[..]).unwrap();
+            assert_eq!(reader.compression(), compression);
+        }
+        // Only the start of a long zlib source is inflated to detect it
+        let data = zlib(noise().as_bytes());
+        assert!(data.len() > 8 * 1024);
+        let stream = StringStream::new(Decompress::new(&data[..]).unwrap());
+        assert_eq!(stream.collect::<String>(), noise());
+
+        // Plain text with a valid zlib header
+        let long = "x^2 = 4\n".repeat(2000);
+        for text in [
+            "x^ is a valid zlib header",
+            "x^9 = 1",
+            "x^7 = y",
+            "x^2 = 4\n",
+            "x^3 ok\n",
+            "x^b + c",
+            "x^z",
+            &long,
+        ] {
+            let reader = Decompress::new(text.as_bytes()).unwrap();
+            assert_eq!(reader.compression(), Compression::None, "{text}");
+            assert_eq!(StringStream::new(reader).collect::<String>(), text);
+        }
+        // Or a preset dictionary, or too short for a block header
+        for data in [&b"\x78\xBB\x4B\x04\x00\x00\x00"[..], b"\x78\x9C"] {
+            let reader = Decompress::new(data).unwrap();
+            assert_eq!(reader.compression(), Compression::None);
+        }
+
+        // Short plain sources come through whole
+        let stream = StringStream::new(Decompress::new(&b"\x1F"[..]).unwrap());
+        assert_eq!(stream.collect::<String>(), "\x1F");
+    }
+
+    #[test]
+    fn multi_member_test() {
+        // `cat a.gz b.gz` decompresses to both
+        let mut data = gzip("first\n".as_bytes());
+        data.extend(gzip("second \u{1F600}\n".as_bytes()));
+        let mut stream = StringStream::with_capacity(4, Decompress::new(&data[..]).unwrap());
+        assert_eq!(stream.next_line().unwrap(), "first");
+        assert_eq!(stream.next_line().unwrap(), "second \u{1F600}");
+        assert_eq!(stream.next_line(), None);
+    }
+
+    #[test]
+    fn corrupt_test() {
+        let mut data = gzip(log().as_bytes());
+        data.truncate(data.len() / 2);
+        let mut stream = StringStream::new(Decompress::new(&data[..]).unwrap());
+        let error = stream.try_chars().find_map(Result::err).unwrap();
+        assert!(matches!(error, DecodeError::Io { .. }));
+    }
+}
@@ -0,0 +1,325 @@
+use super::{decode_utf8, Decoded};
+
+/// Text encoding of a `StringStream` source
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1, every byte maps to the code point of the same value
+    Latin1,
+    /// Latin-1 with printable characters in place of most C1 controls
+    Windows1252,
+}
+
+/// Code points for `0x80..=0x9F` in Windows-1252
+///
+/// ---
+/// The 5 unassigned bytes map to the C1 control of the same value,
+/// matching the WHATWG encoding standard
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+impl Encoding {
+    /// Identify a byte order mark at the start of `bytes`,
+    /// returning the encoding and length of the mark
+    pub fn from_bom(bytes: &[u8]) -> Option<(Self, usize)> {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => Some((Self::Utf8, 3)),
+            [0xFF, 0xFE, ..] => Some((Self::Utf16Le, 2)),
+            [0xFE, 0xFF, ..] => Some((Self::Utf16Be, 2)),
+            _ => None,
+        }
+    }
+
+    /// Byte order mark for the encoding, if it has one
+    pub fn bom(self) -> Option<&'static [u8]> {
+        match self {
+            Self::Utf8 => Some(&[0xEF, 0xBB, 0xBF]),
+            Self::Utf16Le => Some(&[0xFF, 0xFE]),
+            Self::Utf16Be => Some(&[0xFE, 0xFF]),
+            Self::Latin1 | Self::Windows1252 => None,
+        }
+    }
+
+    /// Encode `ch` into `buf`, `None` if the encoding can't represent it
+    pub fn encode(self, ch: char, buf: &mut [u8; 4]) -> Option<&[u8]> {
+        let byte = match self {
+            Self::Utf8 => return Some(ch.encode_utf8(buf).as_bytes()),
+            Self::Utf16Le | Self::Utf16Be => {
+                let mut units = [0; 2];
+                let units = ch.encode_utf16(&mut units);
+                for (i, unit) in units.iter().enumerate() {
+                    let bytes = match self {
+                        Self::Utf16Le => unit.to_le_bytes(),
+                        _ => unit.to_be_bytes(),
+                    };
+                    buf[i * 2..i * 2 + 2].copy_from_slice(&bytes);
+                }
+                return Some(&buf[..units.len() * 2]);
+            }
+            Self::Latin1 => u8::try_from(ch).ok()?,
+            Self::Windows1252 => match WINDOWS_1252_HIGH.iter().position(|&x| x == ch) {
+                Some(i) => 0x80 + i as u8,
+                None => u8::try_from(ch)
+                    .ok()
+                    .filter(|x| !(0x80..=0x9F).contains(x))?,
+            },
+        };
+
+        buf[0] = byte;
+        Some(&buf[..1])
+    }
+
+    /// Number of bytes `ch` takes up when encoded
+    pub fn encoded_len(self, ch: char) -> usize {
+        match self {
+            Self::Utf8 => ch.len_utf8(),
+            Self::Utf16Le | Self::Utf16Be => ch.len_utf16() * 2,
+            Self::Latin1 | Self::Windows1252 => 1,
+        }
+    }
+
+    /// Decode the sequence at the start of `bytes`, which must not be empty
+    #[inline]
+    pub(super) fn decode(self, bytes: &[u8]) -> Decoded {
+        match self {
+            Self::Utf8 => decode_utf8(bytes),
+            Self::Utf16Le => decode_utf16(bytes, u16::from_le_bytes),
+            Self::Utf16Be => decode_utf16(bytes, u16::from_be_bytes),
+            Self::Latin1 => Decoded::Char(bytes[0] as char, 1),
+            Self::Windows1252 => {
+                let ch = match bytes[0] {
+                    x @ 0x80..=0x9F => WINDOWS_1252_HIGH[(x - 0x80) as usize],
+                    x => x as char,
+                };
+                Decoded::Char(ch, 1)
+            }
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> Decoded {
+    let unit = |i: usize| bytes.get(i..i + 2).map(|x| to_unit([x[0], x[1]]));
+
+    let Some(first) = unit(0) else {
+        return Decoded::Incomplete;
+    };
+
+    match first {
+        0xD800..=0xDBFF => match unit(2) {
+            Some(second @ 0xDC00..=0xDFFF) => {
+                let ch = 0x10000 + ((first as u32 - 0xD800) << 10 | (second as u32 - 0xDC00));
+                match char::from_u32(ch) {
+                    Some(ch) => Decoded::Char(ch, 4),
+                    None => Decoded::Invalid(2),
+                }
+            }
+            // Unpaired high surrogate
+            Some(_) => Decoded::Invalid(2),
+            None => Decoded::Incomplete,
+        },
+        // Unpaired low surrogate
+        0xDC00..=0xDFFF => Decoded::Invalid(2),
+        x => match char::from_u32(x as u32) {
+            Some(ch) => Decoded::Char(ch, 2),
+            None => Decoded::Invalid(2),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Encoding, WINDOWS_1252_HIGH};
+    use crate::string_stream::{DecodeMode, StringStream};
+
+    const TEXT: &str = "plain\r\nGr\u{FC}\u{DF}e \u{2713}\n\u{1F609} last";
+
+    fn utf16(text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+        text.encode_utf16().flat_map(to_bytes).collect()
+    }
+
+    fn decode(data: &[u8], encoding: Encoding) -> String {
+        StringStream::with_capacity(5, data)
+            .with_encoding(encoding)
+            .collect()
+    }
+
+    #[test]
+    fn utf16_round_trip_test() {
+        let le = utf16(TEXT, u16::to_le_bytes);
+        let be = utf16(TEXT, u16::to_be_bytes);
+
+        assert_eq!(decode(&le, Encoding::Utf16Le), TEXT);
+        assert_eq!(decode(&be, Encoding::Utf16Be), TEXT);
+
+        let lines = StringStream::new(le.as_slice())
+            .with_encoding(Encoding::Utf16Le)
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            ["plain", "Gr\u{FC}\u{DF}e \u{2713}", "\u{1F609} last"]
+        );
+    }
+
+    #[test]
+    fn utf16_invalid_test() {
+        // Unpaired high surrogate, unpaired low surrogate, truncated unit
+        let data = [0x3D, 0xD8, 0x41, 0x00, 0x00, 0xDC, 0x42, 0x00, 0x43];
+        let text = StringStream::new(data.as_slice())
+            .with_encoding(Encoding::Utf16Le)
+            .with_mode(DecodeMode::Lossy)
+            .collect::<String>();
+
+        assert_eq!(text, "\u{FFFD}A\u{FFFD}B\u{FFFD}");
+    }
+
+    #[test]
+    fn latin1_round_trip_test() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let text = decode(&data, Encoding::Latin1);
+
+        let encoded = text.chars().map(|x| x as u32 as u8).collect::<Vec<_>>();
+        assert_eq!(text.chars().count(), 256);
+        assert_eq!(encoded, data);
+    }
+
+    #[test]
+    fn windows_1252_round_trip_test() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let text = decode(&data, Encoding::Windows1252);
+
+        let encoded = text
+            .chars()
+            .map(|ch| match WINDOWS_1252_HIGH.iter().position(|&x| x == ch) {
+                Some(i) => 0x80 + i as u8,
+                None => ch as u32 as u8,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(text.chars().count(), 256);
+        assert_eq!(encoded, data);
+
+        let data = b"\x80 \x93quoted\x94 caf\xE9 \x85";
+        assert_eq!(
+            decode(data, Encoding::Windows1252),
+            "\u{20AC} \u{201C}quoted\u{201D} caf\u{E9} \u{2026}"
+        );
+    }
+
+    #[test]
+    fn utf8_round_trip_test() {
+        assert_eq!(decode(TEXT.as_bytes(), Encoding::Utf8), TEXT);
+    }
+
+    #[test]
+    fn encode_test() {
+        let text = "a\u{e9}\u{20AC}\u{81}\u{1F600}";
+        let mut buf = [0; 4];
+        for encoding in [Encoding::Utf8, Encoding::Utf16Le, Encoding::Utf16Be] {
+            let data = text
+                .chars()
+                .flat_map(|x| encoding.encode(x, &mut buf).unwrap().to_vec())
+                .collect::<Vec<_>>();
+            assert_eq!(decode(&data, encoding), text);
+            assert_eq!(
+                Encoding::from_bom(encoding.bom().unwrap()).unwrap().0,
+                encoding
+            );
+        }
+
+        assert_eq!(
+            Encoding::Latin1.encode('\u{e9}', &mut buf),
+            Some(&[0xE9][..])
+        );
+        assert_eq!(
+            Encoding::Latin1.encode('\u{81}', &mut buf),
+            Some(&[0x81][..])
+        );
+        assert_eq!(Encoding::Latin1.encode('\u{20AC}', &mut buf), None);
+        assert_eq!(
+            Encoding::Windows1252.encode('\u{20AC}', &mut buf),
+            Some(&[0x80][..])
+        );
+        assert_eq!(
+            Encoding::Windows1252.encode('\u{81}', &mut buf),
+            Some(&[0x81][..])
+        );
+        // Bytes 0x80..=0x9F decode to other characters
+        assert_eq!(Encoding::Windows1252.encode('\u{80}', &mut buf), None);
+        assert_eq!(Encoding::Windows1252.encode('\u{1F600}', &mut buf), None);
+        assert_eq!(Encoding::Latin1.bom(), None);
+    }
+
+    #[test]
+    fn detect_bom_test() {
+        let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+        utf8.extend(TEXT.as_bytes());
+        let mut le = vec![0xFF, 0xFE];
+        le.extend(utf16(TEXT, u16::to_le_bytes));
+        let mut be = vec![0xFE, 0xFF];
+        be.extend(utf16(TEXT, u16::to_be_bytes));
+
+        for (data, expected) in [
+            (utf8, Encoding::Utf8),
+            (le, Encoding::Utf16Le),
+            (be, Encoding::Utf16Be),
+        ] {
+            let mut stream = StringStream::new(data.as_slice())
+                .with_encoding(Encoding::Windows1252)
+                .detect_encoding();
+            let first_line = stream.next_line();
+
+            assert_eq!(stream.encoding(), expected);
+            assert_eq!(first_line.as_deref(), Some("plain"));
+            assert_eq!(
+                stream.lines().collect::<Vec<_>>(),
+                ["Gr\u{FC}\u{DF}e \u{2713}", "\u{1F609} last"]
+            );
+        }
+    }
+
+    #[test]
+    fn detect_bom_fallback_test() {
+        let data = b"caf\xE9";
+        let stream = StringStream::new(data.as_slice())
+            .with_encoding(Encoding::Latin1)
+            .detect_encoding();
+        assert_eq!(stream.collect::<String>(), "caf\u{E9}");
+
+        // Too short to hold a mark
+        let stream = StringStream::new(b"a".as_slice()).detect_encoding();
+        assert_eq!(stream.collect::<String>(), "a");
+
+        // Without detection the mark is kept
+        let stream = StringStream::new(b"\xEF\xBB\xBFa".as_slice());
+        assert_eq!(stream.collect::<String>(), "\u{FEFF}a");
+    }
+
+    #[test]
+    fn utf16_position_test() {
+        let mut data = vec![0xFF, 0xFE];
+        data.extend(utf16("a\n\u{1F609}b", u16::to_le_bytes));
+        let mut stream = StringStream::new(data.as_slice()).detect_encoding();
+
+        let positions = stream
+            .char_positions()
+            .map(|(pos, ch)| (ch, pos.offset, pos.line, pos.column))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            positions,
+            [
+                ('a', 2, 1, 1),
+                ('\n', 4, 1, 2),
+                ('\u{1F609}', 6, 2, 1),
+                ('b', 10, 2, 2),
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::io::Read;
+
+use super::{cmp_range, StringStream};
+
+mod tables;
+
+/// Grapheme_Cluster_Break value of a character, with
+/// Extended_Pictographic and InCB=Consonant split out of `Any`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Any,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    Pictographic,
+    Consonant,
+}
+impl Class {
+    fn of(ch: char) -> Self {
+        if ch.is_ascii() {
+            return match ch {
+                '\r' => Self::Cr,
+                '\n' => Self::Lf,
+                '\0'..='\x1F' | '\x7F' => Self::Control,
+                _ => Self::Any,
+            };
+        }
+
+        // Hangul syllables alternate between one LV and 27 LVT
+        if ('\u{AC00}'..='\u{D7A3}').contains(&ch) {
+            return match (ch as u32 - 0xAC00) % 28 {
+                0 => Self::Lv,
+                _ => Self::Lvt,
+            };
+        }
+
+        tables::CLASSES
+            .binary_search_by(|&(lo, hi, _)| cmp_range(ch, lo, hi))
+            .map_or(Self::Any, |i| tables::CLASSES[i].2)
+    }
+}
+
+fn is_incb_extend(ch: char) -> bool {
+    tables::INCB_EXTEND
+        .binary_search_by(|&(lo, hi)| cmp_range(ch, lo, hi))
+        .is_ok()
+}
+
+fn is_incb_linker(ch: char) -> bool {
+    tables::INCB_LINKERS.binary_search(&ch).is_ok()
+}
+
+/// Progress through an emoji ZWJ sequence (GB11)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emoji {
+    None,
+    /// Extended_Pictographic followed by any number of Extend
+    Base,
+    /// `Base` followed by ZWJ
+    Joined,
+}
+
+/// Progress through an Indic conjunct (GB9c)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Conjunct {
+    None,
+    /// Consonant followed by any number of InCB=Extend
+    Consonant,
+    /// `Consonant` followed by at least one linker
+    Linked,
+}
+
+/// Finds extended grapheme cluster boundaries (UAX #29) one character at a time
+#[derive(Debug, Clone)]
+struct Segmenter {
+    prev: Class,
+    emoji: Emoji,
+    conjunct: Conjunct,
+    /// Odd number of regional indicators in a row before the next character
+    odd_ri: bool,
+}
+impl Segmenter {
+    fn new(first: char) -> Self {
+        let mut segmenter = Self {
+            prev: Class::Any,
+            emoji: Emoji::None,
+            conjunct: Conjunct::None,
+            odd_ri: false,
+        };
+        segmenter.update(first, Class::of(first));
+        segmenter
+    }
+
+    /// Whether there is a boundary before `ch`, moving past it
+    fn push(&mut self, ch: char) -> bool {
+        use Class::*;
+
+        let next = Class::of(ch);
+        let split = match (self.prev, next) {
+            (Cr, Lf) => false,                                               // GB3
+            (Cr | Lf | Control, _) | (_, Cr | Lf | Control) => true,         // GB4, GB5
+            (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => false, // GB6-8
+            (_, Extend | Zwj | SpacingMark) | (Prepend, _) => false,         // GB9-9b
+            (_, Consonant) if self.conjunct == Conjunct::Linked => false,    // GB9c
+            (Zwj, Pictographic) if self.emoji == Emoji::Joined => false,     // GB11
+            (RegionalIndicator, RegionalIndicator) => !self.odd_ri,          // GB12, GB13
+            _ => true,                                                       // GB999
+        };
+
+        self.update(ch, next);
+        split
+    }
+
+    fn update(&mut self, ch: char, class: Class) {
+        self.emoji = match (self.emoji, class) {
+            (_, Class::Pictographic) => Emoji::Base,
+            (Emoji::Base, Class::Extend) => Emoji::Base,
+            (Emoji::Base, Class::Zwj) => Emoji::Joined,
+            _ => Emoji::None,
+        };
+
+        self.conjunct = match self.conjunct {
+            _ if class == Class::Consonant => Conjunct::Consonant,
+            Conjunct::Consonant | Conjunct::Linked if is_incb_linker(ch) => Conjunct::Linked,
+            x @ (Conjunct::Consonant | Conjunct::Linked) if is_incb_extend(ch) => x,
+            _ => Conjunct::None,
+        };
+
+        self.odd_ri = class == Class::RegionalIndicator && !self.odd_ri;
+        self.prev = class;
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read,
+{
+    /// Read the next extended grapheme cluster, a user-perceived character
+    /// such as a letter with combining marks, a flag or an emoji ZWJ sequence
+    ///
+    /// ---
+    /// `\r\n` is a single cluster
+    pub fn next_grapheme(&mut self) -> Option<String> {
+        let first = self.next()?;
+        let mut segmenter = Segmenter::new(first);
+        let mut cluster = String::from(first);
+
+        while let Some(ch) = self.peek() {
+            if segmenter.push(ch) {
+                break;
+            }
+            self.next();
+            cluster.push(ch);
+        }
+        Some(cluster)
+    }
+
+    /// Iterator over extended grapheme clusters
+    pub fn graphemes(mut self) -> impl Iterator<Item = String> {
+        std::iter::from_fn(move || self.next_grapheme())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::string_stream::StringStream;
+
+    fn graphemes(data: &str) -> Vec<String> {
+        StringStream::with_capacity(4, data.as_bytes())
+            .graphemes()
+            .collect()
+    }
+
+    #[test]
+    fn graphemes_test() {
+        let data = concat!(
+            "e\u{301}",
+            "\r\n",
+            "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}",
+            "\u{1F1EC}\u{1F1E7}",
+            "\u{1F1FA}",
+            "\u{1F44D}\u{1F3FD}",
+            "\u{D55C}",
+            "\u{1100}\u{1161}\u{11A8}",
+            "\u{915}\u{94D}\u{937}",
+            "x",
+        );
+        assert_eq!(
+            graphemes(data),
+            [
+                "e\u{301}",
+                "\r\n",
+                "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}",
+                "\u{1F1EC}\u{1F1E7}",
+                "\u{1F1FA}",
+                "\u{1F44D}\u{1F3FD}",
+                "\u{D55C}",
+                "\u{1100}\u{1161}\u{11A8}",
+                "\u{915}\u{94D}\u{937}",
+                "x",
+            ]
+        );
+        assert!(graphemes("").is_empty());
+    }
+
+    #[test]
+    fn truncate_test() {
+        // Keeping the first 3 clusters never cuts an emoji in half
+        let data = "ab\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}cd";
+        let kept = StringStream::new(data.as_bytes())
+            .graphemes()
+            .take(3)
+            .collect::<String>();
+        assert_eq!(kept, "ab\u{1F3F3}\u{FE0F}\u{200D}\u{1F308}");
+    }
+
+    /// Cases from the Unicode GraphemeBreakTest.txt data file
+    #[test]
+    fn grapheme_break_test() {
+        let fixture = include_str!("testdata/GraphemeBreakTest.txt");
+        let mut count = 0;
+
+        for (number, line) in fixture.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut expected = Vec::new();
+            let mut data = String::new();
+            for part in line.split('÷').map(str::trim).filter(|x| !x.is_empty()) {
+                let cluster = part
+                    .split('×')
+                    .map(|x| u32::from_str_radix(x.trim(), 16).unwrap())
+                    .map(char::from_u32)
+                    .collect::<Option<String>>();
+                // Surrogates can't be in a `str`
+                let Some(cluster) = cluster else {
+                    expected.clear();
+                    break;
+                };
+                data.push_str(&cluster);
+                expected.push(cluster);
+            }
+            if expected.is_empty() {
+                continue;
+            }
+
+            assert_eq!(graphemes(&data), expected, "line {}: {line}", number + 1);
+            count += 1;
+        }
+        assert_eq!(count, 766);
+    }
+}
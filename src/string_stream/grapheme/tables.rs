@@ -0,0 +1,939 @@
+// Unicode 17.0.0 Grapheme_Cluster_Break, Extended_Pictographic and
+// Indic_Conjunct_Break properties, generated from the Unicode Character
+// Database as compiled into the icu_properties crate 2.3.0.
+// Hangul LV and LVT syllables are computed instead of listed
+
+use super::Class::{self, *};
+
+/// Sorted, non-overlapping ranges of every class but `Any`
+#[rustfmt::skip]
+pub(super) const CLASSES: &[(char, char, Class)] = &[
+    ('\u{0}', '\u{9}', Control),
+    ('\u{A}', '\u{A}', Lf),
+    ('\u{B}', '\u{C}', Control),
+    ('\u{D}', '\u{D}', Cr),
+    ('\u{E}', '\u{1F}', Control),
+    ('\u{7F}', '\u{9F}', Control),
+    ('\u{A9}', '\u{A9}', Pictographic),
+    ('\u{AD}', '\u{AD}', Control),
+    ('\u{AE}', '\u{AE}', Pictographic),
+    ('\u{300}', '\u{36F}', Extend),
+    ('\u{483}', '\u{489}', Extend),
+    ('\u{591}', '\u{5BD}', Extend),
+    ('\u{5BF}', '\u{5BF}', Extend),
+    ('\u{5C1}', '\u{5C2}', Extend),
+    ('\u{5C4}', '\u{5C5}', Extend),
+    ('\u{5C7}', '\u{5C7}', Extend),
+    ('\u{600}', '\u{605}', Prepend),
+    ('\u{610}', '\u{61A}', Extend),
+    ('\u{61C}', '\u{61C}', Control),
+    ('\u{64B}', '\u{65F}', Extend),
+    ('\u{670}', '\u{670}', Extend),
+    ('\u{6D6}', '\u{6DC}', Extend),
+    ('\u{6DD}', '\u{6DD}', Prepend),
+    ('\u{6DF}', '\u{6E4}', Extend),
+    ('\u{6E7}', '\u{6E8}', Extend),
+    ('\u{6EA}', '\u{6ED}', Extend),
+    ('\u{70F}', '\u{70F}', Prepend),
+    ('\u{711}', '\u{711}', Extend),
+    ('\u{730}', '\u{74A}', Extend),
+    ('\u{7A6}', '\u{7B0}', Extend),
+    ('\u{7EB}', '\u{7F3}', Extend),
+    ('\u{7FD}', '\u{7FD}', Extend),
+    ('\u{816}', '\u{819}', Extend),
+    ('\u{81B}', '\u{823}', Extend),
+    ('\u{825}', '\u{827}', Extend),
+    ('\u{829}', '\u{82D}', Extend),
+    ('\u{859}', '\u{85B}', Extend),
+    ('\u{890}', '\u{891}', Prepend),
+    ('\u{897}', '\u{89F}', Extend),
+    ('\u{8CA}', '\u{8E1}', Extend),
+    ('\u{8E2}', '\u{8E2}', Prepend),
+    ('\u{8E3}', '\u{902}', Extend),
+    ('\u{903}', '\u{903}', SpacingMark),
+    ('\u{915}', '\u{939}', Consonant),
+    ('\u{93A}', '\u{93A}', Extend),
+    ('\u{93B}', '\u{93B}', SpacingMark),
+    ('\u{93C}', '\u{93C}', Extend),
+    ('\u{93E}', '\u{940}', SpacingMark),
+    ('\u{941}', '\u{948}', Extend),
+    ('\u{949}', '\u{94C}', SpacingMark),
+    ('\u{94D}', '\u{94D}', Extend),
+    ('\u{94E}', '\u{94F}', SpacingMark),
+    ('\u{951}', '\u{957}', Extend),
+    ('\u{958}', '\u{95F}', Consonant),
+    ('\u{962}', '\u{963}', Extend),
+    ('\u{978}', '\u{97F}', Consonant),
+    ('\u{981}', '\u{981}', Extend),
+    ('\u{982}', '\u{983}', SpacingMark),
+    ('\u{995}', '\u{9A8}', Consonant),
+    ('\u{9AA}', '\u{9B0}', Consonant),
+    ('\u{9B2}', '\u{9B2}', Consonant),
+    ('\u{9B6}', '\u{9B9}', Consonant),
+    ('\u{9BC}', '\u{9BC}', Extend),
+    ('\u{9BE}', '\u{9BE}', Extend),
+    ('\u{9BF}', '\u{9C0}', SpacingMark),
+    ('\u{9C1}', '\u{9C4}', Extend),
+    ('\u{9C7}', '\u{9C8}', SpacingMark),
+    ('\u{9CB}', '\u{9CC}', SpacingMark),
+    ('\u{9CD}', '\u{9CD}', Extend),
+    ('\u{9D7}', '\u{9D7}', Extend),
+    ('\u{9DC}', '\u{9DD}', Consonant),
+    ('\u{9DF}', '\u{9DF}', Consonant),
+    ('\u{9E2}', '\u{9E3}', Extend),
+    ('\u{9F0}', '\u{9F1}', Consonant),
+    ('\u{9FE}', '\u{9FE}', Extend),
+    ('\u{A01}', '\u{A02}', Extend),
+    ('\u{A03}', '\u{A03}', SpacingMark),
+    ('\u{A3C}', '\u{A3C}', Extend),
+    ('\u{A3E}', '\u{A40}', SpacingMark),
+    ('\u{A41}', '\u{A42}', Extend),
+    ('\u{A47}', '\u{A48}', Extend),
+    ('\u{A4B}', '\u{A4D}', Extend),
+    ('\u{A51}', '\u{A51}', Extend),
+    ('\u{A70}', '\u{A71}', Extend),
+    ('\u{A75}', '\u{A75}', Extend),
+    ('\u{A81}', '\u{A82}', Extend),
+    ('\u{A83}', '\u{A83}', SpacingMark),
+    ('\u{A95}', '\u{AA8}', Consonant),
+    ('\u{AAA}', '\u{AB0}', Consonant),
+    ('\u{AB2}', '\u{AB3}', Consonant),
+    ('\u{AB5}', '\u{AB9}', Consonant),
+    ('\u{ABC}', '\u{ABC}', Extend),
+    ('\u{ABE}', '\u{AC0}', SpacingMark),
+    ('\u{AC1}', '\u{AC5}', Extend),
+    ('\u{AC7}', '\u{AC8}', Extend),
+    ('\u{AC9}', '\u{AC9}', SpacingMark),
+    ('\u{ACB}', '\u{ACC}', SpacingMark),
+    ('\u{ACD}', '\u{ACD}', Extend),
+    ('\u{AE2}', '\u{AE3}', Extend),
+    ('\u{AF9}', '\u{AF9}', Consonant),
+    ('\u{AFA}', '\u{AFF}', Extend),
+    ('\u{B01}', '\u{B01}', Extend),
+    ('\u{B02}', '\u{B03}', SpacingMark),
+    ('\u{B15}', '\u{B28}', Consonant),
+    ('\u{B2A}', '\u{B30}', Consonant),
+    ('\u{B32}', '\u{B33}', Consonant),
+    ('\u{B35}', '\u{B39}', Consonant),
+    ('\u{B3C}', '\u{B3C}', Extend),
+    ('\u{B3E}', '\u{B3F}', Extend),
+    ('\u{B40}', '\u{B40}', SpacingMark),
+    ('\u{B41}', '\u{B44}', Extend),
+    ('\u{B47}', '\u{B48}', SpacingMark),
+    ('\u{B4B}', '\u{B4C}', SpacingMark),
+    ('\u{B4D}', '\u{B4D}', Extend),
+    ('\u{B55}', '\u{B57}', Extend),
+    ('\u{B5C}', '\u{B5D}', Consonant),
+    ('\u{B5F}', '\u{B5F}', Consonant),
+    ('\u{B62}', '\u{B63}', Extend),
+    ('\u{B71}', '\u{B71}', Consonant),
+    ('\u{B82}', '\u{B82}', Extend),
+    ('\u{BBE}', '\u{BBE}', Extend),
+    ('\u{BBF}', '\u{BBF}', SpacingMark),
+    ('\u{BC0}', '\u{BC0}', Extend),
+    ('\u{BC1}', '\u{BC2}', SpacingMark),
+    ('\u{BC6}', '\u{BC8}', SpacingMark),
+    ('\u{BCA}', '\u{BCC}', SpacingMark),
+    ('\u{BCD}', '\u{BCD}', Extend),
+    ('\u{BD7}', '\u{BD7}', Extend),
+    ('\u{C00}', '\u{C00}', Extend),
+    ('\u{C01}', '\u{C03}', SpacingMark),
+    ('\u{C04}', '\u{C04}', Extend),
+    ('\u{C15}', '\u{C28}', Consonant),
+    ('\u{C2A}', '\u{C39}', Consonant),
+    ('\u{C3C}', '\u{C3C}', Extend),
+    ('\u{C3E}', '\u{C40}', Extend),
+    ('\u{C41}', '\u{C44}', SpacingMark),
+    ('\u{C46}', '\u{C48}', Extend),
+    ('\u{C4A}', '\u{C4D}', Extend),
+    ('\u{C55}', '\u{C56}', Extend),
+    ('\u{C58}', '\u{C5A}', Consonant),
+    ('\u{C62}', '\u{C63}', Extend),
+    ('\u{C81}', '\u{C81}', Extend),
+    ('\u{C82}', '\u{C83}', SpacingMark),
+    ('\u{CBC}', '\u{CBC}', Extend),
+    ('\u{CBE}', '\u{CBE}', SpacingMark),
+    ('\u{CBF}', '\u{CC0}', Extend),
+    ('\u{CC1}', '\u{CC1}', SpacingMark),
+    ('\u{CC2}', '\u{CC2}', Extend),
+    ('\u{CC3}', '\u{CC4}', SpacingMark),
+    ('\u{CC6}', '\u{CC8}', Extend),
+    ('\u{CCA}', '\u{CCD}', Extend),
+    ('\u{CD5}', '\u{CD6}', Extend),
+    ('\u{CE2}', '\u{CE3}', Extend),
+    ('\u{CF3}', '\u{CF3}', SpacingMark),
+    ('\u{D00}', '\u{D01}', Extend),
+    ('\u{D02}', '\u{D03}', SpacingMark),
+    ('\u{D15}', '\u{D3A}', Consonant),
+    ('\u{D3B}', '\u{D3C}', Extend),
+    ('\u{D3E}', '\u{D3E}', Extend),
+    ('\u{D3F}', '\u{D40}', SpacingMark),
+    ('\u{D41}', '\u{D44}', Extend),
+    ('\u{D46}', '\u{D48}', SpacingMark),
+    ('\u{D4A}', '\u{D4C}', SpacingMark),
+    ('\u{D4D}', '\u{D4D}', Extend),
+    ('\u{D4E}', '\u{D4E}', Prepend),
+    ('\u{D57}', '\u{D57}', Extend),
+    ('\u{D62}', '\u{D63}', Extend),
+    ('\u{D81}', '\u{D81}', Extend),
+    ('\u{D82}', '\u{D83}', SpacingMark),
+    ('\u{DCA}', '\u{DCA}', Extend),
+    ('\u{DCF}', '\u{DCF}', Extend),
+    ('\u{DD0}', '\u{DD1}', SpacingMark),
+    ('\u{DD2}', '\u{DD4}', Extend),
+    ('\u{DD6}', '\u{DD6}', Extend),
+    ('\u{DD8}', '\u{DDE}', SpacingMark),
+    ('\u{DDF}', '\u{DDF}', Extend),
+    ('\u{DF2}', '\u{DF3}', SpacingMark),
+    ('\u{E31}', '\u{E31}', Extend),
+    ('\u{E33}', '\u{E33}', SpacingMark),
+    ('\u{E34}', '\u{E3A}', Extend),
+    ('\u{E47}', '\u{E4E}', Extend),
+    ('\u{EB1}', '\u{EB1}', Extend),
+    ('\u{EB3}', '\u{EB3}', SpacingMark),
+    ('\u{EB4}', '\u{EBC}', Extend),
+    ('\u{EC8}', '\u{ECE}', Extend),
+    ('\u{F18}', '\u{F19}', Extend),
+    ('\u{F35}', '\u{F35}', Extend),
+    ('\u{F37}', '\u{F37}', Extend),
+    ('\u{F39}', '\u{F39}', Extend),
+    ('\u{F3E}', '\u{F3F}', SpacingMark),
+    ('\u{F71}', '\u{F7E}', Extend),
+    ('\u{F7F}', '\u{F7F}', SpacingMark),
+    ('\u{F80}', '\u{F84}', Extend),
+    ('\u{F86}', '\u{F87}', Extend),
+    ('\u{F8D}', '\u{F97}', Extend),
+    ('\u{F99}', '\u{FBC}', Extend),
+    ('\u{FC6}', '\u{FC6}', Extend),
+    ('\u{1000}', '\u{102A}', Consonant),
+    ('\u{102D}', '\u{1030}', Extend),
+    ('\u{1031}', '\u{1031}', SpacingMark),
+    ('\u{1032}', '\u{1037}', Extend),
+    ('\u{1039}', '\u{103A}', Extend),
+    ('\u{103B}', '\u{103C}', SpacingMark),
+    ('\u{103D}', '\u{103E}', Extend),
+    ('\u{103F}', '\u{103F}', Consonant),
+    ('\u{1050}', '\u{1055}', Consonant),
+    ('\u{1056}', '\u{1057}', SpacingMark),
+    ('\u{1058}', '\u{1059}', Extend),
+    ('\u{105A}', '\u{105D}', Consonant),
+    ('\u{105E}', '\u{1060}', Extend),
+    ('\u{1061}', '\u{1061}', Consonant),
+    ('\u{1065}', '\u{1066}', Consonant),
+    ('\u{106E}', '\u{1070}', Consonant),
+    ('\u{1071}', '\u{1074}', Extend),
+    ('\u{1075}', '\u{1081}', Consonant),
+    ('\u{1082}', '\u{1082}', Extend),
+    ('\u{1084}', '\u{1084}', SpacingMark),
+    ('\u{1085}', '\u{1086}', Extend),
+    ('\u{108D}', '\u{108D}', Extend),
+    ('\u{108E}', '\u{108E}', Consonant),
+    ('\u{109D}', '\u{109D}', Extend),
+    ('\u{1100}', '\u{115F}', L),
+    ('\u{1160}', '\u{11A7}', V),
+    ('\u{11A8}', '\u{11FF}', T),
+    ('\u{135D}', '\u{135F}', Extend),
+    ('\u{1712}', '\u{1715}', Extend),
+    ('\u{1732}', '\u{1734}', Extend),
+    ('\u{1752}', '\u{1753}', Extend),
+    ('\u{1772}', '\u{1773}', Extend),
+    ('\u{1780}', '\u{17B3}', Consonant),
+    ('\u{17B4}', '\u{17B5}', Extend),
+    ('\u{17B6}', '\u{17B6}', SpacingMark),
+    ('\u{17B7}', '\u{17BD}', Extend),
+    ('\u{17BE}', '\u{17C5}', SpacingMark),
+    ('\u{17C6}', '\u{17C6}', Extend),
+    ('\u{17C7}', '\u{17C8}', SpacingMark),
+    ('\u{17C9}', '\u{17D3}', Extend),
+    ('\u{17DD}', '\u{17DD}', Extend),
+    ('\u{180B}', '\u{180D}', Extend),
+    ('\u{180E}', '\u{180E}', Control),
+    ('\u{180F}', '\u{180F}', Extend),
+    ('\u{1885}', '\u{1886}', Extend),
+    ('\u{18A9}', '\u{18A9}', Extend),
+    ('\u{1920}', '\u{1922}', Extend),
+    ('\u{1923}', '\u{1926}', SpacingMark),
+    ('\u{1927}', '\u{1928}', Extend),
+    ('\u{1929}', '\u{192B}', SpacingMark),
+    ('\u{1930}', '\u{1931}', SpacingMark),
+    ('\u{1932}', '\u{1932}', Extend),
+    ('\u{1933}', '\u{1938}', SpacingMark),
+    ('\u{1939}', '\u{193B}', Extend),
+    ('\u{1A17}', '\u{1A18}', Extend),
+    ('\u{1A19}', '\u{1A1A}', SpacingMark),
+    ('\u{1A1B}', '\u{1A1B}', Extend),
+    ('\u{1A20}', '\u{1A54}', Consonant),
+    ('\u{1A55}', '\u{1A55}', SpacingMark),
+    ('\u{1A56}', '\u{1A56}', Extend),
+    ('\u{1A57}', '\u{1A57}', SpacingMark),
+    ('\u{1A58}', '\u{1A5E}', Extend),
+    ('\u{1A60}', '\u{1A60}', Extend),
+    ('\u{1A62}', '\u{1A62}', Extend),
+    ('\u{1A65}', '\u{1A6C}', Extend),
+    ('\u{1A6D}', '\u{1A72}', SpacingMark),
+    ('\u{1A73}', '\u{1A7C}', Extend),
+    ('\u{1A7F}', '\u{1A7F}', Extend),
+    ('\u{1AB0}', '\u{1ADD}', Extend),
+    ('\u{1AE0}', '\u{1AEB}', Extend),
+    ('\u{1B00}', '\u{1B03}', Extend),
+    ('\u{1B04}', '\u{1B04}', SpacingMark),
+    ('\u{1B0B}', '\u{1B0C}', Consonant),
+    ('\u{1B13}', '\u{1B33}', Consonant),
+    ('\u{1B34}', '\u{1B3D}', Extend),
+    ('\u{1B3E}', '\u{1B41}', SpacingMark),
+    ('\u{1B42}', '\u{1B44}', Extend),
+    ('\u{1B45}', '\u{1B4C}', Consonant),
+    ('\u{1B6B}', '\u{1B73}', Extend),
+    ('\u{1B80}', '\u{1B81}', Extend),
+    ('\u{1B82}', '\u{1B82}', SpacingMark),
+    ('\u{1B83}', '\u{1BA0}', Consonant),
+    ('\u{1BA1}', '\u{1BA1}', SpacingMark),
+    ('\u{1BA2}', '\u{1BA5}', Extend),
+    ('\u{1BA6}', '\u{1BA7}', SpacingMark),
+    ('\u{1BA8}', '\u{1BAD}', Extend),
+    ('\u{1BAE}', '\u{1BAF}', Consonant),
+    ('\u{1BBB}', '\u{1BBD}', Consonant),
+    ('\u{1BE6}', '\u{1BE6}', Extend),
+    ('\u{1BE7}', '\u{1BE7}', SpacingMark),
+    ('\u{1BE8}', '\u{1BE9}', Extend),
+    ('\u{1BEA}', '\u{1BEC}', SpacingMark),
+    ('\u{1BED}', '\u{1BED}', Extend),
+    ('\u{1BEE}', '\u{1BEE}', SpacingMark),
+    ('\u{1BEF}', '\u{1BF3}', Extend),
+    ('\u{1C24}', '\u{1C2B}', SpacingMark),
+    ('\u{1C2C}', '\u{1C33}', Extend),
+    ('\u{1C34}', '\u{1C35}', SpacingMark),
+    ('\u{1C36}', '\u{1C37}', Extend),
+    ('\u{1CD0}', '\u{1CD2}', Extend),
+    ('\u{1CD4}', '\u{1CE0}', Extend),
+    ('\u{1CE1}', '\u{1CE1}', SpacingMark),
+    ('\u{1CE2}', '\u{1CE8}', Extend),
+    ('\u{1CED}', '\u{1CED}', Extend),
+    ('\u{1CF4}', '\u{1CF4}', Extend),
+    ('\u{1CF7}', '\u{1CF7}', SpacingMark),
+    ('\u{1CF8}', '\u{1CF9}', Extend),
+    ('\u{1DC0}', '\u{1DFF}', Extend),
+    ('\u{200B}', '\u{200B}', Control),
+    ('\u{200C}', '\u{200C}', Extend),
+    ('\u{200D}', '\u{200D}', Zwj),
+    ('\u{200E}', '\u{200F}', Control),
+    ('\u{2028}', '\u{202E}', Control),
+    ('\u{203C}', '\u{203C}', Pictographic),
+    ('\u{2049}', '\u{2049}', Pictographic),
+    ('\u{2060}', '\u{206F}', Control),
+    ('\u{20D0}', '\u{20F0}', Extend),
+    ('\u{2122}', '\u{2122}', Pictographic),
+    ('\u{2139}', '\u{2139}', Pictographic),
+    ('\u{2194}', '\u{2199}', Pictographic),
+    ('\u{21A9}', '\u{21AA}', Pictographic),
+    ('\u{231A}', '\u{231B}', Pictographic),
+    ('\u{2328}', '\u{2328}', Pictographic),
+    ('\u{23CF}', '\u{23CF}', Pictographic),
+    ('\u{23E9}', '\u{23F3}', Pictographic),
+    ('\u{23F8}', '\u{23FA}', Pictographic),
+    ('\u{24C2}', '\u{24C2}', Pictographic),
+    ('\u{25AA}', '\u{25AB}', Pictographic),
+    ('\u{25B6}', '\u{25B6}', Pictographic),
+    ('\u{25C0}', '\u{25C0}', Pictographic),
+    ('\u{25FB}', '\u{25FE}', Pictographic),
+    ('\u{2600}', '\u{2604}', Pictographic),
+    ('\u{260E}', '\u{260E}', Pictographic),
+    ('\u{2611}', '\u{2611}', Pictographic),
+    ('\u{2614}', '\u{2615}', Pictographic),
+    ('\u{2618}', '\u{2618}', Pictographic),
+    ('\u{261D}', '\u{261D}', Pictographic),
+    ('\u{2620}', '\u{2620}', Pictographic),
+    ('\u{2622}', '\u{2623}', Pictographic),
+    ('\u{2626}', '\u{2626}', Pictographic),
+    ('\u{262A}', '\u{262A}', Pictographic),
+    ('\u{262E}', '\u{262F}', Pictographic),
+    ('\u{2638}', '\u{263A}', Pictographic),
+    ('\u{2640}', '\u{2640}', Pictographic),
+    ('\u{2642}', '\u{2642}', Pictographic),
+    ('\u{2648}', '\u{2653}', Pictographic),
+    ('\u{265F}', '\u{2660}', Pictographic),
+    ('\u{2663}', '\u{2663}', Pictographic),
+    ('\u{2665}', '\u{2666}', Pictographic),
+    ('\u{2668}', '\u{2668}', Pictographic),
+    ('\u{267B}', '\u{267B}', Pictographic),
+    ('\u{267E}', '\u{267F}', Pictographic),
+    ('\u{2692}', '\u{2697}', Pictographic),
+    ('\u{2699}', '\u{2699}', Pictographic),
+    ('\u{269B}', '\u{269C}', Pictographic),
+    ('\u{26A0}', '\u{26A1}', Pictographic),
+    ('\u{26A7}', '\u{26A7}', Pictographic),
+    ('\u{26AA}', '\u{26AB}', Pictographic),
+    ('\u{26B0}', '\u{26B1}', Pictographic),
+    ('\u{26BD}', '\u{26BE}', Pictographic),
+    ('\u{26C4}', '\u{26C5}', Pictographic),
+    ('\u{26C8}', '\u{26C8}', Pictographic),
+    ('\u{26CE}', '\u{26CF}', Pictographic),
+    ('\u{26D1}', '\u{26D1}', Pictographic),
+    ('\u{26D3}', '\u{26D4}', Pictographic),
+    ('\u{26E9}', '\u{26EA}', Pictographic),
+    ('\u{26F0}', '\u{26F5}', Pictographic),
+    ('\u{26F7}', '\u{26FA}', Pictographic),
+    ('\u{26FD}', '\u{26FD}', Pictographic),
+    ('\u{2702}', '\u{2702}', Pictographic),
+    ('\u{2705}', '\u{2705}', Pictographic),
+    ('\u{2708}', '\u{270D}', Pictographic),
+    ('\u{270F}', '\u{270F}', Pictographic),
+    ('\u{2712}', '\u{2712}', Pictographic),
+    ('\u{2714}', '\u{2714}', Pictographic),
+    ('\u{2716}', '\u{2716}', Pictographic),
+    ('\u{271D}', '\u{271D}', Pictographic),
+    ('\u{2721}', '\u{2721}', Pictographic),
+    ('\u{2728}', '\u{2728}', Pictographic),
+    ('\u{2733}', '\u{2734}', Pictographic),
+    ('\u{2744}', '\u{2744}', Pictographic),
+    ('\u{2747}', '\u{2747}', Pictographic),
+    ('\u{274C}', '\u{274C}', Pictographic),
+    ('\u{274E}', '\u{274E}', Pictographic),
+    ('\u{2753}', '\u{2755}', Pictographic),
+    ('\u{2757}', '\u{2757}', Pictographic),
+    ('\u{2763}', '\u{2764}', Pictographic),
+    ('\u{2795}', '\u{2797}', Pictographic),
+    ('\u{27A1}', '\u{27A1}', Pictographic),
+    ('\u{27B0}', '\u{27B0}', Pictographic),
+    ('\u{27BF}', '\u{27BF}', Pictographic),
+    ('\u{2934}', '\u{2935}', Pictographic),
+    ('\u{2B05}', '\u{2B07}', Pictographic),
+    ('\u{2B1B}', '\u{2B1C}', Pictographic),
+    ('\u{2B50}', '\u{2B50}', Pictographic),
+    ('\u{2B55}', '\u{2B55}', Pictographic),
+    ('\u{2CEF}', '\u{2CF1}', Extend),
+    ('\u{2D7F}', '\u{2D7F}', Extend),
+    ('\u{2DE0}', '\u{2DFF}', Extend),
+    ('\u{302A}', '\u{302F}', Extend),
+    ('\u{3030}', '\u{3030}', Pictographic),
+    ('\u{303D}', '\u{303D}', Pictographic),
+    ('\u{3099}', '\u{309A}', Extend),
+    ('\u{3297}', '\u{3297}', Pictographic),
+    ('\u{3299}', '\u{3299}', Pictographic),
+    ('\u{A66F}', '\u{A672}', Extend),
+    ('\u{A674}', '\u{A67D}', Extend),
+    ('\u{A69E}', '\u{A69F}', Extend),
+    ('\u{A6F0}', '\u{A6F1}', Extend),
+    ('\u{A802}', '\u{A802}', Extend),
+    ('\u{A806}', '\u{A806}', Extend),
+    ('\u{A80B}', '\u{A80B}', Extend),
+    ('\u{A823}', '\u{A824}', SpacingMark),
+    ('\u{A825}', '\u{A826}', Extend),
+    ('\u{A827}', '\u{A827}', SpacingMark),
+    ('\u{A82C}', '\u{A82C}', Extend),
+    ('\u{A880}', '\u{A881}', SpacingMark),
+    ('\u{A8B4}', '\u{A8C3}', SpacingMark),
+    ('\u{A8C4}', '\u{A8C5}', Extend),
+    ('\u{A8E0}', '\u{A8F1}', Extend),
+    ('\u{A8FF}', '\u{A8FF}', Extend),
+    ('\u{A926}', '\u{A92D}', Extend),
+    ('\u{A947}', '\u{A951}', Extend),
+    ('\u{A952}', '\u{A952}', SpacingMark),
+    ('\u{A953}', '\u{A953}', Extend),
+    ('\u{A960}', '\u{A97C}', L),
+    ('\u{A980}', '\u{A982}', Extend),
+    ('\u{A983}', '\u{A983}', SpacingMark),
+    ('\u{A989}', '\u{A98B}', Consonant),
+    ('\u{A98F}', '\u{A9B2}', Consonant),
+    ('\u{A9B3}', '\u{A9B3}', Extend),
+    ('\u{A9B4}', '\u{A9B5}', SpacingMark),
+    ('\u{A9B6}', '\u{A9B9}', Extend),
+    ('\u{A9BA}', '\u{A9BB}', SpacingMark),
+    ('\u{A9BC}', '\u{A9BD}', Extend),
+    ('\u{A9BE}', '\u{A9BF}', SpacingMark),
+    ('\u{A9C0}', '\u{A9C0}', Extend),
+    ('\u{A9E0}', '\u{A9E4}', Consonant),
+    ('\u{A9E5}', '\u{A9E5}', Extend),
+    ('\u{A9E7}', '\u{A9EF}', Consonant),
+    ('\u{A9FA}', '\u{A9FE}', Consonant),
+    ('\u{AA29}', '\u{AA2E}', Extend),
+    ('\u{AA2F}', '\u{AA30}', SpacingMark),
+    ('\u{AA31}', '\u{AA32}', Extend),
+    ('\u{AA33}', '\u{AA34}', SpacingMark),
+    ('\u{AA35}', '\u{AA36}', Extend),
+    ('\u{AA43}', '\u{AA43}', Extend),
+    ('\u{AA4C}', '\u{AA4C}', Extend),
+    ('\u{AA4D}', '\u{AA4D}', SpacingMark),
+    ('\u{AA60}', '\u{AA6F}', Consonant),
+    ('\u{AA71}', '\u{AA73}', Consonant),
+    ('\u{AA7A}', '\u{AA7A}', Consonant),
+    ('\u{AA7C}', '\u{AA7C}', Extend),
+    ('\u{AA7E}', '\u{AA7F}', Consonant),
+    ('\u{AAB0}', '\u{AAB0}', Extend),
+    ('\u{AAB2}', '\u{AAB4}', Extend),
+    ('\u{AAB7}', '\u{AAB8}', Extend),
+    ('\u{AABE}', '\u{AABF}', Extend),
+    ('\u{AAC1}', '\u{AAC1}', Extend),
+    ('\u{AAE0}', '\u{AAEA}', Consonant),
+    ('\u{AAEB}', '\u{AAEB}', SpacingMark),
+    ('\u{AAEC}', '\u{AAED}', Extend),
+    ('\u{AAEE}', '\u{AAEF}', SpacingMark),
+    ('\u{AAF5}', '\u{AAF5}', SpacingMark),
+    ('\u{AAF6}', '\u{AAF6}', Extend),
+    ('\u{ABC0}', '\u{ABDA}', Consonant),
+    ('\u{ABE3}', '\u{ABE4}', SpacingMark),
+    ('\u{ABE5}', '\u{ABE5}', Extend),
+    ('\u{ABE6}', '\u{ABE7}', SpacingMark),
+    ('\u{ABE8}', '\u{ABE8}', Extend),
+    ('\u{ABE9}', '\u{ABEA}', SpacingMark),
+    ('\u{ABEC}', '\u{ABEC}', SpacingMark),
+    ('\u{ABED}', '\u{ABED}', Extend),
+    ('\u{D7B0}', '\u{D7C6}', V),
+    ('\u{D7CB}', '\u{D7FB}', T),
+    ('\u{FB1E}', '\u{FB1E}', Extend),
+    ('\u{FE00}', '\u{FE0F}', Extend),
+    ('\u{FE20}', '\u{FE2F}', Extend),
+    ('\u{FEFF}', '\u{FEFF}', Control),
+    ('\u{FF9E}', '\u{FF9F}', Extend),
+    ('\u{FFF0}', '\u{FFFB}', Control),
+    ('\u{101FD}', '\u{101FD}', Extend),
+    ('\u{102E0}', '\u{102E0}', Extend),
+    ('\u{10376}', '\u{1037A}', Extend),
+    ('\u{10A00}', '\u{10A00}', Consonant),
+    ('\u{10A01}', '\u{10A03}', Extend),
+    ('\u{10A05}', '\u{10A06}', Extend),
+    ('\u{10A0C}', '\u{10A0F}', Extend),
+    ('\u{10A10}', '\u{10A13}', Consonant),
+    ('\u{10A15}', '\u{10A17}', Consonant),
+    ('\u{10A19}', '\u{10A35}', Consonant),
+    ('\u{10A38}', '\u{10A3A}', Extend),
+    ('\u{10A3F}', '\u{10A3F}', Extend),
+    ('\u{10AE5}', '\u{10AE6}', Extend),
+    ('\u{10D24}', '\u{10D27}', Extend),
+    ('\u{10D69}', '\u{10D6D}', Extend),
+    ('\u{10EAB}', '\u{10EAC}', Extend),
+    ('\u{10EFA}', '\u{10EFF}', Extend),
+    ('\u{10F46}', '\u{10F50}', Extend),
+    ('\u{10F82}', '\u{10F85}', Extend),
+    ('\u{11000}', '\u{11000}', SpacingMark),
+    ('\u{11001}', '\u{11001}', Extend),
+    ('\u{11002}', '\u{11002}', SpacingMark),
+    ('\u{11038}', '\u{11046}', Extend),
+    ('\u{11070}', '\u{11070}', Extend),
+    ('\u{11073}', '\u{11074}', Extend),
+    ('\u{1107F}', '\u{11081}', Extend),
+    ('\u{11082}', '\u{11082}', SpacingMark),
+    ('\u{110B0}', '\u{110B2}', SpacingMark),
+    ('\u{110B3}', '\u{110B6}', Extend),
+    ('\u{110B7}', '\u{110B8}', SpacingMark),
+    ('\u{110B9}', '\u{110BA}', Extend),
+    ('\u{110BD}', '\u{110BD}', Prepend),
+    ('\u{110C2}', '\u{110C2}', Extend),
+    ('\u{110CD}', '\u{110CD}', Prepend),
+    ('\u{11100}', '\u{11102}', Extend),
+    ('\u{11103}', '\u{11126}', Consonant),
+    ('\u{11127}', '\u{1112B}', Extend),
+    ('\u{1112C}', '\u{1112C}', SpacingMark),
+    ('\u{1112D}', '\u{11134}', Extend),
+    ('\u{11144}', '\u{11144}', Consonant),
+    ('\u{11145}', '\u{11146}', SpacingMark),
+    ('\u{11147}', '\u{11147}', Consonant),
+    ('\u{11173}', '\u{11173}', Extend),
+    ('\u{11180}', '\u{11181}', Extend),
+    ('\u{11182}', '\u{11182}', SpacingMark),
+    ('\u{111B3}', '\u{111B5}', SpacingMark),
+    ('\u{111B6}', '\u{111BE}', Extend),
+    ('\u{111BF}', '\u{111BF}', SpacingMark),
+    ('\u{111C0}', '\u{111C0}', Extend),
+    ('\u{111C2}', '\u{111C3}', Prepend),
+    ('\u{111C9}', '\u{111CC}', Extend),
+    ('\u{111CE}', '\u{111CE}', SpacingMark),
+    ('\u{111CF}', '\u{111CF}', Extend),
+    ('\u{1122C}', '\u{1122E}', SpacingMark),
+    ('\u{1122F}', '\u{11231}', Extend),
+    ('\u{11232}', '\u{11233}', SpacingMark),
+    ('\u{11234}', '\u{11237}', Extend),
+    ('\u{1123E}', '\u{1123E}', Extend),
+    ('\u{11241}', '\u{11241}', Extend),
+    ('\u{112DF}', '\u{112DF}', Extend),
+    ('\u{112E0}', '\u{112E2}', SpacingMark),
+    ('\u{112E3}', '\u{112EA}', Extend),
+    ('\u{11300}', '\u{11301}', Extend),
+    ('\u{11302}', '\u{11303}', SpacingMark),
+    ('\u{1133B}', '\u{1133C}', Extend),
+    ('\u{1133E}', '\u{1133E}', Extend),
+    ('\u{1133F}', '\u{1133F}', SpacingMark),
+    ('\u{11340}', '\u{11340}', Extend),
+    ('\u{11341}', '\u{11344}', SpacingMark),
+    ('\u{11347}', '\u{11348}', SpacingMark),
+    ('\u{1134B}', '\u{1134C}', SpacingMark),
+    ('\u{1134D}', '\u{1134D}', Extend),
+    ('\u{11357}', '\u{11357}', Extend),
+    ('\u{11362}', '\u{11363}', SpacingMark),
+    ('\u{11366}', '\u{1136C}', Extend),
+    ('\u{11370}', '\u{11374}', Extend),
+    ('\u{11380}', '\u{11389}', Consonant),
+    ('\u{1138B}', '\u{1138B}', Consonant),
+    ('\u{1138E}', '\u{1138E}', Consonant),
+    ('\u{11390}', '\u{113B5}', Consonant),
+    ('\u{113B8}', '\u{113B8}', Extend),
+    ('\u{113B9}', '\u{113BA}', SpacingMark),
+    ('\u{113BB}', '\u{113C0}', Extend),
+    ('\u{113C2}', '\u{113C2}', Extend),
+    ('\u{113C5}', '\u{113C5}', Extend),
+    ('\u{113C7}', '\u{113C9}', Extend),
+    ('\u{113CA}', '\u{113CA}', SpacingMark),
+    ('\u{113CC}', '\u{113CD}', SpacingMark),
+    ('\u{113CE}', '\u{113D0}', Extend),
+    ('\u{113D1}', '\u{113D1}', Prepend),
+    ('\u{113D2}', '\u{113D2}', Extend),
+    ('\u{113E1}', '\u{113E2}', Extend),
+    ('\u{11435}', '\u{11437}', SpacingMark),
+    ('\u{11438}', '\u{1143F}', Extend),
+    ('\u{11440}', '\u{11441}', SpacingMark),
+    ('\u{11442}', '\u{11444}', Extend),
+    ('\u{11445}', '\u{11445}', SpacingMark),
+    ('\u{11446}', '\u{11446}', Extend),
+    ('\u{1145E}', '\u{1145E}', Extend),
+    ('\u{114B0}', '\u{114B0}', Extend),
+    ('\u{114B1}', '\u{114B2}', SpacingMark),
+    ('\u{114B3}', '\u{114B8}', Extend),
+    ('\u{114B9}', '\u{114B9}', SpacingMark),
+    ('\u{114BA}', '\u{114BA}', Extend),
+    ('\u{114BB}', '\u{114BC}', SpacingMark),
+    ('\u{114BD}', '\u{114BD}', Extend),
+    ('\u{114BE}', '\u{114BE}', SpacingMark),
+    ('\u{114BF}', '\u{114C0}', Extend),
+    ('\u{114C1}', '\u{114C1}', SpacingMark),
+    ('\u{114C2}', '\u{114C3}', Extend),
+    ('\u{115AF}', '\u{115AF}', Extend),
+    ('\u{115B0}', '\u{115B1}', SpacingMark),
+    ('\u{115B2}', '\u{115B5}', Extend),
+    ('\u{115B8}', '\u{115BB}', SpacingMark),
+    ('\u{115BC}', '\u{115BD}', Extend),
+    ('\u{115BE}', '\u{115BE}', SpacingMark),
+    ('\u{115BF}', '\u{115C0}', Extend),
+    ('\u{115DC}', '\u{115DD}', Extend),
+    ('\u{11630}', '\u{11632}', SpacingMark),
+    ('\u{11633}', '\u{1163A}', Extend),
+    ('\u{1163B}', '\u{1163C}', SpacingMark),
+    ('\u{1163D}', '\u{1163D}', Extend),
+    ('\u{1163E}', '\u{1163E}', SpacingMark),
+    ('\u{1163F}', '\u{11640}', Extend),
+    ('\u{116AB}', '\u{116AB}', Extend),
+    ('\u{116AC}', '\u{116AC}', SpacingMark),
+    ('\u{116AD}', '\u{116AD}', Extend),
+    ('\u{116AE}', '\u{116AF}', SpacingMark),
+    ('\u{116B0}', '\u{116B7}', Extend),
+    ('\u{1171D}', '\u{1171D}', Extend),
+    ('\u{1171E}', '\u{1171E}', SpacingMark),
+    ('\u{1171F}', '\u{1171F}', Extend),
+    ('\u{11722}', '\u{11725}', Extend),
+    ('\u{11726}', '\u{11726}', SpacingMark),
+    ('\u{11727}', '\u{1172B}', Extend),
+    ('\u{1182C}', '\u{1182E}', SpacingMark),
+    ('\u{1182F}', '\u{11837}', Extend),
+    ('\u{11838}', '\u{11838}', SpacingMark),
+    ('\u{11839}', '\u{1183A}', Extend),
+    ('\u{11900}', '\u{11906}', Consonant),
+    ('\u{11909}', '\u{11909}', Consonant),
+    ('\u{1190C}', '\u{11913}', Consonant),
+    ('\u{11915}', '\u{11916}', Consonant),
+    ('\u{11918}', '\u{1192F}', Consonant),
+    ('\u{11930}', '\u{11930}', Extend),
+    ('\u{11931}', '\u{11935}', SpacingMark),
+    ('\u{11937}', '\u{11938}', SpacingMark),
+    ('\u{1193B}', '\u{1193E}', Extend),
+    ('\u{1193F}', '\u{1193F}', Prepend),
+    ('\u{11940}', '\u{11940}', SpacingMark),
+    ('\u{11941}', '\u{11941}', Prepend),
+    ('\u{11942}', '\u{11942}', SpacingMark),
+    ('\u{11943}', '\u{11943}', Extend),
+    ('\u{119D1}', '\u{119D3}', SpacingMark),
+    ('\u{119D4}', '\u{119D7}', Extend),
+    ('\u{119DA}', '\u{119DB}', Extend),
+    ('\u{119DC}', '\u{119DF}', SpacingMark),
+    ('\u{119E0}', '\u{119E0}', Extend),
+    ('\u{119E4}', '\u{119E4}', SpacingMark),
+    ('\u{11A00}', '\u{11A00}', Consonant),
+    ('\u{11A01}', '\u{11A0A}', Extend),
+    ('\u{11A0B}', '\u{11A32}', Consonant),
+    ('\u{11A33}', '\u{11A38}', Extend),
+    ('\u{11A39}', '\u{11A39}', SpacingMark),
+    ('\u{11A3B}', '\u{11A3E}', Extend),
+    ('\u{11A47}', '\u{11A47}', Extend),
+    ('\u{11A50}', '\u{11A50}', Consonant),
+    ('\u{11A51}', '\u{11A56}', Extend),
+    ('\u{11A57}', '\u{11A58}', SpacingMark),
+    ('\u{11A59}', '\u{11A5B}', Extend),
+    ('\u{11A5C}', '\u{11A83}', Consonant),
+    ('\u{11A84}', '\u{11A89}', Prepend),
+    ('\u{11A8A}', '\u{11A96}', Extend),
+    ('\u{11A97}', '\u{11A97}', SpacingMark),
+    ('\u{11A98}', '\u{11A99}', Extend),
+    ('\u{11B60}', '\u{11B60}', Extend),
+    ('\u{11B61}', '\u{11B61}', SpacingMark),
+    ('\u{11B62}', '\u{11B64}', Extend),
+    ('\u{11B65}', '\u{11B65}', SpacingMark),
+    ('\u{11B66}', '\u{11B66}', Extend),
+    ('\u{11B67}', '\u{11B67}', SpacingMark),
+    ('\u{11C2F}', '\u{11C2F}', SpacingMark),
+    ('\u{11C30}', '\u{11C36}', Extend),
+    ('\u{11C38}', '\u{11C3D}', Extend),
+    ('\u{11C3E}', '\u{11C3E}', SpacingMark),
+    ('\u{11C3F}', '\u{11C3F}', Extend),
+    ('\u{11C92}', '\u{11CA7}', Extend),
+    ('\u{11CA9}', '\u{11CA9}', SpacingMark),
+    ('\u{11CAA}', '\u{11CB0}', Extend),
+    ('\u{11CB1}', '\u{11CB1}', SpacingMark),
+    ('\u{11CB2}', '\u{11CB3}', Extend),
+    ('\u{11CB4}', '\u{11CB4}', SpacingMark),
+    ('\u{11CB5}', '\u{11CB6}', Extend),
+    ('\u{11D31}', '\u{11D36}', Extend),
+    ('\u{11D3A}', '\u{11D3A}', Extend),
+    ('\u{11D3C}', '\u{11D3D}', Extend),
+    ('\u{11D3F}', '\u{11D45}', Extend),
+    ('\u{11D46}', '\u{11D46}', Prepend),
+    ('\u{11D47}', '\u{11D47}', Extend),
+    ('\u{11D8A}', '\u{11D8E}', SpacingMark),
+    ('\u{11D90}', '\u{11D91}', Extend),
+    ('\u{11D93}', '\u{11D94}', SpacingMark),
+    ('\u{11D95}', '\u{11D95}', Extend),
+    ('\u{11D96}', '\u{11D96}', SpacingMark),
+    ('\u{11D97}', '\u{11D97}', Extend),
+    ('\u{11EF3}', '\u{11EF4}', Extend),
+    ('\u{11EF5}', '\u{11EF6}', SpacingMark),
+    ('\u{11F00}', '\u{11F01}', Extend),
+    ('\u{11F02}', '\u{11F02}', Prepend),
+    ('\u{11F03}', '\u{11F03}', SpacingMark),
+    ('\u{11F04}', '\u{11F10}', Consonant),
+    ('\u{11F12}', '\u{11F33}', Consonant),
+    ('\u{11F34}', '\u{11F35}', SpacingMark),
+    ('\u{11F36}', '\u{11F3A}', Extend),
+    ('\u{11F3E}', '\u{11F3F}', SpacingMark),
+    ('\u{11F40}', '\u{11F42}', Extend),
+    ('\u{11F5A}', '\u{11F5A}', Extend),
+    ('\u{13430}', '\u{1343F}', Control),
+    ('\u{13440}', '\u{13440}', Extend),
+    ('\u{13447}', '\u{13455}', Extend),
+    ('\u{1611E}', '\u{16129}', Extend),
+    ('\u{1612A}', '\u{1612C}', SpacingMark),
+    ('\u{1612D}', '\u{1612F}', Extend),
+    ('\u{16AF0}', '\u{16AF4}', Extend),
+    ('\u{16B30}', '\u{16B36}', Extend),
+    ('\u{16D63}', '\u{16D63}', V),
+    ('\u{16D67}', '\u{16D6A}', V),
+    ('\u{16F4F}', '\u{16F4F}', Extend),
+    ('\u{16F51}', '\u{16F87}', SpacingMark),
+    ('\u{16F8F}', '\u{16F92}', Extend),
+    ('\u{16FE4}', '\u{16FE4}', Extend),
+    ('\u{16FF0}', '\u{16FF1}', Extend),
+    ('\u{1BC9D}', '\u{1BC9E}', Extend),
+    ('\u{1BCA0}', '\u{1BCA3}', Control),
+    ('\u{1CF00}', '\u{1CF2D}', Extend),
+    ('\u{1CF30}', '\u{1CF46}', Extend),
+    ('\u{1D165}', '\u{1D169}', Extend),
+    ('\u{1D16D}', '\u{1D172}', Extend),
+    ('\u{1D173}', '\u{1D17A}', Control),
+    ('\u{1D17B}', '\u{1D182}', Extend),
+    ('\u{1D185}', '\u{1D18B}', Extend),
+    ('\u{1D1AA}', '\u{1D1AD}', Extend),
+    ('\u{1D242}', '\u{1D244}', Extend),
+    ('\u{1DA00}', '\u{1DA36}', Extend),
+    ('\u{1DA3B}', '\u{1DA6C}', Extend),
+    ('\u{1DA75}', '\u{1DA75}', Extend),
+    ('\u{1DA84}', '\u{1DA84}', Extend),
+    ('\u{1DA9B}', '\u{1DA9F}', Extend),
+    ('\u{1DAA1}', '\u{1DAAF}', Extend),
+    ('\u{1E000}', '\u{1E006}', Extend),
+    ('\u{1E008}', '\u{1E018}', Extend),
+    ('\u{1E01B}', '\u{1E021}', Extend),
+    ('\u{1E023}', '\u{1E024}', Extend),
+    ('\u{1E026}', '\u{1E02A}', Extend),
+    ('\u{1E08F}', '\u{1E08F}', Extend),
+    ('\u{1E130}', '\u{1E136}', Extend),
+    ('\u{1E2AE}', '\u{1E2AE}', Extend),
+    ('\u{1E2EC}', '\u{1E2EF}', Extend),
+    ('\u{1E4EC}', '\u{1E4EF}', Extend),
+    ('\u{1E5EE}', '\u{1E5EF}', Extend),
+    ('\u{1E6E3}', '\u{1E6E3}', Extend),
+    ('\u{1E6E6}', '\u{1E6E6}', Extend),
+    ('\u{1E6EE}', '\u{1E6EF}', Extend),
+    ('\u{1E6F5}', '\u{1E6F5}', Extend),
+    ('\u{1E8D0}', '\u{1E8D6}', Extend),
+    ('\u{1E944}', '\u{1E94A}', Extend),
+    ('\u{1F004}', '\u{1F004}', Pictographic),
+    ('\u{1F02C}', '\u{1F02F}', Pictographic),
+    ('\u{1F094}', '\u{1F09F}', Pictographic),
+    ('\u{1F0AF}', '\u{1F0B0}', Pictographic),
+    ('\u{1F0C0}', '\u{1F0C0}', Pictographic),
+    ('\u{1F0CF}', '\u{1F0D0}', Pictographic),
+    ('\u{1F0F6}', '\u{1F0FF}', Pictographic),
+    ('\u{1F170}', '\u{1F171}', Pictographic),
+    ('\u{1F17E}', '\u{1F17F}', Pictographic),
+    ('\u{1F18E}', '\u{1F18E}', Pictographic),
+    ('\u{1F191}', '\u{1F19A}', Pictographic),
+    ('\u{1F1AE}', '\u{1F1E5}', Pictographic),
+    ('\u{1F1E6}', '\u{1F1FF}', RegionalIndicator),
+    ('\u{1F201}', '\u{1F20F}', Pictographic),
+    ('\u{1F21A}', '\u{1F21A}', Pictographic),
+    ('\u{1F22F}', '\u{1F22F}', Pictographic),
+    ('\u{1F232}', '\u{1F23A}', Pictographic),
+    ('\u{1F23C}', '\u{1F23F}', Pictographic),
+    ('\u{1F249}', '\u{1F25F}', Pictographic),
+    ('\u{1F266}', '\u{1F321}', Pictographic),
+    ('\u{1F324}', '\u{1F393}', Pictographic),
+    ('\u{1F396}', '\u{1F397}', Pictographic),
+    ('\u{1F399}', '\u{1F39B}', Pictographic),
+    ('\u{1F39E}', '\u{1F3F0}', Pictographic),
+    ('\u{1F3F3}', '\u{1F3F5}', Pictographic),
+    ('\u{1F3F7}', '\u{1F3FA}', Pictographic),
+    ('\u{1F3FB}', '\u{1F3FF}', Extend),
+    ('\u{1F400}', '\u{1F4FD}', Pictographic),
+    ('\u{1F4FF}', '\u{1F53D}', Pictographic),
+    ('\u{1F549}', '\u{1F54E}', Pictographic),
+    ('\u{1F550}', '\u{1F567}', Pictographic),
+    ('\u{1F56F}', '\u{1F570}', Pictographic),
+    ('\u{1F573}', '\u{1F57A}', Pictographic),
+    ('\u{1F587}', '\u{1F587}', Pictographic),
+    ('\u{1F58A}', '\u{1F58D}', Pictographic),
+    ('\u{1F590}', '\u{1F590}', Pictographic),
+    ('\u{1F595}', '\u{1F596}', Pictographic),
+    ('\u{1F5A4}', '\u{1F5A5}', Pictographic),
+    ('\u{1F5A8}', '\u{1F5A8}', Pictographic),
+    ('\u{1F5B1}', '\u{1F5B2}', Pictographic),
+    ('\u{1F5BC}', '\u{1F5BC}', Pictographic),
+    ('\u{1F5C2}', '\u{1F5C4}', Pictographic),
+    ('\u{1F5D1}', '\u{1F5D3}', Pictographic),
+    ('\u{1F5DC}', '\u{1F5DE}', Pictographic),
+    ('\u{1F5E1}', '\u{1F5E1}', Pictographic),
+    ('\u{1F5E3}', '\u{1F5E3}', Pictographic),
+    ('\u{1F5E8}', '\u{1F5E8}', Pictographic),
+    ('\u{1F5EF}', '\u{1F5EF}', Pictographic),
+    ('\u{1F5F3}', '\u{1F5F3}', Pictographic),
+    ('\u{1F5FA}', '\u{1F64F}', Pictographic),
+    ('\u{1F680}', '\u{1F6C5}', Pictographic),
+    ('\u{1F6CB}', '\u{1F6D2}', Pictographic),
+    ('\u{1F6D5}', '\u{1F6E5}', Pictographic),
+    ('\u{1F6E9}', '\u{1F6E9}', Pictographic),
+    ('\u{1F6EB}', '\u{1F6F0}', Pictographic),
+    ('\u{1F6F3}', '\u{1F6FF}', Pictographic),
+    ('\u{1F7DA}', '\u{1F7FF}', Pictographic),
+    ('\u{1F80C}', '\u{1F80F}', Pictographic),
+    ('\u{1F848}', '\u{1F84F}', Pictographic),
+    ('\u{1F85A}', '\u{1F85F}', Pictographic),
+    ('\u{1F888}', '\u{1F88F}', Pictographic),
+    ('\u{1F8AE}', '\u{1F8AF}', Pictographic),
+    ('\u{1F8BC}', '\u{1F8BF}', Pictographic),
+    ('\u{1F8C2}', '\u{1F8CF}', Pictographic),
+    ('\u{1F8D9}', '\u{1F8FF}', Pictographic),
+    ('\u{1F90C}', '\u{1F93A}', Pictographic),
+    ('\u{1F93C}', '\u{1F945}', Pictographic),
+    ('\u{1F947}', '\u{1F9FF}', Pictographic),
+    ('\u{1FA58}', '\u{1FA5F}', Pictographic),
+    ('\u{1FA6E}', '\u{1FAFF}', Pictographic),
+    ('\u{1FC00}', '\u{1FFFD}', Pictographic),
+    ('\u{E0000}', '\u{E001F}', Control),
+    ('\u{E0020}', '\u{E007F}', Extend),
+    ('\u{E0080}', '\u{E00FF}', Control),
+    ('\u{E0100}', '\u{E01EF}', Extend),
+    ('\u{E01F0}', '\u{E0FFF}', Control),
+];
+
+/// Sorted ranges with `Indic_Conjunct_Break=Extend`
+#[rustfmt::skip]
+pub(super) const INCB_EXTEND: &[(char, char)] = &[
+    ('\u{300}', '\u{36F}'), ('\u{483}', '\u{489}'), ('\u{591}', '\u{5BD}'), ('\u{5BF}', '\u{5BF}'),
+    ('\u{5C1}', '\u{5C2}'), ('\u{5C4}', '\u{5C5}'), ('\u{5C7}', '\u{5C7}'), ('\u{610}', '\u{61A}'),
+    ('\u{64B}', '\u{65F}'), ('\u{670}', '\u{670}'), ('\u{6D6}', '\u{6DC}'), ('\u{6DF}', '\u{6E4}'),
+    ('\u{6E7}', '\u{6E8}'), ('\u{6EA}', '\u{6ED}'), ('\u{711}', '\u{711}'), ('\u{730}', '\u{74A}'),
+    ('\u{7A6}', '\u{7B0}'), ('\u{7EB}', '\u{7F3}'), ('\u{7FD}', '\u{7FD}'), ('\u{816}', '\u{819}'),
+    ('\u{81B}', '\u{823}'), ('\u{825}', '\u{827}'), ('\u{829}', '\u{82D}'), ('\u{859}', '\u{85B}'),
+    ('\u{897}', '\u{89F}'), ('\u{8CA}', '\u{8E1}'), ('\u{8E3}', '\u{902}'), ('\u{93A}', '\u{93A}'),
+    ('\u{93C}', '\u{93C}'), ('\u{941}', '\u{948}'), ('\u{951}', '\u{957}'), ('\u{962}', '\u{963}'),
+    ('\u{981}', '\u{981}'), ('\u{9BC}', '\u{9BC}'), ('\u{9BE}', '\u{9BE}'), ('\u{9C1}', '\u{9C4}'),
+    ('\u{9D7}', '\u{9D7}'), ('\u{9E2}', '\u{9E3}'), ('\u{9FE}', '\u{9FE}'), ('\u{A01}', '\u{A02}'),
+    ('\u{A3C}', '\u{A3C}'), ('\u{A41}', '\u{A42}'), ('\u{A47}', '\u{A48}'), ('\u{A4B}', '\u{A4D}'),
+    ('\u{A51}', '\u{A51}'), ('\u{A70}', '\u{A71}'), ('\u{A75}', '\u{A75}'), ('\u{A81}', '\u{A82}'),
+    ('\u{ABC}', '\u{ABC}'), ('\u{AC1}', '\u{AC5}'), ('\u{AC7}', '\u{AC8}'), ('\u{AE2}', '\u{AE3}'),
+    ('\u{AFA}', '\u{AFF}'), ('\u{B01}', '\u{B01}'), ('\u{B3C}', '\u{B3C}'), ('\u{B3E}', '\u{B3F}'),
+    ('\u{B41}', '\u{B44}'), ('\u{B55}', '\u{B57}'), ('\u{B62}', '\u{B63}'), ('\u{B82}', '\u{B82}'),
+    ('\u{BBE}', '\u{BBE}'), ('\u{BC0}', '\u{BC0}'), ('\u{BCD}', '\u{BCD}'), ('\u{BD7}', '\u{BD7}'),
+    ('\u{C00}', '\u{C00}'), ('\u{C04}', '\u{C04}'), ('\u{C3C}', '\u{C3C}'), ('\u{C3E}', '\u{C40}'),
+    ('\u{C46}', '\u{C48}'), ('\u{C4A}', '\u{C4C}'), ('\u{C55}', '\u{C56}'), ('\u{C62}', '\u{C63}'),
+    ('\u{C81}', '\u{C81}'), ('\u{CBC}', '\u{CBC}'), ('\u{CBF}', '\u{CC0}'), ('\u{CC2}', '\u{CC2}'),
+    ('\u{CC6}', '\u{CC8}'), ('\u{CCA}', '\u{CCD}'), ('\u{CD5}', '\u{CD6}'), ('\u{CE2}', '\u{CE3}'),
+    ('\u{D00}', '\u{D01}'), ('\u{D3B}', '\u{D3C}'), ('\u{D3E}', '\u{D3E}'), ('\u{D41}', '\u{D44}'),
+    ('\u{D57}', '\u{D57}'), ('\u{D62}', '\u{D63}'), ('\u{D81}', '\u{D81}'), ('\u{DCA}', '\u{DCA}'),
+    ('\u{DCF}', '\u{DCF}'), ('\u{DD2}', '\u{DD4}'), ('\u{DD6}', '\u{DD6}'), ('\u{DDF}', '\u{DDF}'),
+    ('\u{E31}', '\u{E31}'), ('\u{E34}', '\u{E3A}'), ('\u{E47}', '\u{E4E}'), ('\u{EB1}', '\u{EB1}'),
+    ('\u{EB4}', '\u{EBC}'), ('\u{EC8}', '\u{ECE}'), ('\u{F18}', '\u{F19}'), ('\u{F35}', '\u{F35}'),
+    ('\u{F37}', '\u{F37}'), ('\u{F39}', '\u{F39}'), ('\u{F71}', '\u{F7E}'), ('\u{F80}', '\u{F84}'),
+    ('\u{F86}', '\u{F87}'), ('\u{F8D}', '\u{F97}'), ('\u{F99}', '\u{FBC}'), ('\u{FC6}', '\u{FC6}'),
+    ('\u{102D}', '\u{1030}'), ('\u{1032}', '\u{1037}'), ('\u{103A}', '\u{103A}'), ('\u{103D}', '\u{103E}'),
+    ('\u{1058}', '\u{1059}'), ('\u{105E}', '\u{1060}'), ('\u{1071}', '\u{1074}'), ('\u{1082}', '\u{1082}'),
+    ('\u{1085}', '\u{1086}'), ('\u{108D}', '\u{108D}'), ('\u{109D}', '\u{109D}'), ('\u{135D}', '\u{135F}'),
+    ('\u{1712}', '\u{1715}'), ('\u{1732}', '\u{1734}'), ('\u{1752}', '\u{1753}'), ('\u{1772}', '\u{1773}'),
+    ('\u{17B4}', '\u{17B5}'), ('\u{17B7}', '\u{17BD}'), ('\u{17C6}', '\u{17C6}'), ('\u{17C9}', '\u{17D1}'),
+    ('\u{17D3}', '\u{17D3}'), ('\u{17DD}', '\u{17DD}'), ('\u{180B}', '\u{180D}'), ('\u{180F}', '\u{180F}'),
+    ('\u{1885}', '\u{1886}'), ('\u{18A9}', '\u{18A9}'), ('\u{1920}', '\u{1922}'), ('\u{1927}', '\u{1928}'),
+    ('\u{1932}', '\u{1932}'), ('\u{1939}', '\u{193B}'), ('\u{1A17}', '\u{1A18}'), ('\u{1A1B}', '\u{1A1B}'),
+    ('\u{1A56}', '\u{1A56}'), ('\u{1A58}', '\u{1A5E}'), ('\u{1A62}', '\u{1A62}'), ('\u{1A65}', '\u{1A6C}'),
+    ('\u{1A73}', '\u{1A7C}'), ('\u{1A7F}', '\u{1A7F}'), ('\u{1AB0}', '\u{1ADD}'), ('\u{1AE0}', '\u{1AEB}'),
+    ('\u{1B00}', '\u{1B03}'), ('\u{1B34}', '\u{1B3D}'), ('\u{1B42}', '\u{1B43}'), ('\u{1B6B}', '\u{1B73}'),
+    ('\u{1B80}', '\u{1B81}'), ('\u{1BA2}', '\u{1BA5}'), ('\u{1BA8}', '\u{1BAA}'), ('\u{1BAC}', '\u{1BAD}'),
+    ('\u{1BE6}', '\u{1BE6}'), ('\u{1BE8}', '\u{1BE9}'), ('\u{1BED}', '\u{1BED}'), ('\u{1BEF}', '\u{1BF3}'),
+    ('\u{1C2C}', '\u{1C33}'), ('\u{1C36}', '\u{1C37}'), ('\u{1CD0}', '\u{1CD2}'), ('\u{1CD4}', '\u{1CE0}'),
+    ('\u{1CE2}', '\u{1CE8}'), ('\u{1CED}', '\u{1CED}'), ('\u{1CF4}', '\u{1CF4}'), ('\u{1CF8}', '\u{1CF9}'),
+    ('\u{1DC0}', '\u{1DFF}'), ('\u{200D}', '\u{200D}'), ('\u{20D0}', '\u{20F0}'), ('\u{2CEF}', '\u{2CF1}'),
+    ('\u{2D7F}', '\u{2D7F}'), ('\u{2DE0}', '\u{2DFF}'), ('\u{302A}', '\u{302F}'), ('\u{3099}', '\u{309A}'),
+    ('\u{A66F}', '\u{A672}'), ('\u{A674}', '\u{A67D}'), ('\u{A69E}', '\u{A69F}'), ('\u{A6F0}', '\u{A6F1}'),
+    ('\u{A802}', '\u{A802}'), ('\u{A806}', '\u{A806}'), ('\u{A80B}', '\u{A80B}'), ('\u{A825}', '\u{A826}'),
+    ('\u{A82C}', '\u{A82C}'), ('\u{A8C4}', '\u{A8C5}'), ('\u{A8E0}', '\u{A8F1}'), ('\u{A8FF}', '\u{A8FF}'),
+    ('\u{A926}', '\u{A92D}'), ('\u{A947}', '\u{A951}'), ('\u{A953}', '\u{A953}'), ('\u{A980}', '\u{A982}'),
+    ('\u{A9B3}', '\u{A9B3}'), ('\u{A9B6}', '\u{A9B9}'), ('\u{A9BC}', '\u{A9BD}'), ('\u{A9E5}', '\u{A9E5}'),
+    ('\u{AA29}', '\u{AA2E}'), ('\u{AA31}', '\u{AA32}'), ('\u{AA35}', '\u{AA36}'), ('\u{AA43}', '\u{AA43}'),
+    ('\u{AA4C}', '\u{AA4C}'), ('\u{AA7C}', '\u{AA7C}'), ('\u{AAB0}', '\u{AAB0}'), ('\u{AAB2}', '\u{AAB4}'),
+    ('\u{AAB7}', '\u{AAB8}'), ('\u{AABE}', '\u{AABF}'), ('\u{AAC1}', '\u{AAC1}'), ('\u{AAEC}', '\u{AAED}'),
+    ('\u{ABE5}', '\u{ABE5}'), ('\u{ABE8}', '\u{ABE8}'), ('\u{ABED}', '\u{ABED}'), ('\u{FB1E}', '\u{FB1E}'),
+    ('\u{FE00}', '\u{FE0F}'), ('\u{FE20}', '\u{FE2F}'), ('\u{FF9E}', '\u{FF9F}'), ('\u{101FD}', '\u{101FD}'),
+    ('\u{102E0}', '\u{102E0}'), ('\u{10376}', '\u{1037A}'), ('\u{10A01}', '\u{10A03}'), ('\u{10A05}', '\u{10A06}'),
+    ('\u{10A0C}', '\u{10A0F}'), ('\u{10A38}', '\u{10A3A}'), ('\u{10AE5}', '\u{10AE6}'), ('\u{10D24}', '\u{10D27}'),
+    ('\u{10D69}', '\u{10D6D}'), ('\u{10EAB}', '\u{10EAC}'), ('\u{10EFA}', '\u{10EFF}'), ('\u{10F46}', '\u{10F50}'),
+    ('\u{10F82}', '\u{10F85}'), ('\u{11001}', '\u{11001}'), ('\u{11038}', '\u{11046}'), ('\u{11070}', '\u{11070}'),
+    ('\u{11073}', '\u{11074}'), ('\u{1107F}', '\u{11081}'), ('\u{110B3}', '\u{110B6}'), ('\u{110B9}', '\u{110BA}'),
+    ('\u{110C2}', '\u{110C2}'), ('\u{11100}', '\u{11102}'), ('\u{11127}', '\u{1112B}'), ('\u{1112D}', '\u{11132}'),
+    ('\u{11134}', '\u{11134}'), ('\u{11173}', '\u{11173}'), ('\u{11180}', '\u{11181}'), ('\u{111B6}', '\u{111BE}'),
+    ('\u{111C0}', '\u{111C0}'), ('\u{111C9}', '\u{111CC}'), ('\u{111CF}', '\u{111CF}'), ('\u{1122F}', '\u{11231}'),
+    ('\u{11234}', '\u{11237}'), ('\u{1123E}', '\u{1123E}'), ('\u{11241}', '\u{11241}'), ('\u{112DF}', '\u{112DF}'),
+    ('\u{112E3}', '\u{112EA}'), ('\u{11300}', '\u{11301}'), ('\u{1133B}', '\u{1133C}'), ('\u{1133E}', '\u{1133E}'),
+    ('\u{11340}', '\u{11340}'), ('\u{1134D}', '\u{1134D}'), ('\u{11357}', '\u{11357}'), ('\u{11366}', '\u{1136C}'),
+    ('\u{11370}', '\u{11374}'), ('\u{113B8}', '\u{113B8}'), ('\u{113BB}', '\u{113C0}'), ('\u{113C2}', '\u{113C2}'),
+    ('\u{113C5}', '\u{113C5}'), ('\u{113C7}', '\u{113C9}'), ('\u{113CE}', '\u{113CF}'), ('\u{113D2}', '\u{113D2}'),
+    ('\u{113E1}', '\u{113E2}'), ('\u{11438}', '\u{1143F}'), ('\u{11442}', '\u{11444}'), ('\u{11446}', '\u{11446}'),
+    ('\u{1145E}', '\u{1145E}'), ('\u{114B0}', '\u{114B0}'), ('\u{114B3}', '\u{114B8}'), ('\u{114BA}', '\u{114BA}'),
+    ('\u{114BD}', '\u{114BD}'), ('\u{114BF}', '\u{114C0}'), ('\u{114C2}', '\u{114C3}'), ('\u{115AF}', '\u{115AF}'),
+    ('\u{115B2}', '\u{115B5}'), ('\u{115BC}', '\u{115BD}'), ('\u{115BF}', '\u{115C0}'), ('\u{115DC}', '\u{115DD}'),
+    ('\u{11633}', '\u{1163A}'), ('\u{1163D}', '\u{1163D}'), ('\u{1163F}', '\u{11640}'), ('\u{116AB}', '\u{116AB}'),
+    ('\u{116AD}', '\u{116AD}'), ('\u{116B0}', '\u{116B7}'), ('\u{1171D}', '\u{1171D}'), ('\u{1171F}', '\u{1171F}'),
+    ('\u{11722}', '\u{11725}'), ('\u{11727}', '\u{1172B}'), ('\u{1182F}', '\u{11837}'), ('\u{11839}', '\u{1183A}'),
+    ('\u{11930}', '\u{11930}'), ('\u{1193B}', '\u{1193D}'), ('\u{11943}', '\u{11943}'), ('\u{119D4}', '\u{119D7}'),
+    ('\u{119DA}', '\u{119DB}'), ('\u{119E0}', '\u{119E0}'), ('\u{11A01}', '\u{11A0A}'), ('\u{11A33}', '\u{11A38}'),
+    ('\u{11A3B}', '\u{11A3E}'), ('\u{11A51}', '\u{11A56}'), ('\u{11A59}', '\u{11A5B}'), ('\u{11A8A}', '\u{11A96}'),
+    ('\u{11A98}', '\u{11A98}'), ('\u{11B60}', '\u{11B60}'), ('\u{11B62}', '\u{11B64}'), ('\u{11B66}', '\u{11B66}'),
+    ('\u{11C30}', '\u{11C36}'), ('\u{11C38}', '\u{11C3D}'), ('\u{11C3F}', '\u{11C3F}'), ('\u{11C92}', '\u{11CA7}'),
+    ('\u{11CAA}', '\u{11CB0}'), ('\u{11CB2}', '\u{11CB3}'), ('\u{11CB5}', '\u{11CB6}'), ('\u{11D31}', '\u{11D36}'),
+    ('\u{11D3A}', '\u{11D3A}'), ('\u{11D3C}', '\u{11D3D}'), ('\u{11D3F}', '\u{11D45}'), ('\u{11D47}', '\u{11D47}'),
+    ('\u{11D90}', '\u{11D91}'), ('\u{11D95}', '\u{11D95}'), ('\u{11D97}', '\u{11D97}'), ('\u{11EF3}', '\u{11EF4}'),
+    ('\u{11F00}', '\u{11F01}'), ('\u{11F36}', '\u{11F3A}'), ('\u{11F40}', '\u{11F41}'), ('\u{11F5A}', '\u{11F5A}'),
+    ('\u{13440}', '\u{13440}'), ('\u{13447}', '\u{13455}'), ('\u{1611E}', '\u{16129}'), ('\u{1612D}', '\u{1612F}'),
+    ('\u{16AF0}', '\u{16AF4}'), ('\u{16B30}', '\u{16B36}'), ('\u{16F4F}', '\u{16F4F}'), ('\u{16F8F}', '\u{16F92}'),
+    ('\u{16FE4}', '\u{16FE4}'), ('\u{16FF0}', '\u{16FF1}'), ('\u{1BC9D}', '\u{1BC9E}'), ('\u{1CF00}', '\u{1CF2D}'),
+    ('\u{1CF30}', '\u{1CF46}'), ('\u{1D165}', '\u{1D169}'), ('\u{1D16D}', '\u{1D172}'), ('\u{1D17B}', '\u{1D182}'),
+    ('\u{1D185}', '\u{1D18B}'), ('\u{1D1AA}', '\u{1D1AD}'), ('\u{1D242}', '\u{1D244}'), ('\u{1DA00}', '\u{1DA36}'),
+    ('\u{1DA3B}', '\u{1DA6C}'), ('\u{1DA75}', '\u{1DA75}'), ('\u{1DA84}', '\u{1DA84}'), ('\u{1DA9B}', '\u{1DA9F}'),
+    ('\u{1DAA1}', '\u{1DAAF}'), ('\u{1E000}', '\u{1E006}'), ('\u{1E008}', '\u{1E018}'), ('\u{1E01B}', '\u{1E021}'),
+    ('\u{1E023}', '\u{1E024}'), ('\u{1E026}', '\u{1E02A}'), ('\u{1E08F}', '\u{1E08F}'), ('\u{1E130}', '\u{1E136}'),
+    ('\u{1E2AE}', '\u{1E2AE}'), ('\u{1E2EC}', '\u{1E2EF}'), ('\u{1E4EC}', '\u{1E4EF}'), ('\u{1E5EE}', '\u{1E5EF}'),
+    ('\u{1E6E3}', '\u{1E6E3}'), ('\u{1E6E6}', '\u{1E6E6}'), ('\u{1E6EE}', '\u{1E6EF}'), ('\u{1E6F5}', '\u{1E6F5}'),
+    ('\u{1E8D0}', '\u{1E8D6}'), ('\u{1E944}', '\u{1E94A}'), ('\u{1F3FB}', '\u{1F3FF}'), ('\u{E0020}', '\u{E007F}'),
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// Characters with `Indic_Conjunct_Break=Linker`, sorted
+#[rustfmt::skip]
+pub(super) const INCB_LINKERS: &[char] = &[
+    '\u{94D}', '\u{9CD}', '\u{ACD}', '\u{B4D}', '\u{C4D}', '\u{D4D}', '\u{1039}', '\u{17D2}',
+    '\u{1A60}', '\u{1B44}', '\u{1BAB}', '\u{A9C0}', '\u{AAF6}', '\u{10A3F}', '\u{11133}', '\u{113D0}',
+    '\u{1193E}', '\u{11A47}', '\u{11A99}', '\u{11F42}',
+];
@@ -0,0 +1,299 @@
+use std::io::Read;
+use std::ops::RangeInclusive;
+
+use super::{LineError, StringStream};
+
+/// How `LogicalLines` turns physical lines into logical ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogicalConfig {
+    /// Lines starting with one of these, after any indentation, are skipped
+    pub comment_prefixes: Vec<String>,
+    /// A line ending with an odd number of `\` is joined with the next one,
+    /// dropping the last `\`
+    pub continuation: bool,
+    /// Strip whitespace around each line, continuation lines are joined
+    /// without their indentation
+    pub trim: bool,
+    /// Skip lines that are empty, after trimming if enabled
+    pub skip_blank: bool,
+}
+impl Default for LogicalConfig {
+    fn default() -> Self {
+        Self {
+            comment_prefixes: vec!["#".to_owned()],
+            continuation: true,
+            trim: true,
+            skip_blank: true,
+        }
+    }
+}
+
+/// Line made from one or more physical lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogicalLine {
+    pub text: String,
+    /// Physical lines it was read from, starting at 1.
+    /// Includes comment lines skipped inside a continuation
+    pub lines: RangeInclusive<usize>,
+}
+
+/// Iterator over the logical lines of a `StringStream`,
+/// skipping comments and joining continued lines
+///
+/// ---
+/// Comments only take up whole lines, a comment line inside a continuation
+/// is skipped without ending it. A continuation on the last line ends
+/// at EOF
+///
+/// **NOTE**: Iteration ends at a line `StringStream::try_next_line`
+/// reports an error for, use `try_next` to tell it apart from EOF
+pub struct LogicalLines<T>
+where
+    T: Read,
+{
+    stream: StringStream<T>,
+    config: LogicalConfig,
+}
+impl<T> LogicalLines<T>
+where
+    T: Read,
+{
+    pub fn new(stream: StringStream<T>) -> Self {
+        Self {
+            stream,
+            config: LogicalConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: LogicalConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    fn is_comment(&self, line: &str) -> bool {
+        let line = line.trim_start();
+        self.config
+            .comment_prefixes
+            .iter()
+            .any(|x| !x.is_empty() && line.starts_with(x.as_str()))
+    }
+
+    /// Whether `line` ends with a continuation, removing the `\`
+    fn continues(&self, line: &mut String) -> bool {
+        if !self.config.continuation {
+            return false;
+        }
+        if self.config.trim {
+            line.truncate(line.trim_end().len());
+        }
+
+        let backslashes = line.bytes().rev().take_while(|&x| x == b'\\').count();
+        if backslashes % 2 == 1 {
+            line.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Read the next logical line, reporting the errors
+    /// `StringStream::try_next_line` does
+    ///
+    /// ---
+    /// A logical line with an error in one of its physical lines is dropped,
+    /// reading carries on at the physical line after the error
+    pub fn try_next(&mut self) -> Option<Result<LogicalLine, LineError>> {
+        'lines: loop {
+            let mut text = String::new();
+            let mut first = None;
+            let mut last = 0;
+            let mut continued = false;
+
+            loop {
+                // Pieces of a split line are all on the line they started on
+                let line_number = StringStream::position(&self.stream).line;
+                let mut line = match self.stream.try_next_line() {
+                    Some(Ok(x)) => x,
+                    Some(Err(e)) => return Some(Err(e)),
+                    None if !continued => return None,
+                    None => break,
+                };
+                first.get_or_insert(line_number);
+                last = line_number;
+
+                if self.is_comment(&line) {
+                    if continued {
+                        continue;
+                    }
+                    continue 'lines;
+                }
+
+                continued = self.continues(&mut line);
+                let piece = match self.config.trim {
+                    true => line.trim_start(),
+                    false => &line,
+                };
+                text.push_str(piece);
+                if !continued {
+                    break;
+                }
+            }
+
+            if self.config.trim {
+                text.truncate(text.trim_end().len());
+            }
+            if text.is_empty() && self.config.skip_blank {
+                continue;
+            }
+
+            return Some(Ok(LogicalLine {
+                text,
+                lines: first.unwrap_or(last)..=last,
+            }));
+        }
+    }
+}
+impl<T> Iterator for LogicalLines<T>
+where
+    T: Read,
+{
+    type Item = LogicalLine;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next()?.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogicalConfig, LogicalLine, LogicalLines};
+    use crate::string_stream::{LineConfig, LineError, LineOverflow, StringStream};
+
+    fn logical(data: &str, config: LogicalConfig) -> Vec<(String, (usize, usize))> {
+        LogicalLines::new(StringStream::with_capacity(4, data.as_bytes()))
+            .with_config(config)
+            .map(|LogicalLine { text, lines }| (text, (*lines.start(), *lines.end())))
+            .collect()
+    }
+
+    fn line(text: &str, first: usize, last: usize) -> (String, (usize, usize)) {
+        (text.to_owned(), (first, last))
+    }
+
+    #[test]
+    fn default_test() {
+        let data = "# header\n\nname = value  \r\n  # indented comment\npath = a \\\n    b \\\n\tc\n   \nlast";
+        assert_eq!(
+            logical(data, LogicalConfig::default()),
+            [
+                line("name = value", 3, 3),
+                line("path = a b c", 5, 7),
+                line("last", 9, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn continuation_test() {
+        let data = "a\\\n# skipped\nb\nescaped \\\\\nend \\";
+        assert_eq!(
+            logical(data, LogicalConfig::default()),
+            [
+                // Comments inside a continuation don't end it
+                line("ab", 1, 3),
+                line("escaped \\\\", 4, 4),
+                // Continued into EOF
+                line("end", 5, 5),
+            ]
+        );
+
+        let config = LogicalConfig {
+            continuation: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            logical("a \\\nb", config),
+            [line("a \\", 1, 1), line("b", 2, 2)]
+        );
+    }
+
+    #[test]
+    fn untrimmed_test() {
+        let config = LogicalConfig {
+            comment_prefixes: vec!["//".to_owned(), ";".to_owned()],
+            trim: false,
+            skip_blank: false,
+            ..Default::default()
+        };
+        let data = "  keep  \n// comment\n\n; other\n  a\\\n  b \n# not a comment";
+        assert_eq!(
+            logical(data, config),
+            [
+                line("  keep  ", 1, 1),
+                line("", 3, 3),
+                line("  a  b ", 5, 6),
+                line("# not a comment", 7, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_comments_test() {
+        let config = LogicalConfig {
+            comment_prefixes: Vec::new(),
+            ..Default::default()
+        };
+        assert_eq!(
+            logical("# a\n\n b ", config),
+            [line("# a", 1, 1), line("b", 3, 3)]
+        );
+    }
+
+    #[test]
+    fn split_lines_test() {
+        let stream =
+            StringStream::new("abcdefg\nx\\\nyz\nend".as_bytes()).with_line_config(LineConfig {
+                max_len: Some(3),
+                overflow: LineOverflow::Split,
+                ..Default::default()
+            });
+        let lines = LogicalLines::new(stream)
+            .map(|LogicalLine { text, lines }| (text, (*lines.start(), *lines.end())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                line("abc", 1, 1),
+                line("def", 1, 1),
+                line("g", 1, 1),
+                line("xyz", 2, 3),
+                line("end", 4, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_test() {
+        let data = b"a \\\nb\xFFc\nd\ntoo long\ne";
+        let config = LineConfig {
+            max_len: Some(3),
+            ..Default::default()
+        };
+        let mut lines = LogicalLines::new(StringStream::new(&data[..]).with_line_config(config));
+        assert!(matches!(lines.try_next(), Some(Err(LineError::Decode(_)))));
+        assert_eq!(
+            lines.try_next().unwrap().unwrap(),
+            LogicalLine {
+                text: "d".to_owned(),
+                lines: 3..=3
+            }
+        );
+        assert!(matches!(lines.try_next(), Some(Err(LineError::TooLong(_)))));
+        assert_eq!(lines.try_next().unwrap().unwrap().lines, 5..=5);
+        assert!(lines.try_next().is_none());
+
+        // Iteration ends at the first error
+        let lines = LogicalLines::new(StringStream::new(&data[..])).collect::<Vec<_>>();
+        assert!(lines.is_empty());
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::rc::Rc;
+
+use super::{
+    DecodeError, DecodeMode, Encoding, LineConfig, LineEnding, LineError, Position, StringStream,
+};
+
+/// Location in one of the sources of a `MultiStream`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourcePosition {
+    pub source: Rc<str>,
+    /// Position within that source
+    pub position: Position,
+}
+impl Display for SourcePosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.source, self.position.line, self.position.column
+        )
+    }
+}
+
+/// Line read from a `MultiStream`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLine {
+    pub source: Rc<str>,
+    /// Line number within the source, starting at 1
+    pub line: usize,
+    pub text: String,
+    /// `None` for the last line of a source without a terminator
+    pub ending: Option<LineEnding>,
+}
+
+/// Error reading a line from one of the sources of a `MultiStream`
+#[derive(Debug)]
+pub struct SourceError {
+    pub source: Rc<str>,
+    pub error: LineError,
+}
+impl Display for SourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.source, self.error)
+    }
+}
+impl std::error::Error for SourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Named sources read one after another as a single stream
+///
+/// Each source is decoded by its own `StringStream`, so line numbers,
+/// byte order marks and unfinished lines or sequences never carry over
+/// from one source to the next
+///
+/// ---
+/// **NOTE**: Characters stop wherever `StringStream::next` would, such as
+/// at invalid data with `DecodeMode::Stop`, and carry on in the same source
+/// when read again. Lines move on to the next source when reading one fails
+pub struct MultiStream<T>
+where
+    T: Read,
+{
+    pending: VecDeque<(Rc<str>, T)>,
+    current: Option<(Rc<str>, StringStream<T>)>,
+    capacity: usize,
+    mode: DecodeMode,
+    encoding: Option<Encoding>,
+    line_config: LineConfig,
+}
+impl<T> MultiStream<T>
+where
+    T: Read,
+{
+    pub fn new<S>(sources: impl IntoIterator<Item = (S, T)>) -> Self
+    where
+        S: Into<Rc<str>>,
+    {
+        Self {
+            pending: sources.into_iter().map(|(s, x)| (s.into(), x)).collect(),
+            current: None,
+            capacity: super::DEFAULT_CAPACITY,
+            mode: DecodeMode::default(),
+            encoding: Some(Encoding::default()),
+            line_config: LineConfig::default(),
+        }
+    }
+
+    /// Add a source after the others
+    pub fn push(&mut self, name: impl Into<Rc<str>>, x: T) {
+        self.pending.push_back((name.into(), x));
+    }
+
+    /// Buffer size of the stream for each source
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is less than 4
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity >= super::MIN_CAPACITY, "Capacity too small");
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: DecodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Decode every source as `encoding`
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// Pick the encoding of each source from its byte order mark
+    pub fn detect_encoding(mut self) -> Self {
+        self.encoding = None;
+        self
+    }
+
+    pub fn with_line_config(mut self, config: LineConfig) -> Self {
+        self.line_config = config;
+        self
+    }
+
+    /// Stream of the source being read, opening the next one if needed
+    fn stream(&mut self) -> Option<&mut (Rc<str>, StringStream<T>)> {
+        if self.current.is_none() {
+            let (name, x) = self.pending.pop_front()?;
+            let stream = StringStream::with_capacity(self.capacity, x)
+                .with_mode(self.mode)
+                .with_line_config(self.line_config);
+            let stream = match self.encoding {
+                Some(encoding) => stream.with_encoding(encoding),
+                None => stream.detect_encoding(),
+            };
+            self.current = Some((name, stream));
+        }
+        self.current.as_mut()
+    }
+
+    /// Name of the source being read, `None` once all are done
+    ///
+    /// ---
+    /// Still names the previous source right after its last character,
+    /// the next one is opened when reading from it
+    pub fn source(&self) -> Option<&str> {
+        self.current
+            .as_ref()
+            .map(|(name, _)| &**name)
+            .or_else(|| self.pending.front().map(|(name, _)| &**name))
+    }
+
+    /// Position of the next character to be read
+    pub fn position(&self) -> Option<SourcePosition> {
+        match &self.current {
+            Some((source, stream)) => Some(SourcePosition {
+                source: source.clone(),
+                position: stream.position(),
+            }),
+            None => self.pending.front().map(|(source, _)| SourcePosition {
+                source: source.clone(),
+                position: Position::default(),
+            }),
+        }
+    }
+
+    /// Read the next line from the current source,
+    /// moving on to the next source at the end of one
+    ///
+    /// ---
+    /// Lines that `try_next_line` reports an error for are skipped,
+    /// the rest of a source that fails to read is too
+    pub fn next_line(&mut self) -> Option<SourceLine> {
+        loop {
+            match self.try_next_line()? {
+                Ok(x) => return Some(x),
+                Err(SourceError {
+                    error: LineError::Decode(DecodeError::Io { .. }),
+                    ..
+                }) => self.current = None,
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Same as `next_line`, but reports errors the same as
+    /// `StringStream::try_next_line` along with the source they are in
+    ///
+    /// ---
+    /// Reading carries on at the next line of the same source after an error
+    pub fn try_next_line(&mut self) -> Option<Result<SourceLine, SourceError>> {
+        loop {
+            let (source, stream) = self.stream()?;
+            let line = StringStream::position(stream).line;
+            let mut text = String::new();
+            let result = match stream.try_read_line_into(&mut text) {
+                Some(x) => x,
+                None => {
+                    self.current = None;
+                    continue;
+                }
+            };
+
+            let source = source.clone();
+            return Some(match result {
+                Ok(ending) => Ok(SourceLine {
+                    source,
+                    line,
+                    text,
+                    ending,
+                }),
+                Err(error) => Err(SourceError { source, error }),
+            });
+        }
+    }
+
+    /// Iterator over the lines of every source
+    pub fn lines(mut self) -> impl Iterator<Item = SourceLine> {
+        std::iter::from_fn(move || self.next_line())
+    }
+
+    /// Iterate over characters along with where each one starts
+    pub fn char_positions(&mut self) -> impl Iterator<Item = (SourcePosition, char)> + '_ {
+        std::iter::from_fn(move || loop {
+            let (source, stream) = self.stream()?;
+            stream.skip_bom();
+            let position = StringStream::position(stream);
+            match stream.try_next() {
+                Some(result) => {
+                    let ch = stream.resolve(&result)?;
+                    let source = source.clone();
+                    return Some((SourcePosition { source, position }, ch));
+                }
+                None => self.current = None,
+            }
+        })
+    }
+}
+impl<T> Iterator for MultiStream<T>
+where
+    T: Read,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Only the end of a source moves on to the next one
+            let stream = &mut self.stream()?.1;
+            match stream.try_next() {
+                Some(result) => return stream.resolve(&result),
+                None => self.current = None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiStream, SourceLine};
+    use crate::string_stream::{
+        DecodeError, LineConfig, LineEnding, LineError, LineOverflow, Position,
+    };
+
+    fn line(source: &str, line: usize, text: &str, ending: Option<LineEnding>) -> SourceLine {
+        SourceLine {
+            source: source.into(),
+            line,
+            text: text.to_owned(),
+            ending,
+        }
+    }
+
+    #[test]
+    fn lines_test() {
+        let sources = [
+            ("a.log", "one\r\ntwo\n".as_bytes()),
+            ("empty.log", b""),
+            ("b.log", b"no newline"),
+            ("c.log", b"\nthird"),
+        ];
+        let lines = MultiStream::new(sources)
+            .with_capacity(4)
+            .lines()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            [
+                line("a.log", 1, "one", Some(LineEnding::CrLf)),
+                line("a.log", 2, "two", Some(LineEnding::Lf)),
+                // Never joined with the first line of the next source
+                line("b.log", 1, "no newline", None),
+                line("c.log", 1, "", Some(LineEnding::Lf)),
+                line("c.log", 2, "third", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_sequence_test() {
+        // A sequence cut off at the end of a source is invalid there
+        let sources = [("a", &b"x\xE2\x9C"[..]), ("b", b"\x93y")];
+        let chars = MultiStream::new(sources)
+            .with_mode(crate::string_stream::DecodeMode::Lossy)
+            .collect::<String>();
+        assert_eq!(chars, "x\u{FFFD}\u{FFFD}y");
+
+        // Invalid data ends the characters without skipping the rest
+        let sources = [("a", &b"x\xFFy"[..]), ("b", b"z")];
+        let mut stream = MultiStream::new(sources);
+        assert_eq!(stream.by_ref().collect::<String>(), "x");
+        assert_eq!(stream.source(), Some("a"));
+        assert_eq!(stream.collect::<String>(), "yz");
+
+        let mut stream = MultiStream::new(sources);
+        let chars = stream
+            .char_positions()
+            .map(|(position, ch)| format!("{position} {ch}"))
+            .collect::<Vec<_>>();
+        assert_eq!(chars, ["a:1:1 x"]);
+        assert_eq!(stream.position().unwrap().position.offset, 2);
+    }
+
+    #[test]
+    fn positions_test() {
+        let mut stream = MultiStream::new([("first", "a\nb".as_bytes())]);
+        stream.push("second".to_owned(), "\u{FEFF}\u{e9}".as_bytes());
+        let mut stream = stream.detect_encoding();
+
+        assert_eq!(stream.source(), Some("first"));
+        assert_eq!(stream.position().unwrap().to_string(), "first:1:1");
+
+        let chars = stream
+            .char_positions()
+            .map(|(position, ch)| (position.to_string(), position.position.offset, ch))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chars,
+            [
+                ("first:1:1".to_owned(), 0, 'a'),
+                ("first:1:2".to_owned(), 1, '\n'),
+                ("first:2:1".to_owned(), 2, 'b'),
+                // After the byte order mark
+                ("second:1:1".to_owned(), 3, '\u{e9}'),
+            ]
+        );
+        assert_eq!(stream.next(), None);
+        assert_eq!(stream.source(), None);
+        assert_eq!(stream.position(), None);
+
+        let mut stream = MultiStream::new([("x", "ab".as_bytes())]);
+        stream.next();
+        let position = stream.position().unwrap();
+        assert_eq!(&*position.source, "x");
+        assert_eq!(
+            position.position,
+            Position {
+                offset: 1,
+                line: 1,
+                column: 2
+            }
+        );
+    }
+
+    #[test]
+    fn errors_test() {
+        let config = LineConfig {
+            max_len: Some(5),
+            overflow: LineOverflow::Error,
+            ..Default::default()
+        };
+        let sources = [("a", &b"ok\ntoolong\nbad \xFF\nafter\n"[..]), ("b", b"b1")];
+        let stream = || {
+            MultiStream::new(sources)
+                .with_capacity(4)
+                .with_line_config(config)
+        };
+
+        // Errors don't end the source, a line with invalid data is skipped whole
+        let lines = stream()
+            .lines()
+            .map(|x| format!("{}:{} {}", x.source, x.line, x.text))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["a:1 ok", "a:4 after", "b:1 b1"]);
+
+        let mut stream = stream();
+        assert_eq!(stream.try_next_line().unwrap().unwrap().text, "ok");
+        let error = stream.try_next_line().unwrap().unwrap_err();
+        assert!(matches!(&error.error, LineError::TooLong(x) if x.start.line == 2));
+        assert_eq!(
+            error.to_string(),
+            "a: line 2 at byte 3 is longer than 5 bytes"
+        );
+        let error = stream.try_next_line().unwrap().unwrap_err();
+        assert!(matches!(
+            error.error,
+            LineError::Decode(DecodeError::InvalidSequence { offset: 15, .. })
+        ));
+        let line = stream.try_next_line().unwrap().unwrap();
+        assert_eq!((line.line, &*line.text), (4, "after"));
+        assert_eq!(&*stream.try_next_line().unwrap().unwrap().source, "b");
+        assert!(stream.try_next_line().is_none());
+    }
+
+    #[test]
+    fn line_config_test() {
+        // Line numbers follow the terminators in use
+        let config = LineConfig {
+            lf: false,
+            cr: true,
+            ..Default::default()
+        };
+        let sources = [("mac", "one\rtwo\rthree".as_bytes()), ("next", b"x\ry")];
+        let lines = MultiStream::new(sources)
+            .with_line_config(config)
+            .lines()
+            .map(|x| format!("{}:{} {}", x.source, x.line, x.text))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "mac:1 one",
+                "mac:2 two",
+                "mac:3 three",
+                "next:1 x",
+                "next:2 y"
+            ]
+        );
+    }
+
+    #[test]
+    fn boxed_sources_test() {
+        let files: Vec<(String, Box<dyn std::io::Read>)> = vec![
+            ("cursor".into(), Box::new(std::io::Cursor::new("1\n2\n"))),
+            ("slice".into(), Box::new("3\n".as_bytes())),
+        ];
+        let lines = MultiStream::new(files)
+            .lines()
+            .map(|x| format!("{}:{} {}", x.source, x.line, x.text))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["cursor:1 1", "cursor:2 2", "slice:1 3"]);
+    }
+}
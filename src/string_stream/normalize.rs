@@ -0,0 +1,343 @@
+use std::collections::VecDeque;
+use std::io::Read;
+
+use super::StringStream;
+
+mod tables;
+
+/// Unicode normalization form, see UAX #15
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NormalizationForm {
+    /// Canonical decomposition followed by canonical composition
+    Nfc,
+    /// Canonical decomposition
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition
+    Nfkc,
+    /// Compatibility decomposition
+    Nfkd,
+}
+impl NormalizationForm {
+    fn compatibility(self) -> bool {
+        matches!(self, Self::Nfkc | Self::Nfkd)
+    }
+
+    fn composes(self) -> bool {
+        matches!(self, Self::Nfc | Self::Nfkc)
+    }
+}
+
+const S_BASE: u32 = 0xAC00;
+const L_BASE: u32 = 0x1100;
+const V_BASE: u32 = 0x1161;
+const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
+const T_COUNT: u32 = 28;
+const S_COUNT: u32 = L_COUNT * V_COUNT * T_COUNT;
+
+fn combining_class(ch: char) -> u8 {
+    if ch < '\u{300}' {
+        return 0;
+    }
+    tables::COMBINING_CLASSES
+        .binary_search_by_key(&ch, |x| x.0)
+        .map_or(0, |i| tables::COMBINING_CLASSES[i].1)
+}
+
+fn decomposition(table: &[(char, &'static [char])], ch: char) -> Option<&'static [char]> {
+    table
+        .binary_search_by_key(&ch, |x| x.0)
+        .ok()
+        .map(|i| table[i].1)
+}
+
+/// Full decomposition of `ch`, passed to `f` one character at a time
+fn decompose(ch: char, compatibility: bool, mut f: impl FnMut(char)) {
+    if ch.is_ascii() {
+        return f(ch);
+    }
+
+    let s = (ch as u32).wrapping_sub(S_BASE);
+    if s < S_COUNT {
+        let l = L_BASE + s / (V_COUNT * T_COUNT);
+        let v = V_BASE + s % (V_COUNT * T_COUNT) / T_COUNT;
+        let t = T_BASE + s % T_COUNT;
+        // Always valid, Hangul jamo are below the surrogates
+        let jamo = |x| char::from_u32(x).unwrap();
+        f(jamo(l));
+        f(jamo(v));
+        if t != T_BASE {
+            f(jamo(t));
+        }
+        return;
+    }
+
+    let compatible = match compatibility {
+        true => decomposition(tables::COMPATIBILITY, ch),
+        false => None,
+    };
+    match compatible.or_else(|| decomposition(tables::CANONICAL, ch)) {
+        Some(x) => x.iter().copied().for_each(f),
+        None => f(ch),
+    }
+}
+
+/// Primary composite of `a` followed by `b`
+fn compose(a: char, b: char) -> Option<char> {
+    let (a, b) = (a as u32, b as u32);
+    // L + V, and LV + T
+    if (L_BASE..L_BASE + L_COUNT).contains(&a) && (V_BASE..V_BASE + V_COUNT).contains(&b) {
+        let lv = S_BASE + ((a - L_BASE) * V_COUNT + b - V_BASE) * T_COUNT;
+        return char::from_u32(lv);
+    }
+    let s = a.wrapping_sub(S_BASE);
+    if s < S_COUNT && s % T_COUNT == 0 && (T_BASE + 1..T_BASE + T_COUNT).contains(&b) {
+        return char::from_u32(a + b - T_BASE);
+    }
+
+    let (a, b) = (char::from_u32(a)?, char::from_u32(b)?);
+    tables::COMPOSITIONS
+        .binary_search_by(|&(x, y, _)| (x, y).cmp(&(a, b)))
+        .ok()
+        .map(|i| tables::COMPOSITIONS[i].2)
+}
+
+/// Iterator adapter that normalizes the characters of another,
+/// see `StringStream::normalized`
+///
+/// Characters are buffered only until the next one with
+/// a combining class of 0 that can't combine with them
+///
+/// ---
+/// **NOTE**: Long runs of combining marks are buffered whole,
+/// there is no stream-safe limit on them
+pub struct Normalize<I>
+where
+    I: Iterator<Item = char>,
+{
+    iter: I,
+    form: NormalizationForm,
+    /// Decomposed characters with their combining class.
+    /// The first `ready` are in canonical order, the rest still to be sorted
+    decomposed: VecDeque<(char, u8)>,
+    ready: usize,
+    /// Last character with a combining class of 0 that others may compose with
+    starter: Option<char>,
+    /// Characters after `starter` that did not compose with it
+    held: Vec<(char, u8)>,
+    out: VecDeque<char>,
+}
+impl<I> Normalize<I>
+where
+    I: Iterator<Item = char>,
+{
+    pub fn new(iter: I, form: NormalizationForm) -> Self {
+        Self {
+            iter,
+            form,
+            decomposed: VecDeque::new(),
+            ready: 0,
+            starter: None,
+            held: Vec::new(),
+            out: VecDeque::new(),
+        }
+    }
+
+    /// Sort the combining marks after the last ready character
+    fn sort_pending(&mut self) {
+        let ready = self.ready;
+        self.decomposed.make_contiguous()[ready..].sort_by_key(|x| x.1);
+        self.ready = self.decomposed.len();
+    }
+
+    /// Next decomposed character in canonical order
+    fn next_decomposed(&mut self) -> Option<(char, u8)> {
+        while self.ready == 0 {
+            let Some(ch) = self.iter.next() else {
+                self.sort_pending();
+                break;
+            };
+
+            decompose(ch, self.form.compatibility(), |x| {
+                let class = combining_class(x);
+                if class == 0 {
+                    // Marks never move past a starter
+                    self.sort_pending();
+                    self.ready += 1;
+                }
+                self.decomposed.push_back((x, class));
+            });
+        }
+
+        let next = self.decomposed.pop_front()?;
+        self.ready -= 1;
+        Some(next)
+    }
+
+    /// Output the pending starter and whatever didn't compose with it
+    fn flush(&mut self) {
+        self.out.extend(self.starter.take());
+        self.out.extend(self.held.drain(..).map(|x| x.0));
+    }
+
+    fn push_composed(&mut self, ch: char, class: u8) {
+        if let Some(starter) = self.starter {
+            // Blocked by a mark in between with a class as high
+            let blocked = self.held.last().is_some_and(|x| x.1 >= class);
+            if let Some(composite) = compose(starter, ch).filter(|_| !blocked) {
+                self.starter = Some(composite);
+                return;
+            }
+        }
+
+        match (class, self.starter) {
+            (0, _) => {
+                self.flush();
+                self.starter = Some(ch);
+            }
+            (_, Some(_)) => self.held.push((ch, class)),
+            (_, None) => self.out.push_back(ch),
+        }
+    }
+}
+impl<I> Iterator for Normalize<I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.form.composes() {
+            return self.next_decomposed().map(|x| x.0);
+        }
+
+        loop {
+            if let Some(ch) = self.out.pop_front() {
+                return Some(ch);
+            }
+            match self.next_decomposed() {
+                Some((ch, class)) => self.push_composed(ch, class),
+                None => {
+                    self.flush();
+                    return self.out.pop_front();
+                }
+            }
+        }
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read,
+{
+    /// Iterate over characters in the normalization `form`
+    pub fn normalized(&mut self, form: NormalizationForm) -> Normalize<&mut Self> {
+        Normalize::new(self, form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{NormalizationForm, Normalize};
+    use crate::string_stream::StringStream;
+
+    use NormalizationForm::*;
+
+    fn normalize(text: &str, form: NormalizationForm) -> String {
+        Normalize::new(text.chars(), form).collect()
+    }
+
+    fn parse(column: &str) -> String {
+        column
+            .split_whitespace()
+            .map(|x| char::from_u32(u32::from_str_radix(x, 16).unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn conformance_test() {
+        let data = include_str!("testdata/NormalizationTest.txt");
+        let mut part1 = HashSet::new();
+        let mut part = "";
+        let mut cases = 0;
+
+        for (i, line) in data.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(x) = line.strip_prefix('@') {
+                part = x;
+                continue;
+            }
+
+            let c = line.split(';').take(5).map(parse).collect::<Vec<_>>();
+            if part == "Part1" {
+                part1.insert(c[0].chars().next().unwrap());
+            }
+
+            let check = |form, expected: &str, sources: &[usize]| {
+                for &x in sources {
+                    assert_eq!(
+                        normalize(&c[x], form),
+                        expected,
+                        "line {}: {form:?} of c{}",
+                        i + 1,
+                        x + 1
+                    );
+                }
+            };
+            check(Nfc, &c[1], &[0, 1, 2]);
+            check(Nfc, &c[3], &[3, 4]);
+            check(Nfd, &c[2], &[0, 1, 2]);
+            check(Nfd, &c[4], &[3, 4]);
+            check(Nfkc, &c[3], &[0, 1, 2, 3, 4]);
+            check(Nfkd, &c[4], &[0, 1, 2, 3, 4]);
+            cases += 1;
+        }
+        assert!(cases > 20_000);
+
+        // Characters not in part 1 are left as they are by every form
+        let mut buf = [0; 4];
+        for ch in (0..=0x10FFFF).filter_map(char::from_u32) {
+            if part1.contains(&ch) {
+                continue;
+            }
+            let text = &*ch.encode_utf8(&mut buf);
+            for form in [Nfc, Nfd, Nfkc, Nfkd] {
+                assert_eq!(normalize(text, form), text, "{form:?} of {ch:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn identifiers_test() {
+        // Precomposed and combining spellings compare equal once normalized
+        let precomposed = "caf\u{e9} \u{c5}ngstr\u{f6}m";
+        let combining = "cafe\u{301} A\u{30A}ngstro\u{308}m";
+        assert_ne!(precomposed, combining);
+        assert_eq!(normalize(precomposed, Nfd), normalize(combining, Nfd));
+        assert_eq!(normalize(combining, Nfc), precomposed);
+
+        // Compatibility forms fold presentation variants
+        assert_eq!(normalize("\u{FB01}le \u{2460} x\u{B2}", Nfkc), "file 1 x2");
+        assert_eq!(normalize("\u{FB01}", Nfc), "\u{FB01}");
+    }
+
+    #[test]
+    fn stream_test() {
+        // Marks split from their base by the small buffer
+        let text = "e\u{301}\u{323}q\u{307}\u{323}\n\u{1100}\u{1161}\u{11A8} ok";
+        let mut stream = StringStream::with_capacity(4, text.as_bytes());
+        let nfc = stream.normalized(Nfc).collect::<String>();
+        assert_eq!(nfc, "\u{1EB9}\u{301}q\u{323}\u{307}\n\u{AC01} ok");
+        assert_eq!(normalize(&nfc, Nfd), normalize(text, Nfd));
+
+        let mut stream = StringStream::new("\u{AC01}".as_bytes());
+        let nfd = stream.normalized(Nfd).collect::<String>();
+        assert_eq!(nfd, "\u{1100}\u{1161}\u{11A8}");
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::io::{Read, Seek, SeekFrom};
+
+/// Iterator over the lines of a **Read**able and **Seek**able source,
+/// starting from the last line
+///
+/// Lines are split the same way as `StringStream::next_line`,
+/// on `\n` and `\r\n`. Data is read in blocks from the end,
+/// so only the lines that are asked for are ever read
+///
+/// ---
+/// Expects UTF-8, invalid data is replaced with U+FFFD
+/// the same as `DecodeMode::Lossy`
+///
+/// **NOTE**: Reads from the end of the source,
+/// regardless of where it is positioned
+pub struct ReverseLines<T>
+where
+    T: Read + Seek,
+{
+    source: T,
+    block: usize,
+    /// Unread data, ending where the next line to return ends.
+    /// Blocks are added to the front without moving what is already there
+    pending: VecDeque<u8>,
+    /// Offset in the source of the start of `pending`
+    pos: u64,
+    /// The next line to return ended with `\n`
+    terminated: bool,
+    started: bool,
+    done: bool,
+}
+impl<T> ReverseLines<T>
+where
+    T: Read + Seek,
+{
+    pub fn new(x: T) -> Self {
+        Self::with_capacity(super::DEFAULT_CAPACITY, x)
+    }
+
+    /// Read the source in blocks of `capacity` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0
+    pub fn with_capacity(capacity: usize, x: T) -> Self {
+        assert!(capacity > 0, "capacity must not be 0");
+        Self {
+            source: x,
+            block: capacity,
+            pending: VecDeque::new(),
+            pos: 0,
+            terminated: false,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Last `n` lines, in the order they appear in the source
+    pub fn tail(self, n: usize) -> std::io::Result<Vec<String>> {
+        let mut lines = self.take(n).collect::<Result<Vec<_>, _>>()?;
+        lines.reverse();
+        Ok(lines)
+    }
+
+    /// Read the block before `pos` into the front of `pending`
+    fn read_block(&mut self) -> std::io::Result<()> {
+        let len = self.pos.min(self.block as u64) as usize;
+        self.pos -= len as u64;
+        self.source.seek(SeekFrom::Start(self.pos))?;
+
+        let mut data = vec![0; len];
+        self.source.read_exact(&mut data)?;
+        self.pending.reserve(len);
+        for &x in data.iter().rev() {
+            self.pending.push_front(x);
+        }
+        Ok(())
+    }
+
+    fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        if !self.started {
+            self.started = true;
+            self.pos = self.source.seek(SeekFrom::End(0))?;
+            if self.pos > 0 {
+                self.read_block()?;
+            }
+            // A final line feed ends the last line instead of starting an empty one
+            if self.pending.back() == Some(&b'\n') {
+                self.pending.pop_back();
+                self.terminated = true;
+            }
+        }
+
+        // Start of the line, only searching data that hasn't been searched yet
+        let mut searched = self.pending.len();
+        let start = loop {
+            if let Some(i) = self.pending.range(..searched).rposition(|&x| x == b'\n') {
+                break i + 1;
+            }
+            if self.pos == 0 {
+                if self.pending.is_empty() && !self.terminated {
+                    return Ok(None);
+                }
+                self.done = true;
+                break 0;
+            }
+
+            let len = self.pending.len();
+            self.read_block()?;
+            searched = self.pending.len() - len;
+        };
+
+        let mut line = Vec::from(self.pending.split_off(start));
+        if self.terminated && line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        // Drop the line feed, it ends the line before
+        self.pending.pop_back();
+        self.terminated = true;
+
+        Ok(Some(match String::from_utf8(line) {
+            Ok(x) => x,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }))
+    }
+}
+impl<T> Iterator for ReverseLines<T>
+where
+    T: Read + Seek,
+{
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.next_line();
+        if !matches!(result, Ok(Some(_))) {
+            self.done = true;
+        }
+        result.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::ReverseLines;
+    use crate::string_stream::{DecodeMode, StringStream};
+
+    fn reversed(data: &[u8], capacity: usize) -> Vec<String> {
+        ReverseLines::with_capacity(capacity, Cursor::new(data))
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    fn forward(data: &[u8]) -> Vec<String> {
+        StringStream::new(data)
+            .with_mode(DecodeMode::Lossy)
+            .lines()
+            .collect()
+    }
+
+    #[test]
+    fn reverse_lines_test() {
+        let cases: &[&[u8]] = &[
+            b"",
+            b"\n",
+            b"\n\n",
+            b"\r\n",
+            b"one",
+            b"one\n",
+            b"one\r\ntwo\r\n",
+            b"\nleading",
+            b"a\n\nb\r\n\r\nc",
+            b"lone\rcr\r\nend\r",
+            b"\r\r\n\r",
+            "\u{e9}\u{2713}\n\u{1F600}\u{1F600}\r\n\u{2713}".as_bytes(),
+        ];
+
+        for data in cases {
+            let mut expected = forward(data);
+            expected.reverse();
+            for capacity in [1, 2, 3, 5, 64] {
+                assert_eq!(
+                    reversed(data, capacity),
+                    expected,
+                    "{:?} with capacity {capacity}",
+                    String::from_utf8_lossy(data)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_test() {
+        // Blocks cut through sequences, only whole lines are decoded
+        let data = b"ok \xE2\x9C\x93\nbad \xE2\x9C\n\xF0\x9F\x98\x80\xFF\r\n";
+        let mut expected = forward(data);
+        expected.reverse();
+        for capacity in 1..=8 {
+            assert_eq!(reversed(data, capacity), expected);
+        }
+    }
+
+    #[test]
+    fn tail_test() {
+        let data = (1..=1000)
+            .map(|x| format!("line {x}\r\n"))
+            .collect::<String>();
+
+        let tail = ReverseLines::with_capacity(7, Cursor::new(&data))
+            .tail(3)
+            .unwrap();
+        assert_eq!(tail, ["line 998", "line 999", "line 1000"]);
+
+        let all = ReverseLines::new(Cursor::new(&data)).tail(5000).unwrap();
+        assert_eq!(all, forward(data.as_bytes()));
+        assert!(ReverseLines::new(Cursor::new(&data))
+            .tail(0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn long_line_test() {
+        // Spans thousands of blocks
+        let mut data = "\u{2713}".repeat(100_000);
+        data.push_str("\nshort\n");
+        let lines = ReverseLines::with_capacity(16, Cursor::new(&data))
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "short");
+        assert_eq!(lines[1], "\u{2713}".repeat(100_000));
+    }
+
+    #[test]
+    fn reads_from_end_test() {
+        // Only the blocks holding the last line are read
+        let mut data = vec![b'x'; 100_000];
+        data.extend_from_slice(b"\nlast line\n");
+        let mut cursor = Cursor::new(data);
+
+        let mut lines = ReverseLines::with_capacity(8, &mut cursor);
+        assert_eq!(lines.next().unwrap().unwrap(), "last line");
+        assert!(cursor.position() > 99_990);
+    }
+}
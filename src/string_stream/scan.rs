@@ -0,0 +1,164 @@
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::str::FromStr;
+
+use super::{Position, StringStream};
+
+/// A token that failed to parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenError<E> {
+    /// Text of the token
+    pub token: String,
+    /// Where the token starts
+    pub position: Position,
+    /// Error from `FromStr`
+    pub error: E,
+}
+impl<E: Display> Display for TokenError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid token {:?} at {}: {}",
+            self.token, self.position, self.error
+        )
+    }
+}
+impl<E> std::error::Error for TokenError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read,
+{
+    /// Read characters as long as `f` returns true.
+    /// The first character that doesn't match is left in the stream
+    ///
+    /// ---
+    /// Stops at an invalid sequence the same as `next`
+    pub fn read_while(&mut self, mut f: impl FnMut(char) -> bool) -> String {
+        let mut out = String::new();
+        while let Some(ch) = self.peek() {
+            if !f(ch) {
+                break;
+            }
+            self.next();
+            out.push(ch);
+        }
+        out
+    }
+
+    /// Read characters until `f` returns true.
+    /// The matching character is left in the stream
+    pub fn read_until(&mut self, mut f: impl FnMut(char) -> bool) -> String {
+        self.read_while(|x| !f(x))
+    }
+
+    /// Skip whitespace, including new lines
+    pub fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    /// Read the next run of non-whitespace characters,
+    /// `None` if only whitespace is left
+    pub fn next_word(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let word = self.read_until(char::is_whitespace);
+        (!word.is_empty()).then_some(word)
+    }
+
+    /// Read the next word and parse it,
+    /// `None` if only whitespace is left
+    pub fn next_token<U>(&mut self) -> Option<Result<U, TokenError<U::Err>>>
+    where
+        U: FromStr,
+    {
+        self.skip_whitespace();
+        let position = Self::position(self);
+        let token = self.next_word()?;
+        Some(token.parse().map_err(|error| TokenError {
+            token,
+            position,
+            error,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    use crate::string_stream::{Position, StringStream};
+
+    #[test]
+    fn read_while_test() {
+        let mut stream = StringStream::with_capacity(4, "12345abc, def".as_bytes());
+
+        assert_eq!(stream.read_while(|x| x.is_ascii_digit()), "12345");
+        assert_eq!(stream.read_while(|x| x.is_ascii_digit()), "");
+        assert_eq!(stream.read_until(|x| x == ','), "abc");
+        assert_eq!(stream.next(), Some(','));
+        assert_eq!(stream.read_until(|x| x == ','), " def");
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn next_word_test() {
+        let data = "  first  second\n\n\tthird\r\nf\u{f6}urth \u{3000}fifth  \n ";
+        let mut stream = StringStream::with_capacity(4, data.as_bytes());
+
+        let mut words = Vec::new();
+        while let Some(word) = stream.next_word() {
+            words.push(word);
+        }
+        assert_eq!(words, ["first", "second", "third", "f\u{f6}urth", "fifth"]);
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn next_token_test() {
+        let data = "3\n10 -20\n  30\n1.5 yes";
+        let mut stream = StringStream::with_capacity(4, data.as_bytes());
+
+        let n = stream.next_token::<usize>().unwrap().unwrap();
+        let values = (0..n)
+            .map(|_| stream.next_token::<i64>().unwrap().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, [10, -20, 30]);
+        assert_eq!(stream.next_token::<f64>(), Some(Ok(1.5)));
+
+        let error = stream.next_token::<u8>().unwrap().unwrap_err();
+        assert_eq!(error.token, "yes");
+        assert_eq!(
+            error.position,
+            Position {
+                offset: 18,
+                line: 4,
+                column: 5
+            }
+        );
+        assert_eq!(error.error, "yes".parse::<u8>().unwrap_err());
+        assert_eq!(
+            error.to_string(),
+            "invalid token \"yes\" at line 4, column 5: invalid digit found in string"
+        );
+
+        assert_eq!(stream.next_token::<u8>(), None);
+    }
+
+    #[test]
+    fn next_token_error_continues_test() {
+        let mut stream = StringStream::new("1 two 3".as_bytes());
+        let tokens = std::iter::from_fn(|| stream.next_token::<u32>())
+            .map(|x| x.map_err(|e: super::TokenError<ParseIntError>| e.token))
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, [Ok(1), Err("two".to_owned()), Ok(3)]);
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::VecDeque;
+use std::io::Read;
+
+use super::{Position, StringStream};
+
+/// Occurrence of a pattern found by `StringStream::search`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// Index of the pattern in the list given to `Searcher::new`
+    pub pattern: usize,
+    /// Where the match starts
+    pub start: Position,
+    /// Byte offset just past the match
+    pub end: u64,
+}
+
+/// Node of the pattern trie
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// Edges sorted by character
+    next: Vec<(char, usize)>,
+    /// Longest proper suffix that is also in the trie
+    fail: usize,
+    /// Patterns ending here, including those of the fail states
+    output: Vec<usize>,
+}
+
+/// Set of literal patterns to look for, matched all at once
+/// with an Aho-Corasick automaton
+///
+/// ---
+/// Patterns are compared by character, empty patterns never match
+#[derive(Debug, Clone)]
+pub struct Searcher {
+    states: Vec<State>,
+    /// Length of each pattern in characters
+    lens: Vec<usize>,
+    ignore_case: bool,
+}
+impl Searcher {
+    pub fn new<S>(patterns: impl IntoIterator<Item = S>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self::build(patterns, false)
+    }
+
+    /// Treat ASCII letters as equal regardless of case,
+    /// other characters still have to match exactly
+    pub fn ascii_case_insensitive<S>(patterns: impl IntoIterator<Item = S>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self::build(patterns, true)
+    }
+
+    fn build<S>(patterns: impl IntoIterator<Item = S>, ignore_case: bool) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let mut searcher = Self {
+            states: vec![State::default()],
+            lens: Vec::new(),
+            ignore_case,
+        };
+
+        for (i, pattern) in patterns.into_iter().enumerate() {
+            let mut state = 0;
+            let mut len = 0;
+            for ch in pattern.as_ref().chars() {
+                let ch = searcher.fold(ch);
+                state = match searcher.edge(state, ch) {
+                    Some(x) => x,
+                    None => searcher.add_edge(state, ch),
+                };
+                len += 1;
+            }
+            if len > 0 {
+                searcher.states[state].output.push(i);
+            }
+            searcher.lens.push(len);
+        }
+
+        searcher.link();
+        searcher
+    }
+
+    fn fold(&self, ch: char) -> char {
+        match self.ignore_case {
+            true => ch.to_ascii_lowercase(),
+            false => ch,
+        }
+    }
+
+    fn edge(&self, state: usize, ch: char) -> Option<usize> {
+        let next = &self.states[state].next;
+        next.binary_search_by_key(&ch, |x| x.0)
+            .ok()
+            .map(|i| next[i].1)
+    }
+
+    fn add_edge(&mut self, state: usize, ch: char) -> usize {
+        let target = self.states.len();
+        self.states.push(State::default());
+        let next = &mut self.states[state].next;
+        let i = next.partition_point(|x| x.0 < ch);
+        next.insert(i, (ch, target));
+        target
+    }
+
+    /// Fill in fail links and outputs, breadth first
+    /// so shorter suffixes are done before they are needed
+    fn link(&mut self) {
+        let mut queue = self.states[0]
+            .next
+            .iter()
+            .map(|x| x.1)
+            .collect::<VecDeque<_>>();
+
+        while let Some(state) = queue.pop_front() {
+            for (ch, target) in self.states[state].next.clone() {
+                let fail = self.step(self.states[state].fail, ch);
+                self.states[target].fail = fail;
+                let inherited = self.states[fail].output.clone();
+                self.states[target].output.extend(inherited);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    /// State after reading `ch` in `state`
+    fn step(&self, mut state: usize, ch: char) -> usize {
+        loop {
+            if let Some(x) = self.edge(state, ch) {
+                return x;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.states[state].fail;
+        }
+    }
+
+    /// Length in characters of the longest pattern
+    fn max_len(&self) -> usize {
+        self.lens.iter().copied().max().unwrap_or(0)
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read,
+{
+    /// Find every occurrence of the patterns in `searcher`,
+    /// overlapping ones included, in the order they end.
+    /// Matches ending at the same character come longest first
+    ///
+    /// ---
+    /// Only keeps the positions of as many characters as the longest
+    /// pattern has, so any amount of data can be searched.
+    /// Stops at an invalid sequence the same as `next`
+    pub fn search<'a>(&'a mut self, searcher: &'a Searcher) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        // Start positions of the last characters read
+        let max_len = searcher.max_len();
+        let mut recent = VecDeque::with_capacity(max_len);
+        let mut pending = VecDeque::new();
+
+        std::iter::from_fn(move || loop {
+            if let Some(x) = pending.pop_front() {
+                return Some(x);
+            }
+
+            self.skip_bom();
+            let position = Self::position(self);
+            let ch = self.next()?;
+            if recent.len() == max_len {
+                recent.pop_front();
+            }
+            recent.push_back(position);
+
+            state = searcher.step(state, searcher.fold(ch));
+            let end = Self::position(self).offset;
+            pending.extend(searcher.states[state].output.iter().map(|&pattern| Match {
+                pattern,
+                start: recent[recent.len() - searcher.lens[pattern]],
+                end,
+            }));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Searcher;
+    use crate::string_stream::{DecodeMode, Encoding, StringStream};
+
+    /// Pattern, offset, line and column of each match
+    fn search(data: &[u8], searcher: &Searcher) -> Vec<(usize, u64, usize, usize)> {
+        StringStream::with_capacity(4, data)
+            .search(searcher)
+            .map(|x| (x.pattern, x.start.offset, x.start.line, x.start.column))
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_test() {
+        // Classic example, every pattern is a suffix or prefix of another
+        let searcher = Searcher::new(["he", "she", "his", "hers"]);
+        let matches = StringStream::new("ushers".as_bytes())
+            .search(&searcher)
+            .map(|x| (x.pattern, x.start.offset, x.end))
+            .collect::<Vec<_>>();
+        assert_eq!(matches, [(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+
+        let searcher = Searcher::new(["aa"]);
+        assert_eq!(search(b"aaaa", &searcher).len(), 3);
+    }
+
+    #[test]
+    fn across_buffer_test() {
+        // Every match is cut by the 4 byte buffer somewhere
+        let data = "first line\nsecond needle\r\n\u{2713}needle\u{1F600}needle";
+        let searcher = Searcher::new(["needle", "\u{1F600}n", "line\nsec"]);
+        assert_eq!(
+            search(data.as_bytes(), &searcher),
+            [
+                (2, 6, 1, 7),
+                (0, 18, 2, 8),
+                (0, 29, 3, 2),
+                (1, 35, 3, 8),
+                (0, 39, 3, 9),
+            ]
+        );
+
+        // Same result from a naive search of the whole text
+        let expected = data
+            .match_indices("needle")
+            .map(|(i, _)| i as u64)
+            .collect::<Vec<_>>();
+        let offsets = search(data.as_bytes(), &Searcher::new(["needle"]))
+            .iter()
+            .map(|x| x.1)
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, expected);
+    }
+
+    #[test]
+    fn case_insensitive_test() {
+        let data = b"Error: ERROR error eRRoR \xC9RROR";
+        let searcher = Searcher::ascii_case_insensitive(["error"]);
+        let matches = StringStream::new(&data[..])
+            .with_mode(DecodeMode::Lossy)
+            .search(&searcher)
+            .map(|x| x.start.column)
+            .collect::<Vec<_>>();
+        assert_eq!(matches, [1, 8, 14, 20]);
+
+        // Only ASCII letters fold
+        let searcher = Searcher::ascii_case_insensitive(["\u{e9}T\u{e9}"]);
+        assert_eq!(
+            search("\u{e9}t\u{e9} \u{c9}T\u{c9}".as_bytes(), &searcher).len(),
+            1
+        );
+        assert!(search(b"ERROR", &Searcher::new(["error"])).is_empty());
+    }
+
+    #[test]
+    fn edge_cases_test() {
+        // Empty patterns never match, indices still count them
+        let searcher = Searcher::new(["", "b", "abc", "abcd"]);
+        let matches = search(b"zabcabcd", &searcher);
+        assert_eq!(
+            matches,
+            [
+                (1, 2, 1, 3),
+                (2, 1, 1, 2),
+                (1, 5, 1, 6),
+                (2, 4, 1, 5),
+                (3, 4, 1, 5),
+            ]
+        );
+        assert!(search(b"anything", &Searcher::new(Vec::<String>::new())).is_empty());
+        assert!(search(b"", &Searcher::new(["x"])).is_empty());
+
+        // Same pattern twice reports both
+        let matches = search(b"xx", &Searcher::new(["x", "x"]));
+        assert_eq!(matches.len(), 4);
+    }
+
+    #[test]
+    fn encoding_test() {
+        // Offsets are in bytes of the source encoding
+        let mut data = vec![0xFF, 0xFE];
+        data.extend("ab\ncab".encode_utf16().flat_map(u16::to_le_bytes));
+        let matches = StringStream::with_capacity(4, &data[..])
+            .detect_encoding()
+            .search(&Searcher::new(["ab"]))
+            .map(|x| (x.start.offset, x.end, x.start.line))
+            .collect::<Vec<_>>();
+        assert_eq!(matches, [(2, 6, 1), (10, 14, 2)]);
+
+        let mut stream = StringStream::new(&b"caf\xE9"[..]).with_encoding(Encoding::Latin1);
+        assert_eq!(
+            stream
+                .search(&Searcher::new(["\u{e9}"]))
+                .next()
+                .unwrap()
+                .end,
+            4
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use std::io::{ErrorKind, Write};
+
+use super::{Encoding, LineEnding};
+
+/// Character based writer over a **Write**able type,
+/// the counterpart of `StringStream`
+///
+/// Text is encoded as UTF-8 unless another `Encoding` is chosen.
+/// Writes are passed straight to the destination, wrap it in a
+/// `BufWriter` when writing many small pieces
+pub struct StringSink<W>
+where
+    W: Write,
+{
+    dest: W,
+    encoding: Encoding,
+    ending: LineEnding,
+    /// Write a byte order mark before anything else
+    bom: bool,
+    normalize: bool,
+    /// Last character written was a `\r` that was turned into a line ending
+    after_cr: bool,
+}
+impl<W> StringSink<W>
+where
+    W: Write,
+{
+    pub fn new(x: W) -> Self {
+        Self {
+            dest: x,
+            encoding: Encoding::default(),
+            ending: LineEnding::Lf,
+            bom: false,
+            normalize: false,
+            after_cr: false,
+        }
+    }
+
+    /// Encode text as `encoding`
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Terminator `write_line` ends lines with
+    pub fn with_line_ending(mut self, ending: LineEnding) -> Self {
+        self.ending = ending;
+        self
+    }
+
+    /// Start the output with a byte order mark,
+    /// for encodings that have one
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Replace every `\n`, `\r\n` and lone `\r` in written text
+    /// with the configured line ending
+    pub fn with_normalized_line_endings(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.dest
+    }
+
+    /// Flush and return the destination,
+    /// writing the byte order mark if nothing else was written
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.write_bom()?;
+        self.dest.flush()?;
+        Ok(self.dest)
+    }
+
+    fn write_bom(&mut self) -> std::io::Result<()> {
+        if std::mem::take(&mut self.bom) {
+            if let Some(bom) = self.encoding.bom() {
+                self.dest.write_all(bom)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode and write a single character, no normalisation
+    fn put(&mut self, ch: char) -> std::io::Result<()> {
+        let mut buf = [0; 4];
+        match self.encoding.encode(ch, &mut buf) {
+            Some(bytes) => self.dest.write_all(bytes),
+            None => Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("{ch:?} can't be encoded as {:?}", self.encoding),
+            )),
+        }
+    }
+
+    fn put_ending(&mut self) -> std::io::Result<()> {
+        match self.ending {
+            LineEnding::CrLf => {
+                self.put('\r')?;
+                self.put('\n')
+            }
+            LineEnding::Lf => self.put('\n'),
+            LineEnding::Cr => self.put('\r'),
+            LineEnding::Nel => self.put('\u{85}'),
+            LineEnding::Ls => self.put('\u{2028}'),
+            LineEnding::Ps => self.put('\u{2029}'),
+            LineEnding::Custom(ch) => self.put(ch),
+        }
+    }
+
+    pub fn write_char(&mut self, ch: char) -> std::io::Result<()> {
+        self.write_bom()?;
+        if !self.normalize {
+            return self.put(ch);
+        }
+
+        let after_cr = std::mem::take(&mut self.after_cr);
+        match ch {
+            // Second half of `\r\n`, the ending is already written
+            '\n' if after_cr => Ok(()),
+            '\n' => self.put_ending(),
+            '\r' => {
+                self.after_cr = true;
+                self.put_ending()
+            }
+            _ => self.put(ch),
+        }
+    }
+
+    pub fn write_str(&mut self, s: &str) -> std::io::Result<()> {
+        self.write_bom()?;
+        if self.encoding == Encoding::Utf8 && !self.normalize {
+            return self.dest.write_all(s.as_bytes());
+        }
+
+        s.chars().try_for_each(|x| self.write_char(x))
+    }
+
+    /// Write `line` followed by the line ending
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        self.write_str(line)?;
+        self.after_cr = false;
+        self.put_ending()
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.dest.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::StringSink;
+    use crate::string_stream::{DecodeMode, Encoding, LineConfig, LineEnding, StringStream};
+
+    const ENCODINGS: [Encoding; 3] = [Encoding::Utf8, Encoding::Utf16Le, Encoding::Utf16Be];
+
+    #[test]
+    fn write_test() {
+        let mut sink = StringSink::new(Vec::new());
+        sink.write_char('a').unwrap();
+        sink.write_str("b\u{e9}\r\n").unwrap();
+        sink.write_line("line").unwrap();
+        assert_eq!(sink.finish().unwrap(), "ab\u{e9}\r\nline\n".as_bytes());
+
+        let mut sink = StringSink::new(Vec::new())
+            .with_encoding(Encoding::Utf16Be)
+            .with_line_ending(LineEnding::CrLf)
+            .with_bom(true);
+        sink.write_line("\u{1F600}").unwrap();
+        assert_eq!(
+            sink.finish().unwrap(),
+            [0xFE, 0xFF, 0xD8, 0x3D, 0xDE, 0x00, 0x00, 0x0D, 0x00, 0x0A]
+        );
+    }
+
+    #[test]
+    fn bom_test() {
+        // Written once, even for empty output
+        let sink = StringSink::new(Vec::new()).with_bom(true);
+        assert_eq!(sink.finish().unwrap(), [0xEF, 0xBB, 0xBF]);
+
+        let mut sink = StringSink::new(Vec::new()).with_bom(true);
+        sink.write_str("a").unwrap();
+        sink.write_str("b").unwrap();
+        assert_eq!(sink.finish().unwrap(), b"\xEF\xBB\xBFab");
+
+        // Latin-1 has no mark
+        let mut sink = StringSink::new(Vec::new())
+            .with_encoding(Encoding::Latin1)
+            .with_bom(true);
+        sink.write_str("\u{e9}").unwrap();
+        assert_eq!(sink.finish().unwrap(), [0xE9]);
+    }
+
+    #[test]
+    fn normalize_test() {
+        let text = "unix\nwindows\r\nmac\rmixed\r\r\n\n\rend";
+        for (ending, expected) in [
+            (LineEnding::Lf, "unix\nwindows\nmac\nmixed\n\n\n\nend"),
+            (
+                LineEnding::CrLf,
+                "unix\r\nwindows\r\nmac\r\nmixed\r\n\r\n\r\n\r\nend",
+            ),
+        ] {
+            let mut sink = StringSink::new(Vec::new())
+                .with_line_ending(ending)
+                .with_normalized_line_endings(true);
+            sink.write_str(text).unwrap();
+            assert_eq!(sink.finish().unwrap(), expected.as_bytes());
+
+            // Same output when `\r\n` is split between writes
+            let mut sink = StringSink::new(Vec::new())
+                .with_line_ending(ending)
+                .with_normalized_line_endings(true);
+            text.chars().for_each(|x| sink.write_char(x).unwrap());
+            assert_eq!(sink.finish().unwrap(), expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn unencodable_test() {
+        let mut sink = StringSink::new(Vec::new()).with_encoding(Encoding::Latin1);
+        let error = sink.write_str("caf\u{e9} \u{20AC}").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(sink.get_ref(), b"caf\xE9 ");
+    }
+
+    /// Text with every kind of character the encodings handle differently
+    fn sample() -> String {
+        let mut text = String::from("ascii \u{e9}\u{20AC}\u{FFFD}\u{FEFF}\u{1F600}\u{10FFFF}\0\r");
+        text.extend((0..2000).map(|x| char::from_u32(x * 557 % 0x11000).unwrap_or('?')));
+        text
+    }
+
+    #[test]
+    fn round_trip_chars_test() {
+        let text = sample();
+        for encoding in ENCODINGS {
+            for bom in [false, true] {
+                let mut sink = StringSink::new(Vec::new())
+                    .with_encoding(encoding)
+                    .with_bom(bom);
+                sink.write_str(&text).unwrap();
+                let data = sink.finish().unwrap();
+
+                let stream = StringStream::with_capacity(5, &data[..]);
+                let stream = match bom {
+                    true => stream.detect_encoding(),
+                    false => stream.with_encoding(encoding),
+                };
+                let mut chars = stream.with_mode(DecodeMode::Stop);
+                let decoded = chars.try_chars().collect::<Result<String, _>>().unwrap();
+                assert_eq!(decoded, text, "{encoding:?}, bom {bom}");
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_lines_test() {
+        let lines = ["first", "", "\u{2713} second \u{1F600}", "lone\rcr", " "];
+        for encoding in ENCODINGS {
+            for ending in [LineEnding::Lf, LineEnding::CrLf] {
+                let mut sink = StringSink::new(Vec::new())
+                    .with_encoding(encoding)
+                    .with_line_ending(ending)
+                    .with_bom(true);
+                lines.iter().for_each(|x| sink.write_line(x).unwrap());
+                let data = sink.finish().unwrap();
+
+                let read = StringStream::with_capacity(4, &data[..])
+                    .detect_encoding()
+                    .lines_with_endings()
+                    .collect::<Vec<_>>();
+                let expected = lines
+                    .iter()
+                    .map(|x| (x.to_string(), Some(ending)))
+                    .collect::<Vec<_>>();
+                assert_eq!(read, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_normalized_test() {
+        // Reading with every terminator and writing normalised gives one kind
+        let text = "a\rb\r\nc\nd\u{2028}e";
+        let config = LineConfig {
+            cr: true,
+            unicode: true,
+            ..Default::default()
+        };
+        let mut sink = StringSink::new(Vec::new()).with_line_ending(LineEnding::CrLf);
+        StringStream::new(text.as_bytes())
+            .with_line_config(config)
+            .lines_with_endings()
+            .for_each(|(line, ending)| match ending {
+                Some(_) => sink.write_line(&line).unwrap(),
+                None => sink.write_str(&line).unwrap(),
+            });
+        let data = sink.finish().unwrap();
+        assert_eq!(data, b"a\r\nb\r\nc\r\nd\r\ne");
+
+        let endings = StringStream::new(&data[..])
+            .lines_with_endings()
+            .map(|(_, x)| x)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            endings,
+            [Some(LineEnding::CrLf); 4]
+                .into_iter()
+                .chain([None])
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::io::Read;
+
+use super::{cmp_range, DecodeError, StringStream};
+
+mod tables;
+
+/// How a character is counted by `wc`, ASCII aside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Width {
+    /// Not printable, takes no columns and is not part of a word
+    NonPrinting,
+    /// Printable but takes no columns, like combining marks
+    Zero,
+    /// Takes two columns
+    Double,
+}
+
+/// Printable characters other than ASCII that separate words,
+/// spaces that `iswspace` knows and the no-break ones `wc` adds
+fn is_separator(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{A0}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{2060}' | '\u{3000}'
+    )
+}
+
+/// Line, word, character and byte counts of a text, see `StringStream::stats`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextStats {
+    /// Number of `\n`, a last line without one isn't counted
+    pub lines: u64,
+    pub words: u64,
+    /// Decoded characters, invalid sequences not included
+    pub chars: u64,
+    pub bytes: u64,
+    /// Display width of the widest line, with tabs stopping every 8 columns
+    pub max_line_width: u64,
+    /// Invalid sequences skipped, counted the same as `DecodeMode::Lossy`
+    /// would replace them
+    pub invalid: u64,
+    /// Lines ended by `\n` alone
+    pub lf: u64,
+    /// Lines ended by `\r\n`
+    pub crlf: u64,
+    /// `\r` not followed by `\n`
+    pub cr: u64,
+}
+
+/// Running state while counting
+#[derive(Default)]
+struct Counter {
+    stats: TextStats,
+    in_word: bool,
+    /// Columns in the current line
+    column: u64,
+    after_cr: bool,
+}
+impl Counter {
+    fn end_word(&mut self) {
+        self.stats.words += self.in_word as u64;
+        self.in_word = false;
+    }
+
+    fn end_line(&mut self) {
+        self.stats.max_line_width = self.stats.max_line_width.max(self.column);
+        self.column = 0;
+    }
+
+    fn push(&mut self, ch: char) {
+        self.stats.chars += 1;
+        let after_cr = std::mem::take(&mut self.after_cr);
+        if after_cr && ch != '\n' {
+            self.stats.cr += 1;
+        }
+
+        match ch {
+            '\n' => {
+                self.stats.lines += 1;
+                match after_cr {
+                    true => self.stats.crlf += 1,
+                    false => self.stats.lf += 1,
+                }
+                self.end_line();
+                self.end_word();
+            }
+            '\r' => {
+                self.after_cr = true;
+                self.end_line();
+                self.end_word();
+            }
+            '\x0C' => {
+                self.end_line();
+                self.end_word();
+            }
+            '\t' => {
+                self.column += 8 - self.column % 8;
+                self.end_word();
+            }
+            ' ' => {
+                self.column += 1;
+                self.end_word();
+            }
+            '\x0B' => self.end_word(),
+            '\0'..='\x1F' | '\x7F' => {}
+            _ if ch.is_ascii() => {
+                self.column += 1;
+                self.in_word = true;
+            }
+            _ => {
+                let width = tables::WIDTHS
+                    .binary_search_by(|&(lo, hi, _)| cmp_range(ch, lo, hi))
+                    .map(|i| tables::WIDTHS[i].2);
+                self.column += match width {
+                    Ok(Width::NonPrinting) => return,
+                    Ok(Width::Zero) => 0,
+                    Ok(Width::Double) => 2,
+                    Err(_) => 1,
+                };
+                match is_separator(ch) {
+                    true => self.end_word(),
+                    false => self.in_word = true,
+                }
+            }
+        }
+    }
+
+    fn finish(mut self, bytes: u64) -> TextStats {
+        if self.after_cr {
+            self.stats.cr += 1;
+        }
+        self.end_line();
+        self.end_word();
+        self.stats.bytes = bytes;
+        self.stats
+    }
+}
+
+impl<T> StringStream<T>
+where
+    T: Read,
+{
+    /// Count lines, words, characters and bytes of the rest of the stream
+    /// in one pass, the same way GNU `wc` does in a UTF-8 locale
+    ///
+    /// Invalid sequences are counted in `TextStats::invalid` and
+    /// otherwise skipped, whatever the `DecodeMode`
+    ///
+    /// ---
+    /// **NOTE**: Bytes are those of the source, in any encoding.
+    /// GNU libc decodes sequences above U+10FFFF as characters,
+    /// here they are invalid
+    pub fn stats(&mut self) -> std::io::Result<TextStats> {
+        let start = Self::position(self).offset;
+        let mut counter = Counter::default();
+
+        while let Some(result) = self.try_next() {
+            match result {
+                Ok(ch) => counter.push(ch),
+                Err(DecodeError::InvalidSequence { .. }) => counter.stats.invalid += 1,
+                Err(DecodeError::Io { error, .. }) => return Err(error),
+            }
+        }
+
+        let bytes = Self::position(self).offset - start;
+        Ok(counter.finish(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextStats;
+    use crate::string_stream::{DecodeMode, StringStream};
+
+    fn stats(data: &[u8]) -> TextStats {
+        StringStream::with_capacity(4, data).stats().unwrap()
+    }
+
+    /// Lines, words, chars, bytes and max line width, as `wc -lwmcL` prints them
+    fn wc(data: &[u8]) -> [u64; 5] {
+        let x = stats(data);
+        [x.lines, x.words, x.chars, x.bytes, x.max_line_width]
+    }
+
+    // Expected counts are the output of GNU coreutils 9.1 `wc -lwmcL`
+    // with `LC_ALL=C.UTF-8` on each file
+
+    #[test]
+    fn mixed_test() {
+        let data = include_bytes!("testdata/wc/mixed.txt");
+        assert_eq!(wc(data), [5, 16, 82, 93, 36]);
+
+        let x = stats(data);
+        assert_eq!((x.lf, x.crlf, x.cr, x.invalid), (2, 3, 0, 0));
+    }
+
+    #[test]
+    fn unicode_test() {
+        let data = include_bytes!("testdata/wc/unicode.txt");
+        assert_eq!(wc(data), [3, 14, 96, 110, 33]);
+
+        let x = stats(data);
+        assert_eq!((x.lf, x.crlf, x.cr, x.invalid), (2, 1, 1, 0));
+    }
+
+    #[test]
+    fn invalid_test() {
+        let data = include_bytes!("testdata/wc/invalid.txt");
+        assert_eq!(wc(data), [6, 9, 57, 73, 16]);
+
+        // Same for both modes, only `invalid` counts them
+        let lossy = StringStream::new(&data[..])
+            .with_mode(DecodeMode::Lossy)
+            .stats()
+            .unwrap();
+        assert_eq!(lossy, stats(data));
+        assert_eq!(lossy.invalid, 13);
+        let replaced = String::from_utf8_lossy(data);
+        assert_eq!(replaced.matches('\u{FFFD}').count(), 13);
+    }
+
+    #[test]
+    fn small_test() {
+        assert_eq!(stats(b""), TextStats::default());
+        assert_eq!(wc(b"no newline"), [0, 2, 10, 10, 10]);
+        assert_eq!(wc(b"\n\n"), [2, 0, 2, 2, 0]);
+        assert_eq!(wc(b"a\tb\tc"), [0, 3, 5, 5, 17]);
+
+        let x = stats(b"a\rb\r");
+        assert_eq!((x.lines, x.words, x.cr, x.max_line_width), (0, 2, 2, 1));
+
+        // Counts start where the stream is
+        let mut stream = StringStream::new("skip\nrest of it\n".as_bytes());
+        stream.next_line();
+        assert_eq!(
+            stream.stats().unwrap(),
+            TextStats {
+                lines: 1,
+                words: 3,
+                chars: 11,
+                bytes: 11,
+                max_line_width: 10,
+                lf: 1,
+                ..Default::default()
+            }
+        );
+    }
+}
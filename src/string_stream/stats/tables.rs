@@ -0,0 +1,1182 @@
+// Character classes of the C.UTF-8 locale of GNU libc 2.36, the one GNU
+// `wc` uses, extracted with `iswprint` and `wcwidth`. Code points outside
+// these ranges and above U+007F are printable and one column wide
+
+use super::Width::{self, *};
+
+/// Sorted, non-overlapping ranges of every class but single width
+#[rustfmt::skip]
+pub(super) const WIDTHS: &[(char, char, Width)] = &[
+    ('\u{80}', '\u{9F}', NonPrinting),
+    ('\u{300}', '\u{36F}', Zero),
+    ('\u{378}', '\u{379}', NonPrinting),
+    ('\u{380}', '\u{383}', NonPrinting),
+    ('\u{38B}', '\u{38B}', NonPrinting),
+    ('\u{38D}', '\u{38D}', NonPrinting),
+    ('\u{3A2}', '\u{3A2}', NonPrinting),
+    ('\u{483}', '\u{489}', Zero),
+    ('\u{530}', '\u{530}', NonPrinting),
+    ('\u{557}', '\u{558}', NonPrinting),
+    ('\u{58B}', '\u{58C}', NonPrinting),
+    ('\u{590}', '\u{590}', NonPrinting),
+    ('\u{591}', '\u{5BD}', Zero),
+    ('\u{5BF}', '\u{5BF}', Zero),
+    ('\u{5C1}', '\u{5C2}', Zero),
+    ('\u{5C4}', '\u{5C5}', Zero),
+    ('\u{5C7}', '\u{5C7}', Zero),
+    ('\u{5C8}', '\u{5CF}', NonPrinting),
+    ('\u{5EB}', '\u{5EE}', NonPrinting),
+    ('\u{5F5}', '\u{5FF}', NonPrinting),
+    ('\u{610}', '\u{61A}', Zero),
+    ('\u{61C}', '\u{61C}', Zero),
+    ('\u{64B}', '\u{65F}', Zero),
+    ('\u{670}', '\u{670}', Zero),
+    ('\u{6D6}', '\u{6DC}', Zero),
+    ('\u{6DF}', '\u{6E4}', Zero),
+    ('\u{6E7}', '\u{6E8}', Zero),
+    ('\u{6EA}', '\u{6ED}', Zero),
+    ('\u{70E}', '\u{70E}', NonPrinting),
+    ('\u{711}', '\u{711}', Zero),
+    ('\u{730}', '\u{74A}', Zero),
+    ('\u{74B}', '\u{74C}', NonPrinting),
+    ('\u{7A6}', '\u{7B0}', Zero),
+    ('\u{7B2}', '\u{7BF}', NonPrinting),
+    ('\u{7EB}', '\u{7F3}', Zero),
+    ('\u{7FB}', '\u{7FC}', NonPrinting),
+    ('\u{7FD}', '\u{7FD}', Zero),
+    ('\u{816}', '\u{819}', Zero),
+    ('\u{81B}', '\u{823}', Zero),
+    ('\u{825}', '\u{827}', Zero),
+    ('\u{829}', '\u{82D}', Zero),
+    ('\u{82E}', '\u{82F}', NonPrinting),
+    ('\u{83F}', '\u{83F}', NonPrinting),
+    ('\u{859}', '\u{85B}', Zero),
+    ('\u{85C}', '\u{85D}', NonPrinting),
+    ('\u{85F}', '\u{85F}', NonPrinting),
+    ('\u{86B}', '\u{86F}', NonPrinting),
+    ('\u{88F}', '\u{88F}', NonPrinting),
+    ('\u{892}', '\u{897}', NonPrinting),
+    ('\u{898}', '\u{89F}', Zero),
+    ('\u{8CA}', '\u{8E1}', Zero),
+    ('\u{8E3}', '\u{902}', Zero),
+    ('\u{93A}', '\u{93A}', Zero),
+    ('\u{93C}', '\u{93C}', Zero),
+    ('\u{941}', '\u{948}', Zero),
+    ('\u{94D}', '\u{94D}', Zero),
+    ('\u{951}', '\u{957}', Zero),
+    ('\u{962}', '\u{963}', Zero),
+    ('\u{981}', '\u{981}', Zero),
+    ('\u{984}', '\u{984}', NonPrinting),
+    ('\u{98D}', '\u{98E}', NonPrinting),
+    ('\u{991}', '\u{992}', NonPrinting),
+    ('\u{9A9}', '\u{9A9}', NonPrinting),
+    ('\u{9B1}', '\u{9B1}', NonPrinting),
+    ('\u{9B3}', '\u{9B5}', NonPrinting),
+    ('\u{9BA}', '\u{9BB}', NonPrinting),
+    ('\u{9BC}', '\u{9BC}', Zero),
+    ('\u{9C1}', '\u{9C4}', Zero),
+    ('\u{9C5}', '\u{9C6}', NonPrinting),
+    ('\u{9C9}', '\u{9CA}', NonPrinting),
+    ('\u{9CD}', '\u{9CD}', Zero),
+    ('\u{9CF}', '\u{9D6}', NonPrinting),
+    ('\u{9D8}', '\u{9DB}', NonPrinting),
+    ('\u{9DE}', '\u{9DE}', NonPrinting),
+    ('\u{9E2}', '\u{9E3}', Zero),
+    ('\u{9E4}', '\u{9E5}', NonPrinting),
+    ('\u{9FE}', '\u{9FE}', Zero),
+    ('\u{9FF}', '\u{A00}', NonPrinting),
+    ('\u{A01}', '\u{A02}', Zero),
+    ('\u{A04}', '\u{A04}', NonPrinting),
+    ('\u{A0B}', '\u{A0E}', NonPrinting),
+    ('\u{A11}', '\u{A12}', NonPrinting),
+    ('\u{A29}', '\u{A29}', NonPrinting),
+    ('\u{A31}', '\u{A31}', NonPrinting),
+    ('\u{A34}', '\u{A34}', NonPrinting),
+    ('\u{A37}', '\u{A37}', NonPrinting),
+    ('\u{A3A}', '\u{A3B}', NonPrinting),
+    ('\u{A3C}', '\u{A3C}', Zero),
+    ('\u{A3D}', '\u{A3D}', NonPrinting),
+    ('\u{A41}', '\u{A42}', Zero),
+    ('\u{A43}', '\u{A46}', NonPrinting),
+    ('\u{A47}', '\u{A48}', Zero),
+    ('\u{A49}', '\u{A4A}', NonPrinting),
+    ('\u{A4B}', '\u{A4D}', Zero),
+    ('\u{A4E}', '\u{A50}', NonPrinting),
+    ('\u{A51}', '\u{A51}', Zero),
+    ('\u{A52}', '\u{A58}', NonPrinting),
+    ('\u{A5D}', '\u{A5D}', NonPrinting),
+    ('\u{A5F}', '\u{A65}', NonPrinting),
+    ('\u{A70}', '\u{A71}', Zero),
+    ('\u{A75}', '\u{A75}', Zero),
+    ('\u{A77}', '\u{A80}', NonPrinting),
+    ('\u{A81}', '\u{A82}', Zero),
+    ('\u{A84}', '\u{A84}', NonPrinting),
+    ('\u{A8E}', '\u{A8E}', NonPrinting),
+    ('\u{A92}', '\u{A92}', NonPrinting),
+    ('\u{AA9}', '\u{AA9}', NonPrinting),
+    ('\u{AB1}', '\u{AB1}', NonPrinting),
+    ('\u{AB4}', '\u{AB4}', NonPrinting),
+    ('\u{ABA}', '\u{ABB}', NonPrinting),
+    ('\u{ABC}', '\u{ABC}', Zero),
+    ('\u{AC1}', '\u{AC5}', Zero),
+    ('\u{AC6}', '\u{AC6}', NonPrinting),
+    ('\u{AC7}', '\u{AC8}', Zero),
+    ('\u{ACA}', '\u{ACA}', NonPrinting),
+    ('\u{ACD}', '\u{ACD}', Zero),
+    ('\u{ACE}', '\u{ACF}', NonPrinting),
+    ('\u{AD1}', '\u{ADF}', NonPrinting),
+    ('\u{AE2}', '\u{AE3}', Zero),
+    ('\u{AE4}', '\u{AE5}', NonPrinting),
+    ('\u{AF2}', '\u{AF8}', NonPrinting),
+    ('\u{AFA}', '\u{AFF}', Zero),
+    ('\u{B00}', '\u{B00}', NonPrinting),
+    ('\u{B01}', '\u{B01}', Zero),
+    ('\u{B04}', '\u{B04}', NonPrinting),
+    ('\u{B0D}', '\u{B0E}', NonPrinting),
+    ('\u{B11}', '\u{B12}', NonPrinting),
+    ('\u{B29}', '\u{B29}', NonPrinting),
+    ('\u{B31}', '\u{B31}', NonPrinting),
+    ('\u{B34}', '\u{B34}', NonPrinting),
+    ('\u{B3A}', '\u{B3B}', NonPrinting),
+    ('\u{B3C}', '\u{B3C}', Zero),
+    ('\u{B3F}', '\u{B3F}', Zero),
+    ('\u{B41}', '\u{B44}', Zero),
+    ('\u{B45}', '\u{B46}', NonPrinting),
+    ('\u{B49}', '\u{B4A}', NonPrinting),
+    ('\u{B4D}', '\u{B4D}', Zero),
+    ('\u{B4E}', '\u{B54}', NonPrinting),
+    ('\u{B55}', '\u{B56}', Zero),
+    ('\u{B58}', '\u{B5B}', NonPrinting),
+    ('\u{B5E}', '\u{B5E}', NonPrinting),
+    ('\u{B62}', '\u{B63}', Zero),
+    ('\u{B64}', '\u{B65}', NonPrinting),
+    ('\u{B78}', '\u{B81}', NonPrinting),
+    ('\u{B82}', '\u{B82}', Zero),
+    ('\u{B84}', '\u{B84}', NonPrinting),
+    ('\u{B8B}', '\u{B8D}', NonPrinting),
+    ('\u{B91}', '\u{B91}', NonPrinting),
+    ('\u{B96}', '\u{B98}', NonPrinting),
+    ('\u{B9B}', '\u{B9B}', NonPrinting),
+    ('\u{B9D}', '\u{B9D}', NonPrinting),
+    ('\u{BA0}', '\u{BA2}', NonPrinting),
+    ('\u{BA5}', '\u{BA7}', NonPrinting),
+    ('\u{BAB}', '\u{BAD}', NonPrinting),
+    ('\u{BBA}', '\u{BBD}', NonPrinting),
+    ('\u{BC0}', '\u{BC0}', Zero),
+    ('\u{BC3}', '\u{BC5}', NonPrinting),
+    ('\u{BC9}', '\u{BC9}', NonPrinting),
+    ('\u{BCD}', '\u{BCD}', Zero),
+    ('\u{BCE}', '\u{BCF}', NonPrinting),
+    ('\u{BD1}', '\u{BD6}', NonPrinting),
+    ('\u{BD8}', '\u{BE5}', NonPrinting),
+    ('\u{BFB}', '\u{BFF}', NonPrinting),
+    ('\u{C00}', '\u{C00}', Zero),
+    ('\u{C04}', '\u{C04}', Zero),
+    ('\u{C0D}', '\u{C0D}', NonPrinting),
+    ('\u{C11}', '\u{C11}', NonPrinting),
+    ('\u{C29}', '\u{C29}', NonPrinting),
+    ('\u{C3A}', '\u{C3B}', NonPrinting),
+    ('\u{C3C}', '\u{C3C}', Zero),
+    ('\u{C3E}', '\u{C40}', Zero),
+    ('\u{C45}', '\u{C45}', NonPrinting),
+    ('\u{C46}', '\u{C48}', Zero),
+    ('\u{C49}', '\u{C49}', NonPrinting),
+    ('\u{C4A}', '\u{C4D}', Zero),
+    ('\u{C4E}', '\u{C54}', NonPrinting),
+    ('\u{C55}', '\u{C56}', Zero),
+    ('\u{C57}', '\u{C57}', NonPrinting),
+    ('\u{C5B}', '\u{C5C}', NonPrinting),
+    ('\u{C5E}', '\u{C5F}', NonPrinting),
+    ('\u{C62}', '\u{C63}', Zero),
+    ('\u{C64}', '\u{C65}', NonPrinting),
+    ('\u{C70}', '\u{C76}', NonPrinting),
+    ('\u{C81}', '\u{C81}', Zero),
+    ('\u{C8D}', '\u{C8D}', NonPrinting),
+    ('\u{C91}', '\u{C91}', NonPrinting),
+    ('\u{CA9}', '\u{CA9}', NonPrinting),
+    ('\u{CB4}', '\u{CB4}', NonPrinting),
+    ('\u{CBA}', '\u{CBB}', NonPrinting),
+    ('\u{CBC}', '\u{CBC}', Zero),
+    ('\u{CBF}', '\u{CBF}', Zero),
+    ('\u{CC5}', '\u{CC5}', NonPrinting),
+    ('\u{CC6}', '\u{CC6}', Zero),
+    ('\u{CC9}', '\u{CC9}', NonPrinting),
+    ('\u{CCC}', '\u{CCD}', Zero),
+    ('\u{CCE}', '\u{CD4}', NonPrinting),
+    ('\u{CD7}', '\u{CDC}', NonPrinting),
+    ('\u{CDF}', '\u{CDF}', NonPrinting),
+    ('\u{CE2}', '\u{CE3}', Zero),
+    ('\u{CE4}', '\u{CE5}', NonPrinting),
+    ('\u{CF0}', '\u{CF0}', NonPrinting),
+    ('\u{CF3}', '\u{CFF}', NonPrinting),
+    ('\u{D00}', '\u{D01}', Zero),
+    ('\u{D0D}', '\u{D0D}', NonPrinting),
+    ('\u{D11}', '\u{D11}', NonPrinting),
+    ('\u{D3B}', '\u{D3C}', Zero),
+    ('\u{D41}', '\u{D44}', Zero),
+    ('\u{D45}', '\u{D45}', NonPrinting),
+    ('\u{D49}', '\u{D49}', NonPrinting),
+    ('\u{D4D}', '\u{D4D}', Zero),
+    ('\u{D50}', '\u{D53}', NonPrinting),
+    ('\u{D62}', '\u{D63}', Zero),
+    ('\u{D64}', '\u{D65}', NonPrinting),
+    ('\u{D80}', '\u{D80}', NonPrinting),
+    ('\u{D81}', '\u{D81}', Zero),
+    ('\u{D84}', '\u{D84}', NonPrinting),
+    ('\u{D97}', '\u{D99}', NonPrinting),
+    ('\u{DB2}', '\u{DB2}', NonPrinting),
+    ('\u{DBC}', '\u{DBC}', NonPrinting),
+    ('\u{DBE}', '\u{DBF}', NonPrinting),
+    ('\u{DC7}', '\u{DC9}', NonPrinting),
+    ('\u{DCA}', '\u{DCA}', Zero),
+    ('\u{DCB}', '\u{DCE}', NonPrinting),
+    ('\u{DD2}', '\u{DD4}', Zero),
+    ('\u{DD5}', '\u{DD5}', NonPrinting),
+    ('\u{DD6}', '\u{DD6}', Zero),
+    ('\u{DD7}', '\u{DD7}', NonPrinting),
+    ('\u{DE0}', '\u{DE5}', NonPrinting),
+    ('\u{DF0}', '\u{DF1}', NonPrinting),
+    ('\u{DF5}', '\u{E00}', NonPrinting),
+    ('\u{E31}', '\u{E31}', Zero),
+    ('\u{E34}', '\u{E3A}', Zero),
+    ('\u{E3B}', '\u{E3E}', NonPrinting),
+    ('\u{E47}', '\u{E4E}', Zero),
+    ('\u{E5C}', '\u{E80}', NonPrinting),
+    ('\u{E83}', '\u{E83}', NonPrinting),
+    ('\u{E85}', '\u{E85}', NonPrinting),
+    ('\u{E8B}', '\u{E8B}', NonPrinting),
+    ('\u{EA4}', '\u{EA4}', NonPrinting),
+    ('\u{EA6}', '\u{EA6}', NonPrinting),
+    ('\u{EB1}', '\u{EB1}', Zero),
+    ('\u{EB4}', '\u{EBC}', Zero),
+    ('\u{EBE}', '\u{EBF}', NonPrinting),
+    ('\u{EC5}', '\u{EC5}', NonPrinting),
+    ('\u{EC7}', '\u{EC7}', NonPrinting),
+    ('\u{EC8}', '\u{ECD}', Zero),
+    ('\u{ECE}', '\u{ECF}', NonPrinting),
+    ('\u{EDA}', '\u{EDB}', NonPrinting),
+    ('\u{EE0}', '\u{EFF}', NonPrinting),
+    ('\u{F18}', '\u{F19}', Zero),
+    ('\u{F35}', '\u{F35}', Zero),
+    ('\u{F37}', '\u{F37}', Zero),
+    ('\u{F39}', '\u{F39}', Zero),
+    ('\u{F48}', '\u{F48}', NonPrinting),
+    ('\u{F6D}', '\u{F70}', NonPrinting),
+    ('\u{F71}', '\u{F7E}', Zero),
+    ('\u{F80}', '\u{F84}', Zero),
+    ('\u{F86}', '\u{F87}', Zero),
+    ('\u{F8D}', '\u{F97}', Zero),
+    ('\u{F98}', '\u{F98}', NonPrinting),
+    ('\u{F99}', '\u{FBC}', Zero),
+    ('\u{FBD}', '\u{FBD}', NonPrinting),
+    ('\u{FC6}', '\u{FC6}', Zero),
+    ('\u{FCD}', '\u{FCD}', NonPrinting),
+    ('\u{FDB}', '\u{FFF}', NonPrinting),
+    ('\u{102D}', '\u{1030}', Zero),
+    ('\u{1032}', '\u{1037}', Zero),
+    ('\u{1039}', '\u{103A}', Zero),
+    ('\u{103D}', '\u{103E}', Zero),
+    ('\u{1058}', '\u{1059}', Zero),
+    ('\u{105E}', '\u{1060}', Zero),
+    ('\u{1071}', '\u{1074}', Zero),
+    ('\u{1082}', '\u{1082}', Zero),
+    ('\u{1085}', '\u{1086}', Zero),
+    ('\u{108D}', '\u{108D}', Zero),
+    ('\u{109D}', '\u{109D}', Zero),
+    ('\u{10C6}', '\u{10C6}', NonPrinting),
+    ('\u{10C8}', '\u{10CC}', NonPrinting),
+    ('\u{10CE}', '\u{10CF}', NonPrinting),
+    ('\u{1100}', '\u{115F}', Double),
+    ('\u{1160}', '\u{11FF}', Zero),
+    ('\u{1249}', '\u{1249}', NonPrinting),
+    ('\u{124E}', '\u{124F}', NonPrinting),
+    ('\u{1257}', '\u{1257}', NonPrinting),
+    ('\u{1259}', '\u{1259}', NonPrinting),
+    ('\u{125E}', '\u{125F}', NonPrinting),
+    ('\u{1289}', '\u{1289}', NonPrinting),
+    ('\u{128E}', '\u{128F}', NonPrinting),
+    ('\u{12B1}', '\u{12B1}', NonPrinting),
+    ('\u{12B6}', '\u{12B7}', NonPrinting),
+    ('\u{12BF}', '\u{12BF}', NonPrinting),
+    ('\u{12C1}', '\u{12C1}', NonPrinting),
+    ('\u{12C6}', '\u{12C7}', NonPrinting),
+    ('\u{12D7}', '\u{12D7}', NonPrinting),
+    ('\u{1311}', '\u{1311}', NonPrinting),
+    ('\u{1316}', '\u{1317}', NonPrinting),
+    ('\u{135B}', '\u{135C}', NonPrinting),
+    ('\u{135D}', '\u{135F}', Zero),
+    ('\u{137D}', '\u{137F}', NonPrinting),
+    ('\u{139A}', '\u{139F}', NonPrinting),
+    ('\u{13F6}', '\u{13F7}', NonPrinting),
+    ('\u{13FE}', '\u{13FF}', NonPrinting),
+    ('\u{169D}', '\u{169F}', NonPrinting),
+    ('\u{16F9}', '\u{16FF}', NonPrinting),
+    ('\u{1712}', '\u{1714}', Zero),
+    ('\u{1716}', '\u{171E}', NonPrinting),
+    ('\u{1732}', '\u{1733}', Zero),
+    ('\u{1737}', '\u{173F}', NonPrinting),
+    ('\u{1752}', '\u{1753}', Zero),
+    ('\u{1754}', '\u{175F}', NonPrinting),
+    ('\u{176D}', '\u{176D}', NonPrinting),
+    ('\u{1771}', '\u{1771}', NonPrinting),
+    ('\u{1772}', '\u{1773}', Zero),
+    ('\u{1774}', '\u{177F}', NonPrinting),
+    ('\u{17B4}', '\u{17B5}', Zero),
+    ('\u{17B7}', '\u{17BD}', Zero),
+    ('\u{17C6}', '\u{17C6}', Zero),
+    ('\u{17C9}', '\u{17D3}', Zero),
+    ('\u{17DD}', '\u{17DD}', Zero),
+    ('\u{17DE}', '\u{17DF}', NonPrinting),
+    ('\u{17EA}', '\u{17EF}', NonPrinting),
+    ('\u{17FA}', '\u{17FF}', NonPrinting),
+    ('\u{180B}', '\u{180F}', Zero),
+    ('\u{181A}', '\u{181F}', NonPrinting),
+    ('\u{1879}', '\u{187F}', NonPrinting),
+    ('\u{1885}', '\u{1886}', Zero),
+    ('\u{18A9}', '\u{18A9}', Zero),
+    ('\u{18AB}', '\u{18AF}', NonPrinting),
+    ('\u{18F6}', '\u{18FF}', NonPrinting),
+    ('\u{191F}', '\u{191F}', NonPrinting),
+    ('\u{1920}', '\u{1922}', Zero),
+    ('\u{1927}', '\u{1928}', Zero),
+    ('\u{192C}', '\u{192F}', NonPrinting),
+    ('\u{1932}', '\u{1932}', Zero),
+    ('\u{1939}', '\u{193B}', Zero),
+    ('\u{193C}', '\u{193F}', NonPrinting),
+    ('\u{1941}', '\u{1943}', NonPrinting),
+    ('\u{196E}', '\u{196F}', NonPrinting),
+    ('\u{1975}', '\u{197F}', NonPrinting),
+    ('\u{19AC}', '\u{19AF}', NonPrinting),
+    ('\u{19CA}', '\u{19CF}', NonPrinting),
+    ('\u{19DB}', '\u{19DD}', NonPrinting),
+    ('\u{1A17}', '\u{1A18}', Zero),
+    ('\u{1A1B}', '\u{1A1B}', Zero),
+    ('\u{1A1C}', '\u{1A1D}', NonPrinting),
+    ('\u{1A56}', '\u{1A56}', Zero),
+    ('\u{1A58}', '\u{1A5E}', Zero),
+    ('\u{1A5F}', '\u{1A5F}', NonPrinting),
+    ('\u{1A60}', '\u{1A60}', Zero),
+    ('\u{1A62}', '\u{1A62}', Zero),
+    ('\u{1A65}', '\u{1A6C}', Zero),
+    ('\u{1A73}', '\u{1A7C}', Zero),
+    ('\u{1A7D}', '\u{1A7E}', NonPrinting),
+    ('\u{1A7F}', '\u{1A7F}', Zero),
+    ('\u{1A8A}', '\u{1A8F}', NonPrinting),
+    ('\u{1A9A}', '\u{1A9F}', NonPrinting),
+    ('\u{1AAE}', '\u{1AAF}', NonPrinting),
+    ('\u{1AB0}', '\u{1ACE}', Zero),
+    ('\u{1ACF}', '\u{1AFF}', NonPrinting),
+    ('\u{1B00}', '\u{1B03}', Zero),
+    ('\u{1B34}', '\u{1B34}', Zero),
+    ('\u{1B36}', '\u{1B3A}', Zero),
+    ('\u{1B3C}', '\u{1B3C}', Zero),
+    ('\u{1B42}', '\u{1B42}', Zero),
+    ('\u{1B4D}', '\u{1B4F}', NonPrinting),
+    ('\u{1B6B}', '\u{1B73}', Zero),
+    ('\u{1B7F}', '\u{1B7F}', NonPrinting),
+    ('\u{1B80}', '\u{1B81}', Zero),
+    ('\u{1BA2}', '\u{1BA5}', Zero),
+    ('\u{1BA8}', '\u{1BA9}', Zero),
+    ('\u{1BAB}', '\u{1BAD}', Zero),
+    ('\u{1BE6}', '\u{1BE6}', Zero),
+    ('\u{1BE8}', '\u{1BE9}', Zero),
+    ('\u{1BED}', '\u{1BED}', Zero),
+    ('\u{1BEF}', '\u{1BF1}', Zero),
+    ('\u{1BF4}', '\u{1BFB}', NonPrinting),
+    ('\u{1C2C}', '\u{1C33}', Zero),
+    ('\u{1C36}', '\u{1C37}', Zero),
+    ('\u{1C38}', '\u{1C3A}', NonPrinting),
+    ('\u{1C4A}', '\u{1C4C}', NonPrinting),
+    ('\u{1C89}', '\u{1C8F}', NonPrinting),
+    ('\u{1CBB}', '\u{1CBC}', NonPrinting),
+    ('\u{1CC8}', '\u{1CCF}', NonPrinting),
+    ('\u{1CD0}', '\u{1CD2}', Zero),
+    ('\u{1CD4}', '\u{1CE0}', Zero),
+    ('\u{1CE2}', '\u{1CE8}', Zero),
+    ('\u{1CED}', '\u{1CED}', Zero),
+    ('\u{1CF4}', '\u{1CF4}', Zero),
+    ('\u{1CF8}', '\u{1CF9}', Zero),
+    ('\u{1CFB}', '\u{1CFF}', NonPrinting),
+    ('\u{1DC0}', '\u{1DFF}', Zero),
+    ('\u{1F16}', '\u{1F17}', NonPrinting),
+    ('\u{1F1E}', '\u{1F1F}', NonPrinting),
+    ('\u{1F46}', '\u{1F47}', NonPrinting),
+    ('\u{1F4E}', '\u{1F4F}', NonPrinting),
+    ('\u{1F58}', '\u{1F58}', NonPrinting),
+    ('\u{1F5A}', '\u{1F5A}', NonPrinting),
+    ('\u{1F5C}', '\u{1F5C}', NonPrinting),
+    ('\u{1F5E}', '\u{1F5E}', NonPrinting),
+    ('\u{1F7E}', '\u{1F7F}', NonPrinting),
+    ('\u{1FB5}', '\u{1FB5}', NonPrinting),
+    ('\u{1FC5}', '\u{1FC5}', NonPrinting),
+    ('\u{1FD4}', '\u{1FD5}', NonPrinting),
+    ('\u{1FDC}', '\u{1FDC}', NonPrinting),
+    ('\u{1FF0}', '\u{1FF1}', NonPrinting),
+    ('\u{1FF5}', '\u{1FF5}', NonPrinting),
+    ('\u{1FFF}', '\u{1FFF}', NonPrinting),
+    ('\u{200B}', '\u{200F}', Zero),
+    ('\u{2028}', '\u{2029}', NonPrinting),
+    ('\u{202A}', '\u{202E}', Zero),
+    ('\u{2060}', '\u{2064}', Zero),
+    ('\u{2065}', '\u{2065}', NonPrinting),
+    ('\u{2066}', '\u{206F}', Zero),
+    ('\u{2072}', '\u{2073}', NonPrinting),
+    ('\u{208F}', '\u{208F}', NonPrinting),
+    ('\u{209D}', '\u{209F}', NonPrinting),
+    ('\u{20C1}', '\u{20CF}', NonPrinting),
+    ('\u{20D0}', '\u{20F0}', Zero),
+    ('\u{20F1}', '\u{20FF}', NonPrinting),
+    ('\u{218C}', '\u{218F}', NonPrinting),
+    ('\u{231A}', '\u{231B}', Double),
+    ('\u{2329}', '\u{232A}', Double),
+    ('\u{23E9}', '\u{23EC}', Double),
+    ('\u{23F0}', '\u{23F0}', Double),
+    ('\u{23F3}', '\u{23F3}', Double),
+    ('\u{2427}', '\u{243F}', NonPrinting),
+    ('\u{244B}', '\u{245F}', NonPrinting),
+    ('\u{25FD}', '\u{25FE}', Double),
+    ('\u{2614}', '\u{2615}', Double),
+    ('\u{2648}', '\u{2653}', Double),
+    ('\u{267F}', '\u{267F}', Double),
+    ('\u{2693}', '\u{2693}', Double),
+    ('\u{26A1}', '\u{26A1}', Double),
+    ('\u{26AA}', '\u{26AB}', Double),
+    ('\u{26BD}', '\u{26BE}', Double),
+    ('\u{26C4}', '\u{26C5}', Double),
+    ('\u{26CE}', '\u{26CE}', Double),
+    ('\u{26D4}', '\u{26D4}', Double),
+    ('\u{26EA}', '\u{26EA}', Double),
+    ('\u{26F2}', '\u{26F3}', Double),
+    ('\u{26F5}', '\u{26F5}', Double),
+    ('\u{26FA}', '\u{26FA}', Double),
+    ('\u{26FD}', '\u{26FD}', Double),
+    ('\u{2705}', '\u{2705}', Double),
+    ('\u{270A}', '\u{270B}', Double),
+    ('\u{2728}', '\u{2728}', Double),
+    ('\u{274C}', '\u{274C}', Double),
+    ('\u{274E}', '\u{274E}', Double),
+    ('\u{2753}', '\u{2755}', Double),
+    ('\u{2757}', '\u{2757}', Double),
+    ('\u{2795}', '\u{2797}', Double),
+    ('\u{27B0}', '\u{27B0}', Double),
+    ('\u{27BF}', '\u{27BF}', Double),
+    ('\u{2B1B}', '\u{2B1C}', Double),
+    ('\u{2B50}', '\u{2B50}', Double),
+    ('\u{2B55}', '\u{2B55}', Double),
+    ('\u{2B74}', '\u{2B75}', NonPrinting),
+    ('\u{2B96}', '\u{2B96}', NonPrinting),
+    ('\u{2CEF}', '\u{2CF1}', Zero),
+    ('\u{2CF4}', '\u{2CF8}', NonPrinting),
+    ('\u{2D26}', '\u{2D26}', NonPrinting),
+    ('\u{2D28}', '\u{2D2C}', NonPrinting),
+    ('\u{2D2E}', '\u{2D2F}', NonPrinting),
+    ('\u{2D68}', '\u{2D6E}', NonPrinting),
+    ('\u{2D71}', '\u{2D7E}', NonPrinting),
+    ('\u{2D7F}', '\u{2D7F}', Zero),
+    ('\u{2D97}', '\u{2D9F}', NonPrinting),
+    ('\u{2DA7}', '\u{2DA7}', NonPrinting),
+    ('\u{2DAF}', '\u{2DAF}', NonPrinting),
+    ('\u{2DB7}', '\u{2DB7}', NonPrinting),
+    ('\u{2DBF}', '\u{2DBF}', NonPrinting),
+    ('\u{2DC7}', '\u{2DC7}', NonPrinting),
+    ('\u{2DCF}', '\u{2DCF}', NonPrinting),
+    ('\u{2DD7}', '\u{2DD7}', NonPrinting),
+    ('\u{2DDF}', '\u{2DDF}', NonPrinting),
+    ('\u{2DE0}', '\u{2DFF}', Zero),
+    ('\u{2E5E}', '\u{2E7F}', NonPrinting),
+    ('\u{2E80}', '\u{2E99}', Double),
+    ('\u{2E9A}', '\u{2E9A}', NonPrinting),
+    ('\u{2E9B}', '\u{2EF3}', Double),
+    ('\u{2EF4}', '\u{2EFF}', NonPrinting),
+    ('\u{2F00}', '\u{2FD5}', Double),
+    ('\u{2FD6}', '\u{2FEF}', NonPrinting),
+    ('\u{2FF0}', '\u{2FFB}', Double),
+    ('\u{2FFC}', '\u{2FFF}', NonPrinting),
+    ('\u{3000}', '\u{3029}', Double),
+    ('\u{302A}', '\u{302D}', Zero),
+    ('\u{302E}', '\u{303E}', Double),
+    ('\u{3040}', '\u{3040}', NonPrinting),
+    ('\u{3041}', '\u{3096}', Double),
+    ('\u{3097}', '\u{3098}', NonPrinting),
+    ('\u{3099}', '\u{309A}', Zero),
+    ('\u{309B}', '\u{30FF}', Double),
+    ('\u{3100}', '\u{3104}', NonPrinting),
+    ('\u{3105}', '\u{312F}', Double),
+    ('\u{3130}', '\u{3130}', NonPrinting),
+    ('\u{3131}', '\u{318E}', Double),
+    ('\u{318F}', '\u{318F}', NonPrinting),
+    ('\u{3190}', '\u{31E3}', Double),
+    ('\u{31E4}', '\u{31EF}', NonPrinting),
+    ('\u{31F0}', '\u{321E}', Double),
+    ('\u{321F}', '\u{321F}', NonPrinting),
+    ('\u{3220}', '\u{A48C}', Double),
+    ('\u{A48D}', '\u{A48F}', NonPrinting),
+    ('\u{A490}', '\u{A4C6}', Double),
+    ('\u{A4C7}', '\u{A4CF}', NonPrinting),
+    ('\u{A62C}', '\u{A63F}', NonPrinting),
+    ('\u{A66F}', '\u{A672}', Zero),
+    ('\u{A674}', '\u{A67D}', Zero),
+    ('\u{A69E}', '\u{A69F}', Zero),
+    ('\u{A6F0}', '\u{A6F1}', Zero),
+    ('\u{A6F8}', '\u{A6FF}', NonPrinting),
+    ('\u{A7CB}', '\u{A7CF}', NonPrinting),
+    ('\u{A7D2}', '\u{A7D2}', NonPrinting),
+    ('\u{A7D4}', '\u{A7D4}', NonPrinting),
+    ('\u{A7DA}', '\u{A7F1}', NonPrinting),
+    ('\u{A802}', '\u{A802}', Zero),
+    ('\u{A806}', '\u{A806}', Zero),
+    ('\u{A80B}', '\u{A80B}', Zero),
+    ('\u{A825}', '\u{A826}', Zero),
+    ('\u{A82C}', '\u{A82C}', Zero),
+    ('\u{A82D}', '\u{A82F}', NonPrinting),
+    ('\u{A83A}', '\u{A83F}', NonPrinting),
+    ('\u{A878}', '\u{A87F}', NonPrinting),
+    ('\u{A8C4}', '\u{A8C5}', Zero),
+    ('\u{A8C6}', '\u{A8CD}', NonPrinting),
+    ('\u{A8DA}', '\u{A8DF}', NonPrinting),
+    ('\u{A8E0}', '\u{A8F1}', Zero),
+    ('\u{A8FF}', '\u{A8FF}', Zero),
+    ('\u{A926}', '\u{A92D}', Zero),
+    ('\u{A947}', '\u{A951}', Zero),
+    ('\u{A954}', '\u{A95E}', NonPrinting),
+    ('\u{A960}', '\u{A97C}', Double),
+    ('\u{A97D}', '\u{A97F}', NonPrinting),
+    ('\u{A980}', '\u{A982}', Zero),
+    ('\u{A9B3}', '\u{A9B3}', Zero),
+    ('\u{A9B6}', '\u{A9B9}', Zero),
+    ('\u{A9BC}', '\u{A9BD}', Zero),
+    ('\u{A9CE}', '\u{A9CE}', NonPrinting),
+    ('\u{A9DA}', '\u{A9DD}', NonPrinting),
+    ('\u{A9E5}', '\u{A9E5}', Zero),
+    ('\u{A9FF}', '\u{A9FF}', NonPrinting),
+    ('\u{AA29}', '\u{AA2E}', Zero),
+    ('\u{AA31}', '\u{AA32}', Zero),
+    ('\u{AA35}', '\u{AA36}', Zero),
+    ('\u{AA37}', '\u{AA3F}', NonPrinting),
+    ('\u{AA43}', '\u{AA43}', Zero),
+    ('\u{AA4C}', '\u{AA4C}', Zero),
+    ('\u{AA4E}', '\u{AA4F}', NonPrinting),
+    ('\u{AA5A}', '\u{AA5B}', NonPrinting),
+    ('\u{AA7C}', '\u{AA7C}', Zero),
+    ('\u{AAB0}', '\u{AAB0}', Zero),
+    ('\u{AAB2}', '\u{AAB4}', Zero),
+    ('\u{AAB7}', '\u{AAB8}', Zero),
+    ('\u{AABE}', '\u{AABF}', Zero),
+    ('\u{AAC1}', '\u{AAC1}', Zero),
+    ('\u{AAC3}', '\u{AADA}', NonPrinting),
+    ('\u{AAEC}', '\u{AAED}', Zero),
+    ('\u{AAF6}', '\u{AAF6}', Zero),
+    ('\u{AAF7}', '\u{AB00}', NonPrinting),
+    ('\u{AB07}', '\u{AB08}', NonPrinting),
+    ('\u{AB0F}', '\u{AB10}', NonPrinting),
+    ('\u{AB17}', '\u{AB1F}', NonPrinting),
+    ('\u{AB27}', '\u{AB27}', NonPrinting),
+    ('\u{AB2F}', '\u{AB2F}', NonPrinting),
+    ('\u{AB6C}', '\u{AB6F}', NonPrinting),
+    ('\u{ABE5}', '\u{ABE5}', Zero),
+    ('\u{ABE8}', '\u{ABE8}', Zero),
+    ('\u{ABED}', '\u{ABED}', Zero),
+    ('\u{ABEE}', '\u{ABEF}', NonPrinting),
+    ('\u{ABFA}', '\u{ABFF}', NonPrinting),
+    ('\u{AC00}', '\u{D7A3}', Double),
+    ('\u{D7A4}', '\u{D7AF}', NonPrinting),
+    ('\u{D7B0}', '\u{D7C6}', Zero),
+    ('\u{D7C7}', '\u{D7CA}', NonPrinting),
+    ('\u{D7CB}', '\u{D7FB}', Zero),
+    ('\u{D7FC}', '\u{D7FF}', NonPrinting),
+    ('\u{F900}', '\u{FA6D}', Double),
+    ('\u{FA6E}', '\u{FA6F}', NonPrinting),
+    ('\u{FA70}', '\u{FAD9}', Double),
+    ('\u{FADA}', '\u{FAFF}', NonPrinting),
+    ('\u{FB07}', '\u{FB12}', NonPrinting),
+    ('\u{FB18}', '\u{FB1C}', NonPrinting),
+    ('\u{FB1E}', '\u{FB1E}', Zero),
+    ('\u{FB37}', '\u{FB37}', NonPrinting),
+    ('\u{FB3D}', '\u{FB3D}', NonPrinting),
+    ('\u{FB3F}', '\u{FB3F}', NonPrinting),
+    ('\u{FB42}', '\u{FB42}', NonPrinting),
+    ('\u{FB45}', '\u{FB45}', NonPrinting),
+    ('\u{FBC3}', '\u{FBD2}', NonPrinting),
+    ('\u{FD90}', '\u{FD91}', NonPrinting),
+    ('\u{FDC8}', '\u{FDCE}', NonPrinting),
+    ('\u{FDD0}', '\u{FDEF}', NonPrinting),
+    ('\u{FE00}', '\u{FE0F}', Zero),
+    ('\u{FE10}', '\u{FE19}', Double),
+    ('\u{FE1A}', '\u{FE1F}', NonPrinting),
+    ('\u{FE20}', '\u{FE2F}', Zero),
+    ('\u{FE30}', '\u{FE52}', Double),
+    ('\u{FE53}', '\u{FE53}', NonPrinting),
+    ('\u{FE54}', '\u{FE66}', Double),
+    ('\u{FE67}', '\u{FE67}', NonPrinting),
+    ('\u{FE68}', '\u{FE6B}', Double),
+    ('\u{FE6C}', '\u{FE6F}', NonPrinting),
+    ('\u{FE75}', '\u{FE75}', NonPrinting),
+    ('\u{FEFD}', '\u{FEFE}', NonPrinting),
+    ('\u{FEFF}', '\u{FEFF}', Zero),
+    ('\u{FF00}', '\u{FF00}', NonPrinting),
+    ('\u{FF01}', '\u{FF60}', Double),
+    ('\u{FFBF}', '\u{FFC1}', NonPrinting),
+    ('\u{FFC8}', '\u{FFC9}', NonPrinting),
+    ('\u{FFD0}', '\u{FFD1}', NonPrinting),
+    ('\u{FFD8}', '\u{FFD9}', NonPrinting),
+    ('\u{FFDD}', '\u{FFDF}', NonPrinting),
+    ('\u{FFE0}', '\u{FFE6}', Double),
+    ('\u{FFE7}', '\u{FFE7}', NonPrinting),
+    ('\u{FFEF}', '\u{FFF8}', NonPrinting),
+    ('\u{FFF9}', '\u{FFFB}', Zero),
+    ('\u{FFFE}', '\u{FFFF}', NonPrinting),
+    ('\u{1000C}', '\u{1000C}', NonPrinting),
+    ('\u{10027}', '\u{10027}', NonPrinting),
+    ('\u{1003B}', '\u{1003B}', NonPrinting),
+    ('\u{1003E}', '\u{1003E}', NonPrinting),
+    ('\u{1004E}', '\u{1004F}', NonPrinting),
+    ('\u{1005E}', '\u{1007F}', NonPrinting),
+    ('\u{100FB}', '\u{100FF}', NonPrinting),
+    ('\u{10103}', '\u{10106}', NonPrinting),
+    ('\u{10134}', '\u{10136}', NonPrinting),
+    ('\u{1018F}', '\u{1018F}', NonPrinting),
+    ('\u{1019D}', '\u{1019F}', NonPrinting),
+    ('\u{101A1}', '\u{101CF}', NonPrinting),
+    ('\u{101FD}', '\u{101FD}', Zero),
+    ('\u{101FE}', '\u{1027F}', NonPrinting),
+    ('\u{1029D}', '\u{1029F}', NonPrinting),
+    ('\u{102D1}', '\u{102DF}', NonPrinting),
+    ('\u{102E0}', '\u{102E0}', Zero),
+    ('\u{102FC}', '\u{102FF}', NonPrinting),
+    ('\u{10324}', '\u{1032C}', NonPrinting),
+    ('\u{1034B}', '\u{1034F}', NonPrinting),
+    ('\u{10376}', '\u{1037A}', Zero),
+    ('\u{1037B}', '\u{1037F}', NonPrinting),
+    ('\u{1039E}', '\u{1039E}', NonPrinting),
+    ('\u{103C4}', '\u{103C7}', NonPrinting),
+    ('\u{103D6}', '\u{103FF}', NonPrinting),
+    ('\u{1049E}', '\u{1049F}', NonPrinting),
+    ('\u{104AA}', '\u{104AF}', NonPrinting),
+    ('\u{104D4}', '\u{104D7}', NonPrinting),
+    ('\u{104FC}', '\u{104FF}', NonPrinting),
+    ('\u{10528}', '\u{1052F}', NonPrinting),
+    ('\u{10564}', '\u{1056E}', NonPrinting),
+    ('\u{1057B}', '\u{1057B}', NonPrinting),
+    ('\u{1058B}', '\u{1058B}', NonPrinting),
+    ('\u{10593}', '\u{10593}', NonPrinting),
+    ('\u{10596}', '\u{10596}', NonPrinting),
+    ('\u{105A2}', '\u{105A2}', NonPrinting),
+    ('\u{105B2}', '\u{105B2}', NonPrinting),
+    ('\u{105BA}', '\u{105BA}', NonPrinting),
+    ('\u{105BD}', '\u{105FF}', NonPrinting),
+    ('\u{10737}', '\u{1073F}', NonPrinting),
+    ('\u{10756}', '\u{1075F}', NonPrinting),
+    ('\u{10768}', '\u{1077F}', NonPrinting),
+    ('\u{10786}', '\u{10786}', NonPrinting),
+    ('\u{107B1}', '\u{107B1}', NonPrinting),
+    ('\u{107BB}', '\u{107FF}', NonPrinting),
+    ('\u{10806}', '\u{10807}', NonPrinting),
+    ('\u{10809}', '\u{10809}', NonPrinting),
+    ('\u{10836}', '\u{10836}', NonPrinting),
+    ('\u{10839}', '\u{1083B}', NonPrinting),
+    ('\u{1083D}', '\u{1083E}', NonPrinting),
+    ('\u{10856}', '\u{10856}', NonPrinting),
+    ('\u{1089F}', '\u{108A6}', NonPrinting),
+    ('\u{108B0}', '\u{108DF}', NonPrinting),
+    ('\u{108F3}', '\u{108F3}', NonPrinting),
+    ('\u{108F6}', '\u{108FA}', NonPrinting),
+    ('\u{1091C}', '\u{1091E}', NonPrinting),
+    ('\u{1093A}', '\u{1093E}', NonPrinting),
+    ('\u{10940}', '\u{1097F}', NonPrinting),
+    ('\u{109B8}', '\u{109BB}', NonPrinting),
+    ('\u{109D0}', '\u{109D1}', NonPrinting),
+    ('\u{10A01}', '\u{10A03}', Zero),
+    ('\u{10A04}', '\u{10A04}', NonPrinting),
+    ('\u{10A05}', '\u{10A06}', Zero),
+    ('\u{10A07}', '\u{10A0B}', NonPrinting),
+    ('\u{10A0C}', '\u{10A0F}', Zero),
+    ('\u{10A14}', '\u{10A14}', NonPrinting),
+    ('\u{10A18}', '\u{10A18}', NonPrinting),
+    ('\u{10A36}', '\u{10A37}', NonPrinting),
+    ('\u{10A38}', '\u{10A3A}', Zero),
+    ('\u{10A3B}', '\u{10A3E}', NonPrinting),
+    ('\u{10A3F}', '\u{10A3F}', Zero),
+    ('\u{10A49}', '\u{10A4F}', NonPrinting),
+    ('\u{10A59}', '\u{10A5F}', NonPrinting),
+    ('\u{10AA0}', '\u{10ABF}', NonPrinting),
+    ('\u{10AE5}', '\u{10AE6}', Zero),
+    ('\u{10AE7}', '\u{10AEA}', NonPrinting),
+    ('\u{10AF7}', '\u{10AFF}', NonPrinting),
+    ('\u{10B36}', '\u{10B38}', NonPrinting),
+    ('\u{10B56}', '\u{10B57}', NonPrinting),
+    ('\u{10B73}', '\u{10B77}', NonPrinting),
+    ('\u{10B92}', '\u{10B98}', NonPrinting),
+    ('\u{10B9D}', '\u{10BA8}', NonPrinting),
+    ('\u{10BB0}', '\u{10BFF}', NonPrinting),
+    ('\u{10C49}', '\u{10C7F}', NonPrinting),
+    ('\u{10CB3}', '\u{10CBF}', NonPrinting),
+    ('\u{10CF3}', '\u{10CF9}', NonPrinting),
+    ('\u{10D24}', '\u{10D27}', Zero),
+    ('\u{10D28}', '\u{10D2F}', NonPrinting),
+    ('\u{10D3A}', '\u{10E5F}', NonPrinting),
+    ('\u{10E7F}', '\u{10E7F}', NonPrinting),
+    ('\u{10EAA}', '\u{10EAA}', NonPrinting),
+    ('\u{10EAB}', '\u{10EAC}', Zero),
+    ('\u{10EAE}', '\u{10EAF}', NonPrinting),
+    ('\u{10EB2}', '\u{10EFF}', NonPrinting),
+    ('\u{10F28}', '\u{10F2F}', NonPrinting),
+    ('\u{10F46}', '\u{10F50}', Zero),
+    ('\u{10F5A}', '\u{10F6F}', NonPrinting),
+    ('\u{10F82}', '\u{10F85}', Zero),
+    ('\u{10F8A}', '\u{10FAF}', NonPrinting),
+    ('\u{10FCC}', '\u{10FDF}', NonPrinting),
+    ('\u{10FF7}', '\u{10FFF}', NonPrinting),
+    ('\u{11001}', '\u{11001}', Zero),
+    ('\u{11038}', '\u{11046}', Zero),
+    ('\u{1104E}', '\u{11051}', NonPrinting),
+    ('\u{11070}', '\u{11070}', Zero),
+    ('\u{11073}', '\u{11074}', Zero),
+    ('\u{11076}', '\u{1107E}', NonPrinting),
+    ('\u{1107F}', '\u{11081}', Zero),
+    ('\u{110B3}', '\u{110B6}', Zero),
+    ('\u{110B9}', '\u{110BA}', Zero),
+    ('\u{110C2}', '\u{110C2}', Zero),
+    ('\u{110C3}', '\u{110CC}', NonPrinting),
+    ('\u{110CE}', '\u{110CF}', NonPrinting),
+    ('\u{110E9}', '\u{110EF}', NonPrinting),
+    ('\u{110FA}', '\u{110FF}', NonPrinting),
+    ('\u{11100}', '\u{11102}', Zero),
+    ('\u{11127}', '\u{1112B}', Zero),
+    ('\u{1112D}', '\u{11134}', Zero),
+    ('\u{11135}', '\u{11135}', NonPrinting),
+    ('\u{11148}', '\u{1114F}', NonPrinting),
+    ('\u{11173}', '\u{11173}', Zero),
+    ('\u{11177}', '\u{1117F}', NonPrinting),
+    ('\u{11180}', '\u{11181}', Zero),
+    ('\u{111B6}', '\u{111BE}', Zero),
+    ('\u{111C9}', '\u{111CC}', Zero),
+    ('\u{111CF}', '\u{111CF}', Zero),
+    ('\u{111E0}', '\u{111E0}', NonPrinting),
+    ('\u{111F5}', '\u{111FF}', NonPrinting),
+    ('\u{11212}', '\u{11212}', NonPrinting),
+    ('\u{1122F}', '\u{11231}', Zero),
+    ('\u{11234}', '\u{11234}', Zero),
+    ('\u{11236}', '\u{11237}', Zero),
+    ('\u{1123E}', '\u{1123E}', Zero),
+    ('\u{1123F}', '\u{1127F}', NonPrinting),
+    ('\u{11287}', '\u{11287}', NonPrinting),
+    ('\u{11289}', '\u{11289}', NonPrinting),
+    ('\u{1128E}', '\u{1128E}', NonPrinting),
+    ('\u{1129E}', '\u{1129E}', NonPrinting),
+    ('\u{112AA}', '\u{112AF}', NonPrinting),
+    ('\u{112DF}', '\u{112DF}', Zero),
+    ('\u{112E3}', '\u{112EA}', Zero),
+    ('\u{112EB}', '\u{112EF}', NonPrinting),
+    ('\u{112FA}', '\u{112FF}', NonPrinting),
+    ('\u{11300}', '\u{11301}', Zero),
+    ('\u{11304}', '\u{11304}', NonPrinting),
+    ('\u{1130D}', '\u{1130E}', NonPrinting),
+    ('\u{11311}', '\u{11312}', NonPrinting),
+    ('\u{11329}', '\u{11329}', NonPrinting),
+    ('\u{11331}', '\u{11331}', NonPrinting),
+    ('\u{11334}', '\u{11334}', NonPrinting),
+    ('\u{1133A}', '\u{1133A}', NonPrinting),
+    ('\u{1133B}', '\u{1133C}', Zero),
+    ('\u{11340}', '\u{11340}', Zero),
+    ('\u{11345}', '\u{11346}', NonPrinting),
+    ('\u{11349}', '\u{1134A}', NonPrinting),
+    ('\u{1134E}', '\u{1134F}', NonPrinting),
+    ('\u{11351}', '\u{11356}', NonPrinting),
+    ('\u{11358}', '\u{1135C}', NonPrinting),
+    ('\u{11364}', '\u{11365}', NonPrinting),
+    ('\u{11366}', '\u{1136C}', Zero),
+    ('\u{1136D}', '\u{1136F}', NonPrinting),
+    ('\u{11370}', '\u{11374}', Zero),
+    ('\u{11375}', '\u{113FF}', NonPrinting),
+    ('\u{11438}', '\u{1143F}', Zero),
+    ('\u{11442}', '\u{11444}', Zero),
+    ('\u{11446}', '\u{11446}', Zero),
+    ('\u{1145C}', '\u{1145C}', NonPrinting),
+    ('\u{1145E}', '\u{1145E}', Zero),
+    ('\u{11462}', '\u{1147F}', NonPrinting),
+    ('\u{114B3}', '\u{114B8}', Zero),
+    ('\u{114BA}', '\u{114BA}', Zero),
+    ('\u{114BF}', '\u{114C0}', Zero),
+    ('\u{114C2}', '\u{114C3}', Zero),
+    ('\u{114C8}', '\u{114CF}', NonPrinting),
+    ('\u{114DA}', '\u{1157F}', NonPrinting),
+    ('\u{115B2}', '\u{115B5}', Zero),
+    ('\u{115B6}', '\u{115B7}', NonPrinting),
+    ('\u{115BC}', '\u{115BD}', Zero),
+    ('\u{115BF}', '\u{115C0}', Zero),
+    ('\u{115DC}', '\u{115DD}', Zero),
+    ('\u{115DE}', '\u{115FF}', NonPrinting),
+    ('\u{11633}', '\u{1163A}', Zero),
+    ('\u{1163D}', '\u{1163D}', Zero),
+    ('\u{1163F}', '\u{11640}', Zero),
+    ('\u{11645}', '\u{1164F}', NonPrinting),
+    ('\u{1165A}', '\u{1165F}', NonPrinting),
+    ('\u{1166D}', '\u{1167F}', NonPrinting),
+    ('\u{116AB}', '\u{116AB}', Zero),
+    ('\u{116AD}', '\u{116AD}', Zero),
+    ('\u{116B0}', '\u{116B5}', Zero),
+    ('\u{116B7}', '\u{116B7}', Zero),
+    ('\u{116BA}', '\u{116BF}', NonPrinting),
+    ('\u{116CA}', '\u{116FF}', NonPrinting),
+    ('\u{1171B}', '\u{1171C}', NonPrinting),
+    ('\u{1171D}', '\u{1171F}', Zero),
+    ('\u{11722}', '\u{11725}', Zero),
+    ('\u{11727}', '\u{1172B}', Zero),
+    ('\u{1172C}', '\u{1172F}', NonPrinting),
+    ('\u{11747}', '\u{117FF}', NonPrinting),
+    ('\u{1182F}', '\u{11837}', Zero),
+    ('\u{11839}', '\u{1183A}', Zero),
+    ('\u{1183C}', '\u{1189F}', NonPrinting),
+    ('\u{118F3}', '\u{118FE}', NonPrinting),
+    ('\u{11907}', '\u{11908}', NonPrinting),
+    ('\u{1190A}', '\u{1190B}', NonPrinting),
+    ('\u{11914}', '\u{11914}', NonPrinting),
+    ('\u{11917}', '\u{11917}', NonPrinting),
+    ('\u{11936}', '\u{11936}', NonPrinting),
+    ('\u{11939}', '\u{1193A}', NonPrinting),
+    ('\u{1193B}', '\u{1193C}', Zero),
+    ('\u{1193E}', '\u{1193E}', Zero),
+    ('\u{11943}', '\u{11943}', Zero),
+    ('\u{11947}', '\u{1194F}', NonPrinting),
+    ('\u{1195A}', '\u{1199F}', NonPrinting),
+    ('\u{119A8}', '\u{119A9}', NonPrinting),
+    ('\u{119D4}', '\u{119D7}', Zero),
+    ('\u{119D8}', '\u{119D9}', NonPrinting),
+    ('\u{119DA}', '\u{119DB}', Zero),
+    ('\u{119E0}', '\u{119E0}', Zero),
+    ('\u{119E5}', '\u{119FF}', NonPrinting),
+    ('\u{11A01}', '\u{11A0A}', Zero),
+    ('\u{11A33}', '\u{11A38}', Zero),
+    ('\u{11A3B}', '\u{11A3E}', Zero),
+    ('\u{11A47}', '\u{11A47}', Zero),
+    ('\u{11A48}', '\u{11A4F}', NonPrinting),
+    ('\u{11A51}', '\u{11A56}', Zero),
+    ('\u{11A59}', '\u{11A5B}', Zero),
+    ('\u{11A8A}', '\u{11A96}', Zero),
+    ('\u{11A98}', '\u{11A99}', Zero),
+    ('\u{11AA3}', '\u{11AAF}', NonPrinting),
+    ('\u{11AF9}', '\u{11BFF}', NonPrinting),
+    ('\u{11C09}', '\u{11C09}', NonPrinting),
+    ('\u{11C30}', '\u{11C36}', Zero),
+    ('\u{11C37}', '\u{11C37}', NonPrinting),
+    ('\u{11C38}', '\u{11C3D}', Zero),
+    ('\u{11C3F}', '\u{11C3F}', Zero),
+    ('\u{11C46}', '\u{11C4F}', NonPrinting),
+    ('\u{11C6D}', '\u{11C6F}', NonPrinting),
+    ('\u{11C90}', '\u{11C91}', NonPrinting),
+    ('\u{11C92}', '\u{11CA7}', Zero),
+    ('\u{11CA8}', '\u{11CA8}', NonPrinting),
+    ('\u{11CAA}', '\u{11CB0}', Zero),
+    ('\u{11CB2}', '\u{11CB3}', Zero),
+    ('\u{11CB5}', '\u{11CB6}', Zero),
+    ('\u{11CB7}', '\u{11CFF}', NonPrinting),
+    ('\u{11D07}', '\u{11D07}', NonPrinting),
+    ('\u{11D0A}', '\u{11D0A}', NonPrinting),
+    ('\u{11D31}', '\u{11D36}', Zero),
+    ('\u{11D37}', '\u{11D39}', NonPrinting),
+    ('\u{11D3A}', '\u{11D3A}', Zero),
+    ('\u{11D3B}', '\u{11D3B}', NonPrinting),
+    ('\u{11D3C}', '\u{11D3D}', Zero),
+    ('\u{11D3E}', '\u{11D3E}', NonPrinting),
+    ('\u{11D3F}', '\u{11D45}', Zero),
+    ('\u{11D47}', '\u{11D47}', Zero),
+    ('\u{11D48}', '\u{11D4F}', NonPrinting),
+    ('\u{11D5A}', '\u{11D5F}', NonPrinting),
+    ('\u{11D66}', '\u{11D66}', NonPrinting),
+    ('\u{11D69}', '\u{11D69}', NonPrinting),
+    ('\u{11D8F}', '\u{11D8F}', NonPrinting),
+    ('\u{11D90}', '\u{11D91}', Zero),
+    ('\u{11D92}', '\u{11D92}', NonPrinting),
+    ('\u{11D95}', '\u{11D95}', Zero),
+    ('\u{11D97}', '\u{11D97}', Zero),
+    ('\u{11D99}', '\u{11D9F}', NonPrinting),
+    ('\u{11DAA}', '\u{11EDF}', NonPrinting),
+    ('\u{11EF3}', '\u{11EF4}', Zero),
+    ('\u{11EF9}', '\u{11FAF}', NonPrinting),
+    ('\u{11FB1}', '\u{11FBF}', NonPrinting),
+    ('\u{11FF2}', '\u{11FFE}', NonPrinting),
+    ('\u{1239A}', '\u{123FF}', NonPrinting),
+    ('\u{1246F}', '\u{1246F}', NonPrinting),
+    ('\u{12475}', '\u{1247F}', NonPrinting),
+    ('\u{12544}', '\u{12F8F}', NonPrinting),
+    ('\u{12FF3}', '\u{12FFF}', NonPrinting),
+    ('\u{1342F}', '\u{1342F}', NonPrinting),
+    ('\u{13430}', '\u{13438}', Zero),
+    ('\u{13439}', '\u{143FF}', NonPrinting),
+    ('\u{14647}', '\u{167FF}', NonPrinting),
+    ('\u{16A39}', '\u{16A3F}', NonPrinting),
+    ('\u{16A5F}', '\u{16A5F}', NonPrinting),
+    ('\u{16A6A}', '\u{16A6D}', NonPrinting),
+    ('\u{16ABF}', '\u{16ABF}', NonPrinting),
+    ('\u{16ACA}', '\u{16ACF}', NonPrinting),
+    ('\u{16AEE}', '\u{16AEF}', NonPrinting),
+    ('\u{16AF0}', '\u{16AF4}', Zero),
+    ('\u{16AF6}', '\u{16AFF}', NonPrinting),
+    ('\u{16B30}', '\u{16B36}', Zero),
+    ('\u{16B46}', '\u{16B4F}', NonPrinting),
+    ('\u{16B5A}', '\u{16B5A}', NonPrinting),
+    ('\u{16B62}', '\u{16B62}', NonPrinting),
+    ('\u{16B78}', '\u{16B7C}', NonPrinting),
+    ('\u{16B90}', '\u{16E3F}', NonPrinting),
+    ('\u{16E9B}', '\u{16EFF}', NonPrinting),
+    ('\u{16F4B}', '\u{16F4E}', NonPrinting),
+    ('\u{16F4F}', '\u{16F4F}', Zero),
+    ('\u{16F88}', '\u{16F8E}', NonPrinting),
+    ('\u{16F8F}', '\u{16F92}', Zero),
+    ('\u{16FA0}', '\u{16FDF}', NonPrinting),
+    ('\u{16FE0}', '\u{16FE3}', Double),
+    ('\u{16FE4}', '\u{16FE4}', Zero),
+    ('\u{16FE5}', '\u{16FEF}', NonPrinting),
+    ('\u{16FF0}', '\u{16FF1}', Double),
+    ('\u{16FF2}', '\u{16FFF}', NonPrinting),
+    ('\u{17000}', '\u{187F7}', Double),
+    ('\u{187F8}', '\u{187FF}', NonPrinting),
+    ('\u{18800}', '\u{18CD5}', Double),
+    ('\u{18CD6}', '\u{18CFF}', NonPrinting),
+    ('\u{18D00}', '\u{18D08}', Double),
+    ('\u{18D09}', '\u{1AFEF}', NonPrinting),
+    ('\u{1AFF0}', '\u{1AFF3}', Double),
+    ('\u{1AFF4}', '\u{1AFF4}', NonPrinting),
+    ('\u{1AFF5}', '\u{1AFFB}', Double),
+    ('\u{1AFFC}', '\u{1AFFC}', NonPrinting),
+    ('\u{1AFFD}', '\u{1AFFE}', Double),
+    ('\u{1AFFF}', '\u{1AFFF}', NonPrinting),
+    ('\u{1B000}', '\u{1B122}', Double),
+    ('\u{1B123}', '\u{1B14F}', NonPrinting),
+    ('\u{1B150}', '\u{1B152}', Double),
+    ('\u{1B153}', '\u{1B163}', NonPrinting),
+    ('\u{1B164}', '\u{1B167}', Double),
+    ('\u{1B168}', '\u{1B16F}', NonPrinting),
+    ('\u{1B170}', '\u{1B2FB}', Double),
+    ('\u{1B2FC}', '\u{1BBFF}', NonPrinting),
+    ('\u{1BC6B}', '\u{1BC6F}', NonPrinting),
+    ('\u{1BC7D}', '\u{1BC7F}', NonPrinting),
+    ('\u{1BC89}', '\u{1BC8F}', NonPrinting),
+    ('\u{1BC9A}', '\u{1BC9B}', NonPrinting),
+    ('\u{1BC9D}', '\u{1BC9E}', Zero),
+    ('\u{1BCA0}', '\u{1BCA3}', Zero),
+    ('\u{1BCA4}', '\u{1CEFF}', NonPrinting),
+    ('\u{1CF00}', '\u{1CF2D}', Zero),
+    ('\u{1CF2E}', '\u{1CF2F}', NonPrinting),
+    ('\u{1CF30}', '\u{1CF46}', Zero),
+    ('\u{1CF47}', '\u{1CF4F}', NonPrinting),
+    ('\u{1CFC4}', '\u{1CFFF}', NonPrinting),
+    ('\u{1D0F6}', '\u{1D0FF}', NonPrinting),
+    ('\u{1D127}', '\u{1D128}', NonPrinting),
+    ('\u{1D167}', '\u{1D169}', Zero),
+    ('\u{1D173}', '\u{1D182}', Zero),
+    ('\u{1D185}', '\u{1D18B}', Zero),
+    ('\u{1D1AA}', '\u{1D1AD}', Zero),
+    ('\u{1D1EB}', '\u{1D1FF}', NonPrinting),
+    ('\u{1D242}', '\u{1D244}', Zero),
+    ('\u{1D246}', '\u{1D2DF}', NonPrinting),
+    ('\u{1D2F4}', '\u{1D2FF}', NonPrinting),
+    ('\u{1D357}', '\u{1D35F}', NonPrinting),
+    ('\u{1D379}', '\u{1D3FF}', NonPrinting),
+    ('\u{1D455}', '\u{1D455}', NonPrinting),
+    ('\u{1D49D}', '\u{1D49D}', NonPrinting),
+    ('\u{1D4A0}', '\u{1D4A1}', NonPrinting),
+    ('\u{1D4A3}', '\u{1D4A4}', NonPrinting),
+    ('\u{1D4A7}', '\u{1D4A8}', NonPrinting),
+    ('\u{1D4AD}', '\u{1D4AD}', NonPrinting),
+    ('\u{1D4BA}', '\u{1D4BA}', NonPrinting),
+    ('\u{1D4BC}', '\u{1D4BC}', NonPrinting),
+    ('\u{1D4C4}', '\u{1D4C4}', NonPrinting),
+    ('\u{1D506}', '\u{1D506}', NonPrinting),
+    ('\u{1D50B}', '\u{1D50C}', NonPrinting),
+    ('\u{1D515}', '\u{1D515}', NonPrinting),
+    ('\u{1D51D}', '\u{1D51D}', NonPrinting),
+    ('\u{1D53A}', '\u{1D53A}', NonPrinting),
+    ('\u{1D53F}', '\u{1D53F}', NonPrinting),
+    ('\u{1D545}', '\u{1D545}', NonPrinting),
+    ('\u{1D547}', '\u{1D549}', NonPrinting),
+    ('\u{1D551}', '\u{1D551}', NonPrinting),
+    ('\u{1D6A6}', '\u{1D6A7}', NonPrinting),
+    ('\u{1D7CC}', '\u{1D7CD}', NonPrinting),
+    ('\u{1DA00}', '\u{1DA36}', Zero),
+    ('\u{1DA3B}', '\u{1DA6C}', Zero),
+    ('\u{1DA75}', '\u{1DA75}', Zero),
+    ('\u{1DA84}', '\u{1DA84}', Zero),
+    ('\u{1DA8C}', '\u{1DA9A}', NonPrinting),
+    ('\u{1DA9B}', '\u{1DA9F}', Zero),
+    ('\u{1DAA0}', '\u{1DAA0}', NonPrinting),
+    ('\u{1DAA1}', '\u{1DAAF}', Zero),
+    ('\u{1DAB0}', '\u{1DEFF}', NonPrinting),
+    ('\u{1DF1F}', '\u{1DFFF}', NonPrinting),
+    ('\u{1E000}', '\u{1E006}', Zero),
+    ('\u{1E007}', '\u{1E007}', NonPrinting),
+    ('\u{1E008}', '\u{1E018}', Zero),
+    ('\u{1E019}', '\u{1E01A}', NonPrinting),
+    ('\u{1E01B}', '\u{1E021}', Zero),
+    ('\u{1E022}', '\u{1E022}', NonPrinting),
+    ('\u{1E023}', '\u{1E024}', Zero),
+    ('\u{1E025}', '\u{1E025}', NonPrinting),
+    ('\u{1E026}', '\u{1E02A}', Zero),
+    ('\u{1E02B}', '\u{1E0FF}', NonPrinting),
+    ('\u{1E12D}', '\u{1E12F}', NonPrinting),
+    ('\u{1E130}', '\u{1E136}', Zero),
+    ('\u{1E13E}', '\u{1E13F}', NonPrinting),
+    ('\u{1E14A}', '\u{1E14D}', NonPrinting),
+    ('\u{1E150}', '\u{1E28F}', NonPrinting),
+    ('\u{1E2AE}', '\u{1E2AE}', Zero),
+    ('\u{1E2AF}', '\u{1E2BF}', NonPrinting),
+    ('\u{1E2EC}', '\u{1E2EF}', Zero),
+    ('\u{1E2FA}', '\u{1E2FE}', NonPrinting),
+    ('\u{1E300}', '\u{1E7DF}', NonPrinting),
+    ('\u{1E7E7}', '\u{1E7E7}', NonPrinting),
+    ('\u{1E7EC}', '\u{1E7EC}', NonPrinting),
+    ('\u{1E7EF}', '\u{1E7EF}', NonPrinting),
+    ('\u{1E7FF}', '\u{1E7FF}', NonPrinting),
+    ('\u{1E8C5}', '\u{1E8C6}', NonPrinting),
+    ('\u{1E8D0}', '\u{1E8D6}', Zero),
+    ('\u{1E8D7}', '\u{1E8FF}', NonPrinting),
+    ('\u{1E944}', '\u{1E94A}', Zero),
+    ('\u{1E94C}', '\u{1E94F}', NonPrinting),
+    ('\u{1E95A}', '\u{1E95D}', NonPrinting),
+    ('\u{1E960}', '\u{1EC70}', NonPrinting),
+    ('\u{1ECB5}', '\u{1ED00}', NonPrinting),
+    ('\u{1ED3E}', '\u{1EDFF}', NonPrinting),
+    ('\u{1EE04}', '\u{1EE04}', NonPrinting),
+    ('\u{1EE20}', '\u{1EE20}', NonPrinting),
+    ('\u{1EE23}', '\u{1EE23}', NonPrinting),
+    ('\u{1EE25}', '\u{1EE26}', NonPrinting),
+    ('\u{1EE28}', '\u{1EE28}', NonPrinting),
+    ('\u{1EE33}', '\u{1EE33}', NonPrinting),
+    ('\u{1EE38}', '\u{1EE38}', NonPrinting),
+    ('\u{1EE3A}', '\u{1EE3A}', NonPrinting),
+    ('\u{1EE3C}', '\u{1EE41}', NonPrinting),
+    ('\u{1EE43}', '\u{1EE46}', NonPrinting),
+    ('\u{1EE48}', '\u{1EE48}', NonPrinting),
+    ('\u{1EE4A}', '\u{1EE4A}', NonPrinting),
+    ('\u{1EE4C}', '\u{1EE4C}', NonPrinting),
+    ('\u{1EE50}', '\u{1EE50}', NonPrinting),
+    ('\u{1EE53}', '\u{1EE53}', NonPrinting),
+    ('\u{1EE55}', '\u{1EE56}', NonPrinting),
+    ('\u{1EE58}', '\u{1EE58}', NonPrinting),
+    ('\u{1EE5A}', '\u{1EE5A}', NonPrinting),
+    ('\u{1EE5C}', '\u{1EE5C}', NonPrinting),
+    ('\u{1EE5E}', '\u{1EE5E}', NonPrinting),
+    ('\u{1EE60}', '\u{1EE60}', NonPrinting),
+    ('\u{1EE63}', '\u{1EE63}', NonPrinting),
+    ('\u{1EE65}', '\u{1EE66}', NonPrinting),
+    ('\u{1EE6B}', '\u{1EE6B}', NonPrinting),
+    ('\u{1EE73}', '\u{1EE73}', NonPrinting),
+    ('\u{1EE78}', '\u{1EE78}', NonPrinting),
+    ('\u{1EE7D}', '\u{1EE7D}', NonPrinting),
+    ('\u{1EE7F}', '\u{1EE7F}', NonPrinting),
+    ('\u{1EE8A}', '\u{1EE8A}', NonPrinting),
+    ('\u{1EE9C}', '\u{1EEA0}', NonPrinting),
+    ('\u{1EEA4}', '\u{1EEA4}', NonPrinting),
+    ('\u{1EEAA}', '\u{1EEAA}', NonPrinting),
+    ('\u{1EEBC}', '\u{1EEEF}', NonPrinting),
+    ('\u{1EEF2}', '\u{1EFFF}', NonPrinting),
+    ('\u{1F004}', '\u{1F004}', Double),
+    ('\u{1F02C}', '\u{1F02F}', NonPrinting),
+    ('\u{1F094}', '\u{1F09F}', NonPrinting),
+    ('\u{1F0AF}', '\u{1F0B0}', NonPrinting),
+    ('\u{1F0C0}', '\u{1F0C0}', NonPrinting),
+    ('\u{1F0CF}', '\u{1F0CF}', Double),
+    ('\u{1F0D0}', '\u{1F0D0}', NonPrinting),
+    ('\u{1F0F6}', '\u{1F0FF}', NonPrinting),
+    ('\u{1F18E}', '\u{1F18E}', Double),
+    ('\u{1F191}', '\u{1F19A}', Double),
+    ('\u{1F1AE}', '\u{1F1E5}', NonPrinting),
+    ('\u{1F200}', '\u{1F202}', Double),
+    ('\u{1F203}', '\u{1F20F}', NonPrinting),
+    ('\u{1F210}', '\u{1F23B}', Double),
+    ('\u{1F23C}', '\u{1F23F}', NonPrinting),
+    ('\u{1F240}', '\u{1F248}', Double),
+    ('\u{1F249}', '\u{1F24F}', NonPrinting),
+    ('\u{1F250}', '\u{1F251}', Double),
+    ('\u{1F252}', '\u{1F25F}', NonPrinting),
+    ('\u{1F260}', '\u{1F265}', Double),
+    ('\u{1F266}', '\u{1F2FF}', NonPrinting),
+    ('\u{1F300}', '\u{1F320}', Double),
+    ('\u{1F32D}', '\u{1F335}', Double),
+    ('\u{1F337}', '\u{1F37C}', Double),
+    ('\u{1F37E}', '\u{1F393}', Double),
+    ('\u{1F3A0}', '\u{1F3CA}', Double),
+    ('\u{1F3CF}', '\u{1F3D3}', Double),
+    ('\u{1F3E0}', '\u{1F3F0}', Double),
+    ('\u{1F3F4}', '\u{1F3F4}', Double),
+    ('\u{1F3F8}', '\u{1F43E}', Double),
+    ('\u{1F440}', '\u{1F440}', Double),
+    ('\u{1F442}', '\u{1F4FC}', Double),
+    ('\u{1F4FF}', '\u{1F53D}', Double),
+    ('\u{1F54B}', '\u{1F54E}', Double),
+    ('\u{1F550}', '\u{1F567}', Double),
+    ('\u{1F57A}', '\u{1F57A}', Double),
+    ('\u{1F595}', '\u{1F596}', Double),
+    ('\u{1F5A4}', '\u{1F5A4}', Double),
+    ('\u{1F5FB}', '\u{1F64F}', Double),
+    ('\u{1F680}', '\u{1F6C5}', Double),
+    ('\u{1F6CC}', '\u{1F6CC}', Double),
+    ('\u{1F6D0}', '\u{1F6D2}', Double),
+    ('\u{1F6D5}', '\u{1F6D7}', Double),
+    ('\u{1F6D8}', '\u{1F6DC}', NonPrinting),
+    ('\u{1F6DD}', '\u{1F6DF}', Double),
+    ('\u{1F6EB}', '\u{1F6EC}', Double),
+    ('\u{1F6ED}', '\u{1F6EF}', NonPrinting),
+    ('\u{1F6F4}', '\u{1F6FC}', Double),
+    ('\u{1F6FD}', '\u{1F6FF}', NonPrinting),
+    ('\u{1F774}', '\u{1F77F}', NonPrinting),
+    ('\u{1F7D9}', '\u{1F7DF}', NonPrinting),
+    ('\u{1F7E0}', '\u{1F7EB}', Double),
+    ('\u{1F7EC}', '\u{1F7EF}', NonPrinting),
+    ('\u{1F7F0}', '\u{1F7F0}', Double),
+    ('\u{1F7F1}', '\u{1F7FF}', NonPrinting),
+    ('\u{1F80C}', '\u{1F80F}', NonPrinting),
+    ('\u{1F848}', '\u{1F84F}', NonPrinting),
+    ('\u{1F85A}', '\u{1F85F}', NonPrinting),
+    ('\u{1F888}', '\u{1F88F}', NonPrinting),
+    ('\u{1F8AE}', '\u{1F8AF}', NonPrinting),
+    ('\u{1F8B2}', '\u{1F8FF}', NonPrinting),
+    ('\u{1F90C}', '\u{1F93A}', Double),
+    ('\u{1F93C}', '\u{1F945}', Double),
+    ('\u{1F947}', '\u{1F9FF}', Double),
+    ('\u{1FA54}', '\u{1FA5F}', NonPrinting),
+    ('\u{1FA6E}', '\u{1FA6F}', NonPrinting),
+    ('\u{1FA70}', '\u{1FA74}', Double),
+    ('\u{1FA75}', '\u{1FA77}', NonPrinting),
+    ('\u{1FA78}', '\u{1FA7C}', Double),
+    ('\u{1FA7D}', '\u{1FA7F}', NonPrinting),
+    ('\u{1FA80}', '\u{1FA86}', Double),
+    ('\u{1FA87}', '\u{1FA8F}', NonPrinting),
+    ('\u{1FA90}', '\u{1FAAC}', Double),
+    ('\u{1FAAD}', '\u{1FAAF}', NonPrinting),
+    ('\u{1FAB0}', '\u{1FABA}', Double),
+    ('\u{1FABB}', '\u{1FABF}', NonPrinting),
+    ('\u{1FAC0}', '\u{1FAC5}', Double),
+    ('\u{1FAC6}', '\u{1FACF}', NonPrinting),
+    ('\u{1FAD0}', '\u{1FAD9}', Double),
+    ('\u{1FADA}', '\u{1FADF}', NonPrinting),
+    ('\u{1FAE0}', '\u{1FAE7}', Double),
+    ('\u{1FAE8}', '\u{1FAEF}', NonPrinting),
+    ('\u{1FAF0}', '\u{1FAF6}', Double),
+    ('\u{1FAF7}', '\u{1FAFF}', NonPrinting),
+    ('\u{1FB93}', '\u{1FB93}', NonPrinting),
+    ('\u{1FBCB}', '\u{1FBEF}', NonPrinting),
+    ('\u{1FBFA}', '\u{1FFFF}', NonPrinting),
+    ('\u{20000}', '\u{2A6DF}', Double),
+    ('\u{2A6E0}', '\u{2A6FF}', NonPrinting),
+    ('\u{2A700}', '\u{2B738}', Double),
+    ('\u{2B739}', '\u{2B73F}', NonPrinting),
+    ('\u{2B740}', '\u{2B81D}', Double),
+    ('\u{2B81E}', '\u{2B81F}', NonPrinting),
+    ('\u{2B820}', '\u{2CEA1}', Double),
+    ('\u{2CEA2}', '\u{2CEAF}', NonPrinting),
+    ('\u{2CEB0}', '\u{2EBE0}', Double),
+    ('\u{2EBE1}', '\u{2F7FF}', NonPrinting),
+    ('\u{2F800}', '\u{2FA1D}', Double),
+    ('\u{2FA1E}', '\u{2FFFF}', NonPrinting),
+    ('\u{30000}', '\u{3134A}', Double),
+    ('\u{3134B}', '\u{E0000}', NonPrinting),
+    ('\u{E0001}', '\u{E0001}', Zero),
+    ('\u{E0002}', '\u{E001F}', NonPrinting),
+    ('\u{E0020}', '\u{E007F}', Zero),
+    ('\u{E0080}', '\u{E00FF}', NonPrinting),
+    ('\u{E0100}', '\u{E01EF}', Zero),
+    ('\u{E01F0}', '\u{EFFFF}', NonPrinting),
+    ('\u{FFFFE}', '\u{FFFFF}', NonPrinting),
+    ('\u{10FFFE}', '\u{10FFFF}', NonPrinting),
+];
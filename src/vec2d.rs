@@ -13,6 +13,43 @@ impl Display for Error {
 }
 impl std::error::Error for Error {}
 
+/// Errors produced when reading or writing the binary `Vec2d` layout
+#[cfg(feature = "byte_readers")]
+#[derive(Debug)]
+pub enum BytesError<E> {
+    Io(std::io::Error),
+    InvalidMagic([u8; 4]),
+    InvalidEndian(u8),
+    /// Number of elements doesn't match `width * height`
+    SizeMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// `width * height` overflows `usize`
+    SizeOverflow {
+        width: u64,
+        height: u64,
+    },
+    Element {
+        index: usize,
+        source: E,
+    },
+}
+#[cfg(feature = "byte_readers")]
+impl<E: Debug> Display for BytesError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+#[cfg(feature = "byte_readers")]
+impl<E: Debug> std::error::Error for BytesError<E> {}
+#[cfg(feature = "byte_readers")]
+impl<E> From<std::io::Error> for BytesError<E> {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
 pub struct SingleColumnIter<'a, T> {
     v: &'a Vec2d<T>,
     col: usize,
@@ -303,6 +340,108 @@ where
     }
 }
 
+#[cfg(feature = "byte_readers")]
+impl<T> Vec2d<T> {
+    /// Identifies the start of an encoded `Vec2d`
+    pub const MAGIC: [u8; 4] = *b"V2D\0";
+}
+
+#[cfg(feature = "byte_readers")]
+impl<T> Vec2d<T>
+where
+    T: crate::byte_readers::ToBytes,
+{
+    /// Write as binary, elements are written in row-major order
+    ///
+    /// ---
+    /// Layout:
+    /// - magic: `Vec2d::MAGIC`
+    /// - width: `u64` little endian
+    /// - height: `u64` little endian
+    /// - element endianness: `u8`, `0` for little and `1` for big
+    /// - elements: `width * height` elements, each written with `endian`
+    pub fn write_bytes(
+        &self,
+        mut data: impl std::io::Write,
+        endian: crate::byte_readers::Endian,
+    ) -> Result<(), BytesError<T::Error>> {
+        use crate::byte_readers::Endian;
+
+        if self.len() != self.size() {
+            return Err(BytesError::SizeMismatch {
+                expected: self.size(),
+                actual: self.len(),
+            });
+        }
+
+        data.write_all(&Self::MAGIC)?;
+        data.write_all(&(self.width as u64).to_le_bytes())?;
+        data.write_all(&(self.height as u64).to_le_bytes())?;
+        data.write_all(&[match endian {
+            Endian::Little => 0,
+            Endian::Big => 1,
+        }])?;
+
+        for (index, item) in self.iter().enumerate() {
+            let res = match endian {
+                Endian::Little => item.to_bytes_le(&mut data),
+                Endian::Big => item.to_bytes_be(&mut data),
+            };
+            res.map_err(|source| BytesError::Element { index, source })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "byte_readers")]
+impl<T> Vec2d<T>
+where
+    T: crate::byte_readers::FromBytes,
+{
+    /// Read from the layout written by `Vec2d::write_bytes`
+    ///
+    /// ---
+    /// **NOTE**: Input that ends before `width * height` elements is reported
+    /// as `BytesError::Element` for the first missing one, with the error
+    /// `T` gives for it, `UnexpectedEof` for the primitives
+    pub fn read_bytes(mut data: impl std::io::Read) -> Result<Self, BytesError<T::Error>> {
+        use crate::byte_readers::{Endian, FromBytes};
+
+        let mut magic = [0u8; 4];
+        data.read_exact(&mut magic)?;
+        if magic != Self::MAGIC {
+            return Err(BytesError::InvalidMagic(magic));
+        }
+
+        let width = u64::from_bytes_le(&mut data)?;
+        let height = u64::from_bytes_le(&mut data)?;
+        let endian = match u8::from_bytes_le(&mut data)? {
+            0 => Endian::Little,
+            1 => Endian::Big,
+            x => return Err(BytesError::InvalidEndian(x)),
+        };
+
+        let overflow = || BytesError::SizeOverflow { width, height };
+        let w = usize::try_from(width).map_err(|_| overflow())?;
+        let h = usize::try_from(height).map_err(|_| overflow())?;
+        let size = w.checked_mul(h).ok_or_else(overflow)?;
+
+        // Don't trust the header for the allocation size
+        let mut this = Self::new(w, h);
+        for index in 0..size {
+            let item = match endian {
+                Endian::Little => T::from_bytes_le(&mut data),
+                Endian::Big => T::from_bytes_be(&mut data),
+            };
+            this.data
+                .push(item.map_err(|source| BytesError::Element { index, source })?);
+        }
+
+        Ok(this)
+    }
+}
+
 impl<T> PartialEq for Vec2d<T>
 where
     T: PartialEq,
@@ -546,4 +685,100 @@ mod tests {
             "Vec2d { width: 2, height: 4, data: [[1, 2], [3, 4], [5, 6], [7, 8]] }"
         )
     }
+
+    #[cfg(feature = "byte_readers")]
+    #[test]
+    fn bytes_round_trip_test() -> AnyResult<()> {
+        use crate::byte_readers::Endian;
+
+        #[rustfmt::skip]
+        let v = Vec2d::from_iter(3, 2, [
+            1i32, -2, 3,
+            4, 5, -6,
+        ])?;
+
+        for endian in [Endian::Little, Endian::Big] {
+            let mut buf = Vec::new();
+            v.write_bytes(&mut buf, endian)?;
+            assert_eq!(buf.len(), 4 + 8 + 8 + 1 + 6 * 4);
+
+            let decoded = Vec2d::<i32>::read_bytes(buf.as_slice())?;
+            assert_eq!(decoded, v);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "byte_readers")]
+    #[test]
+    fn bytes_layout_test() -> AnyResult<()> {
+        use crate::byte_readers::Endian;
+
+        let v = Vec2d::from_iter(1, 2, [1u16, 2])?;
+        let mut buf = Vec::new();
+        v.write_bytes(&mut buf, Endian::Big)?;
+
+        #[rustfmt::skip]
+        assert_eq!(buf, [
+            b'V', b'2', b'D', 0,
+            1, 0, 0, 0, 0, 0, 0, 0,
+            2, 0, 0, 0, 0, 0, 0, 0,
+            1,
+            0, 1,
+            0, 2,
+        ]);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "byte_readers")]
+    #[test]
+    fn bytes_size_mismatch_test() {
+        use crate::byte_readers::Endian;
+
+        let v = Vec2d::from_iter(2, 2, [1u8, 2, 3]).unwrap();
+        let res = v.write_bytes(Vec::new(), Endian::Little);
+        assert!(matches!(
+            res,
+            Err(BytesError::SizeMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+
+        // Header claims 2x2 but only 3 elements follow
+        let mut buf = Vec::new();
+        buf.extend(Vec2d::<u8>::MAGIC);
+        buf.extend(2u64.to_le_bytes());
+        buf.extend(2u64.to_le_bytes());
+        buf.extend([0, 1, 2, 3]);
+        let res = Vec2d::<u8>::read_bytes(buf.as_slice());
+        assert!(matches!(
+            res,
+            Err(BytesError::Element { index: 3, source }) if source.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[cfg(feature = "byte_readers")]
+    #[test]
+    fn bytes_invalid_header_test() {
+        let res = Vec2d::<u8>::read_bytes(&b"NOPE"[..]);
+        assert!(matches!(res, Err(BytesError::InvalidMagic(x)) if &x == b"NOPE"));
+
+        let mut buf = Vec::new();
+        buf.extend(Vec2d::<u8>::MAGIC);
+        buf.extend(0u64.to_le_bytes());
+        buf.extend(0u64.to_le_bytes());
+        buf.push(7);
+        let res = Vec2d::<u8>::read_bytes(buf.as_slice());
+        assert!(matches!(res, Err(BytesError::InvalidEndian(7))));
+
+        let mut buf = Vec::new();
+        buf.extend(Vec2d::<u8>::MAGIC);
+        buf.extend(u64::MAX.to_le_bytes());
+        buf.extend(u64::MAX.to_le_bytes());
+        buf.push(0);
+        let res = Vec2d::<u8>::read_bytes(buf.as_slice());
+        assert!(matches!(res, Err(BytesError::SizeOverflow { .. })));
+    }
 }